@@ -9,4 +9,5 @@ criterion_main! {
     benchmarks::anti_aliasing::benches,
     benchmarks::lighting::benches,
     benchmarks::meshes::benches,
+    benchmarks::transforms::benches,
 }
@@ -2,12 +2,15 @@ use criterion::{black_box, criterion_group, Criterion};
 use ray_tracer::core::*;
 use ray_tracer::renderer::{render, Camera, Samples};
 use ray_tracer::scene::{Light, Material, MaterialKind, Object, World};
+use ray_tracer::wavefront_parser::WavefrontParser;
 use std::f64::consts::PI;
+use std::path::Path;
 
 criterion_group! {
     benches,
     single_ray_many_reflective_refractive_objects,
-    many_objects_full_scene
+    many_objects_full_scene,
+    optimised_groups_against_bvh
 }
 
 fn many_objects_full_scene(c: &mut Criterion) {
@@ -171,3 +174,34 @@ fn single_ray_many_reflective_refractive_objects(c: &mut Criterion) {
         },
     );
 }
+
+fn optimised_groups_against_bvh(c: &mut Criterion) {
+    let mut group = c.benchmark_group("intersect 100x100 rays with suzanne high poly");
+    let parser = WavefrontParser::new(Path::new(env!("CARGO_MANIFEST_DIR")).join("meshes"));
+
+    let rays = (0..100)
+        .flat_map(|x| (0..100).map(move |y| (x, y)))
+        .map(|(x, y)| {
+            let origin = Point3D::new(0.0, 0.0, -5.0);
+            let target = Point3D::new(x as f64 / 50.0 - 1.0, y as f64 / 50.0 - 1.0, 0.0);
+
+            Ray::new(origin, (target - origin).normalised())
+        })
+        .collect::<Vec<_>>();
+
+    let optimised = parser.load("suzanne high poly.obj").unwrap().optimised(4);
+    group.bench_function("optimised", |b| {
+        b.iter(|| {
+            rays.iter()
+                .for_each(|ray| drop(black_box(optimised.intersect(ray))))
+        })
+    });
+
+    let bvh = parser.load("suzanne high poly.obj").unwrap().bvh(4);
+    group.bench_function("bvh", |b| {
+        b.iter(|| {
+            rays.iter()
+                .for_each(|ray| drop(black_box(bvh.intersect(ray))))
+        })
+    });
+}
@@ -23,7 +23,6 @@ fn lighting_a_single_object_with_an_area_light(c: &mut Criterion) {
             Vector3D::new(0.0, 1.0, 0.0),
             nonzero_ext::nonzero!(4u8),
             nonzero_ext::nonzero!(4u8),
-            0,
         ));
         world.add(Object::sphere().with_material(Material {
             kind: MaterialKind::Solid(Colour::WHITE),
@@ -61,7 +60,6 @@ fn lighting_multiple_objects_with_an_area_light(c: &mut Criterion) {
             Vector3D::new(0.0, 2.0, 0.0),
             nonzero_ext::nonzero!(4u8),
             nonzero_ext::nonzero!(4u8),
-            7859535925052243674,
         ));
 
         let light_source = Object::cube()
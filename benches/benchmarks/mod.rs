@@ -3,4 +3,5 @@ pub mod bounding_boxes;
 pub mod complex_scenes;
 pub mod lighting;
 pub mod meshes;
+pub mod transforms;
 pub mod trivial_scenes;
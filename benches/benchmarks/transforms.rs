@@ -0,0 +1,42 @@
+use criterion::{black_box, criterion_group, Criterion};
+use ray_tracer::core::*;
+use std::f64::consts::PI;
+
+criterion_group! {
+    benches,
+    transforming_points
+}
+
+/// compares transforming points with the stored forward matrix against inverting the stored inverse for every point,
+/// which is what transforming a point would cost if only the inverse were stored
+fn transforming_points(c: &mut Criterion) {
+    let transform = Transform::identity()
+        .rotate_y(PI / 5.0)
+        .scale_all(1.5)
+        .translate_x(2.0)
+        .translate_z(-3.0);
+    let points = (0..1000)
+        .map(|i| Point3D::new(i as f64, (i % 7) as f64, -(i as f64) / 3.0))
+        .collect::<Vec<_>>();
+
+    let mut group = c.benchmark_group("transforming 1000 points");
+
+    group.bench_function("with the stored forward matrix", |b| {
+        b.iter(|| {
+            for &point in &points {
+                black_box(transform.transform_point(black_box(point)));
+            }
+        })
+    });
+
+    group.bench_function("inverting the inverse for each point", |b| {
+        b.iter(|| {
+            for &point in &points {
+                let forward = black_box(transform.inverse()).inverse().unwrap();
+                black_box(forward * black_box(point));
+            }
+        })
+    });
+
+    group.finish();
+}
@@ -19,7 +19,6 @@ fn main() -> Result<(), String> {
         Vector3D::new(0.0, 2.0, 0.0),
         nonzero_ext::nonzero!(20u8),
         nonzero_ext::nonzero!(20u8),
-        rand::random::<u64>(),
     ));
 
     let light_source = Object::cube()
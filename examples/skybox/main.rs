@@ -14,6 +14,7 @@ fn main() -> anyhow::Result<()> {
     let mut world = World::empty();
     scene.objects()?.into_iter().for_each(|obj| world.add(obj));
     world.lights = scene.lights();
+    world.settings.sky = scene.sky()?;
 
     let camera = scene.camera()?;
 
@@ -25,7 +25,7 @@ fn main() -> Result<(), String> {
     world.add(
         Object::sphere()
             .with_material(Material {
-                kind: MaterialKind::Uv(UvPattern::image(texture)),
+                kind: MaterialKind::Uv(UvPattern::image(texture.into())),
                 ..Default::default()
             })
             .transformed(Transform::identity().rotate_y(PI)),
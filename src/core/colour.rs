@@ -13,7 +13,13 @@ impl Colour {
     pub const GREEN: Colour = Colour::new(0.0, 1.0, 0.0);
     pub const BLUE: Colour = Colour::new(0.0, 0.0, 1.0);
 
+    /// components above 1 are allowed (e.g. for bright lights), but NaN or infinite components are a bug, as they would
+    /// silently spread to every colour they're combined with
     pub const fn new(r: f64, g: f64, b: f64) -> Self {
+        debug_assert!(
+            r.is_finite() && g.is_finite() && b.is_finite(),
+            "colour components must be finite"
+        );
         Colour(r, g, b)
     }
 
@@ -21,6 +27,67 @@ impl Colour {
         Colour(c, c, c)
     }
 
+    /// converts 8-bit components (0 to 255) into the 0.0 to 1.0 range
+    pub const fn from_rgb8(r: u8, g: u8, b: u8) -> Self {
+        Colour(r as f64 / 255.0, g as f64 / 255.0, b as f64 / 255.0)
+    }
+
+    /// parses a hex colour code (as used in CSS), in the form `#RRGGBB` or `RRGGBB`
+    pub fn from_hex(hex: &str) -> anyhow::Result<Self> {
+        let digits = hex.strip_prefix('#').unwrap_or(hex);
+        if digits.len() != 6 || !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+            anyhow::bail!("invalid hex colour {:?} - expected `#RRGGBB`", hex);
+        }
+
+        let component = |index: usize| u8::from_str_radix(&digits[index..index + 2], 16);
+
+        Ok(Colour::from_rgb8(
+            component(0)?,
+            component(2)?,
+            component(4)?,
+        ))
+    }
+
+    /// converts from hue (in degrees), saturation (0.0 to 1.0), and value (0.0 to 1.0)
+    pub fn from_hsv(h: f64, s: f64, v: f64) -> Self {
+        let h = h.rem_euclid(360.0) / 60.0;
+        let chroma = v * s;
+        let x = chroma * (1.0 - (h.rem_euclid(2.0) - 1.0).abs());
+        let m = v - chroma;
+
+        let (r, g, b) = match h as u8 {
+            0 => (chroma, x, 0.0),
+            1 => (x, chroma, 0.0),
+            2 => (0.0, chroma, x),
+            3 => (0.0, x, chroma),
+            4 => (x, 0.0, chroma),
+            _ => (chroma, 0.0, x),
+        };
+
+        Colour::new(r + m, g + m, b + m)
+    }
+
+    /// the inverse of `from_hsv` - the hue of greys (including black and white) is 0
+    pub fn to_hsv(&self) -> (f64, f64, f64) {
+        let (r, g, b) = (self.red(), self.green(), self.blue());
+        let max = r.max(g).max(b);
+        let min = r.min(g).min(b);
+        let delta = max - min;
+
+        let h = if delta == 0.0 {
+            0.0
+        } else if max == r {
+            60.0 * ((g - b) / delta).rem_euclid(6.0)
+        } else if max == g {
+            60.0 * ((b - r) / delta + 2.0)
+        } else {
+            60.0 * ((r - g) / delta + 4.0)
+        };
+        let s = if max == 0.0 { 0.0 } else { delta / max };
+
+        (h, s, max)
+    }
+
     pub const fn red(&self) -> f64 {
         self.0
     }
@@ -74,6 +141,24 @@ impl Colour {
         (self + other) * 0.5
     }
 
+    /// limits each component to the 0.0 to 1.0 range
+    pub const fn clamp(&self) -> Self {
+        Colour(
+            self.0.clamp(0.0, 1.0),
+            self.1.clamp(0.0, 1.0),
+            self.2.clamp(0.0, 1.0),
+        )
+    }
+
+    /// linearly interpolates each component between `self` (when `t` is 0) and `other` (when `t` is 1)
+    pub const fn lerp(&self, other: &Self, t: f64) -> Self {
+        Colour(
+            self.0 + (other.0 - self.0) * t,
+            self.1 + (other.1 - self.1) * t,
+            self.2 + (other.2 - self.2) * t,
+        )
+    }
+
     /// returns `true` when `self` is imperceptibly different to `other` i.e. when the colours are
     /// interchangeable
     ///
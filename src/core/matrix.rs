@@ -41,7 +41,32 @@ impl Matrix4D {
         )
     }
 
-    pub(super) fn inverse(&self) -> Option<Self> {
+    /// the element-wise mean of two matrices
+    pub(in crate::core) fn mean(&self, other: &Self) -> Self {
+        let mut underlying = self.underlying;
+        for (row, other_row) in underlying.iter_mut().zip(other.underlying.iter()) {
+            for (element, other_element) in row.iter_mut().zip(other_row.iter()) {
+                *element = (*element + other_element) / 2.0;
+            }
+        }
+
+        Matrix4D { underlying }
+    }
+
+    /// element-wise linear interpolation, such that `t = 0` is `self` and `t = 1` is `other`
+    pub(in crate::core) fn lerp(&self, other: &Self, t: f64) -> Self {
+        let mut underlying = self.underlying;
+        for (row, other_row) in underlying.iter_mut().zip(other.underlying.iter()) {
+            for (element, other_element) in row.iter_mut().zip(other_row.iter()) {
+                *element += (other_element - *element) * t;
+            }
+        }
+
+        Matrix4D { underlying }
+    }
+
+    /// `None` if the matrix is not invertible (i.e. the determinant is 0)
+    pub fn inverse(&self) -> Option<Self> {
         let determinant = self.determinant();
 
         if determinant == 0.0 {
@@ -318,8 +343,16 @@ impl Mul<Point3D> for Matrix4D {
     }
 }
 
+/// A 3x3 matrix - primarily used to calculate the determinant and inverse of a `Matrix4D`, but usable in its own right
+///
+/// ```
+/// use ray_tracer::core::Matrix3D;
+///
+/// let matrix = Matrix3D::new([1.0, 2.0, 6.0], [-5.0, 8.0, -4.0], [2.0, 6.0, 4.0]);
+/// assert_eq!(matrix.determinant(), -196.0);
+/// ```
 #[derive(Debug, PartialEq, Clone)]
-pub(in crate::core) struct Matrix3D {
+pub struct Matrix3D {
     underlying: [[f64; 3]; 3],
 }
 
@@ -330,13 +363,41 @@ impl Matrix3D {
         }
     }
 
-    pub(crate) fn determinant(&self) -> f64 {
+    pub fn determinant(&self) -> f64 {
         self.m00() * self.cofactor(0, 0)
             + self.m01() * self.cofactor(0, 1)
             + self.m02() * self.cofactor(0, 2)
     }
 
-    pub(crate) fn cofactor(&self, row: u8, column: u8) -> f64 {
+    /// `None` if the matrix is not invertible (i.e. the determinant is 0)
+    ///
+    /// ```
+    /// use ray_tracer::core::Matrix3D;
+    ///
+    /// let matrix = Matrix3D::new([2.0, 0.0, 0.0], [0.0, 4.0, 0.0], [0.0, 0.0, 0.5]);
+    /// assert_eq!(
+    ///     matrix.inverse(),
+    ///     Some(Matrix3D::new([0.5, 0.0, 0.0], [0.0, 0.25, 0.0], [0.0, 0.0, 2.0]))
+    /// );
+    /// assert_eq!(Matrix3D::new([1.0; 3], [1.0; 3], [1.0; 3]).inverse(), None);
+    /// ```
+    pub fn inverse(&self) -> Option<Self> {
+        let determinant = self.determinant();
+        if determinant == 0.0 {
+            return None;
+        }
+
+        // the transposed matrix of cofactors, divided by the determinant
+        let element = |row: u8, column: u8| self.cofactor(column, row) / determinant;
+
+        Some(Matrix3D::new(
+            [element(0, 0), element(0, 1), element(0, 2)],
+            [element(1, 0), element(1, 1), element(1, 2)],
+            [element(2, 0), element(2, 1), element(2, 2)],
+        ))
+    }
+
+    pub fn cofactor(&self, row: u8, column: u8) -> f64 {
         let minor = self.minor(row, column);
 
         if (row + column) % 2 == 0 {
@@ -346,11 +407,11 @@ impl Matrix3D {
         }
     }
 
-    pub(crate) fn minor(&self, row: u8, column: u8) -> f64 {
+    pub fn minor(&self, row: u8, column: u8) -> f64 {
         self.submatrix(row, column).determinant()
     }
 
-    pub(crate) fn submatrix(&self, excluding_row: u8, excluding_column: u8) -> Matrix2D {
+    pub fn submatrix(&self, excluding_row: u8, excluding_column: u8) -> Matrix2D {
         match (excluding_row, excluding_column) {
             (0, 0) => Matrix2D::new([self.m11(), self.m12()], [self.m21(), self.m22()]),
             (0, 1) => Matrix2D::new([self.m10(), self.m12()], [self.m20(), self.m22()]),
@@ -368,45 +429,57 @@ impl Matrix3D {
         }
     }
 
-    pub(crate) fn m00(&self) -> f64 {
+    pub fn m00(&self) -> f64 {
         self.underlying[0][0]
     }
 
-    fn m01(&self) -> f64 {
+    pub fn m01(&self) -> f64 {
         self.underlying[0][1]
     }
 
-    fn m02(&self) -> f64 {
+    pub fn m02(&self) -> f64 {
         self.underlying[0][2]
     }
 
-    fn m10(&self) -> f64 {
+    pub fn m10(&self) -> f64 {
         self.underlying[1][0]
     }
 
-    pub(crate) fn m11(&self) -> f64 {
+    pub fn m11(&self) -> f64 {
         self.underlying[1][1]
     }
 
-    fn m12(&self) -> f64 {
+    pub fn m12(&self) -> f64 {
         self.underlying[1][2]
     }
 
-    fn m20(&self) -> f64 {
+    pub fn m20(&self) -> f64 {
         self.underlying[2][0]
     }
 
-    fn m21(&self) -> f64 {
+    pub fn m21(&self) -> f64 {
         self.underlying[2][1]
     }
 
-    pub(crate) fn m22(&self) -> f64 {
+    pub fn m22(&self) -> f64 {
         self.underlying[2][2]
     }
 }
 
+/// A 2x2 matrix
+///
+/// ```
+/// use ray_tracer::core::Matrix2D;
+///
+/// let matrix = Matrix2D::new([4.0, 7.0], [2.0, 6.0]);
+/// assert_eq!(matrix.determinant(), 10.0);
+/// assert_eq!(
+///     matrix.inverse(),
+///     Some(Matrix2D::new([0.6, -0.7], [-0.2, 0.4]))
+/// );
+/// ```
 #[derive(Debug, PartialEq, Clone)]
-pub(in crate::core) struct Matrix2D {
+pub struct Matrix2D {
     underlying: [[f64; 2]; 2],
 }
 
@@ -417,23 +490,36 @@ impl Matrix2D {
         }
     }
 
-    pub(crate) fn determinant(&self) -> f64 {
+    pub fn determinant(&self) -> f64 {
         self.m00() * self.m11() - self.m01() * self.m10()
     }
 
-    pub(crate) fn m00(&self) -> f64 {
+    /// `None` if the matrix is not invertible (i.e. the determinant is 0)
+    pub fn inverse(&self) -> Option<Self> {
+        let determinant = self.determinant();
+        if determinant == 0.0 {
+            return None;
+        }
+
+        Some(Matrix2D::new(
+            [self.m11() / determinant, -self.m01() / determinant],
+            [-self.m10() / determinant, self.m00() / determinant],
+        ))
+    }
+
+    pub fn m00(&self) -> f64 {
         self.underlying[0][0]
     }
 
-    pub(crate) fn m01(&self) -> f64 {
+    pub fn m01(&self) -> f64 {
         self.underlying[0][1]
     }
 
-    pub(crate) fn m10(&self) -> f64 {
+    pub fn m10(&self) -> f64 {
         self.underlying[1][0]
     }
 
-    pub(crate) fn m11(&self) -> f64 {
+    pub fn m11(&self) -> f64 {
         self.underlying[1][1]
     }
 }
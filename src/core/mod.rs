@@ -5,10 +5,13 @@ mod vector;
 pub use vector::{Normal3D, Vector3D, VectorMaths};
 
 mod matrix;
-pub use matrix::Matrix4D;
+pub use matrix::{Matrix2D, Matrix3D, Matrix4D};
 
 mod transform;
-pub use transform::Transform;
+pub use transform::{Transform, TransformPath};
+
+mod quaternion;
+pub use quaternion::Quaternion;
 
 mod colour;
 pub use colour::Colour;
@@ -29,6 +32,103 @@ pub fn quadratic(a: f64, b: f64, c: f64) -> Option<(f64, f64)> {
     Some((first, second))
 }
 
+/// the real roots of `at^4 + bt^3 + ct^2 + dt + e`, in ascending order - repeated roots (e.g. from a ray grazing a surface) are included once per multiplicity
+pub fn quartic(a: f64, b: f64, c: f64, d: f64, e: f64) -> Vec<f64> {
+    real_roots(&[a, b, c, d, e])
+}
+
+/// Finds the real roots of a polynomial, with coefficients ordered from the highest power to the constant.
+///
+/// Closed form solutions to cubics and quartics are numerically unstable, so instead, the roots of the derivative
+/// (found recursively) are used to split the real line into intervals where the polynomial is strictly increasing or decreasing,
+/// which can each contain at most one root, found by bisection
+fn real_roots(coefficients: &[f64]) -> Vec<f64> {
+    let coefficients = match coefficients.iter().position(|c| *c != 0.0) {
+        // dividing through by the leading coefficient doesn't change the roots, but keeps "roughly zero" meaningful
+        Some(first_non_zero) => coefficients[first_non_zero..]
+            .iter()
+            .map(|c| c / coefficients[first_non_zero])
+            .collect::<Vec<_>>(),
+        None => return Vec::new(),
+    };
+    let degree = coefficients.len() - 1;
+
+    match degree {
+        0 => return Vec::new(),
+        1 => return vec![-coefficients[1]],
+        _ => (),
+    };
+
+    let evaluate = |t: f64| coefficients.iter().fold(0.0, |acc, c| acc * t + c);
+
+    let derivative = coefficients[..degree]
+        .iter()
+        .enumerate()
+        .map(|(i, c)| c * (degree - i) as f64)
+        .collect::<Vec<_>>();
+    let turning_points = real_roots(&derivative);
+
+    // every root lies within this distance of zero (Cauchy's bound)
+    let bound = 1.0
+        + coefficients[1..]
+            .iter()
+            .map(|c| c.abs())
+            .fold(0.0, f64::max);
+
+    let mut boundaries = vec![-bound];
+    boundaries.extend(turning_points.iter().filter(|t| t.abs() < bound));
+    boundaries.push(bound);
+
+    let mut roots = Vec::new();
+    for interval in boundaries.windows(2) {
+        let (mut low, mut high) = (interval[0], interval[1]);
+        let (mut low_value, high_value) = (evaluate(low), evaluate(high));
+
+        if low_value.is_roughly_zero() {
+            // a turning point which touches zero is a repeated root
+            if low != -bound {
+                roots.push(low);
+                roots.push(low);
+            }
+            continue;
+        }
+
+        if high_value.is_roughly_zero() || low_value.signum() == high_value.signum() {
+            continue;
+        }
+
+        while high - low > f64::EPSILON * high.abs().max(1.0) {
+            let middle = (low + high) / 2.0;
+            let middle_value = evaluate(middle);
+
+            if middle_value.signum() == low_value.signum() {
+                low = middle;
+                low_value = middle_value;
+            } else {
+                high = middle;
+            }
+        }
+
+        roots.push((low + high) / 2.0);
+    }
+
+    roots
+}
+
+/// Combines the `values` into a single seed for a random number generator, by mixing each value in turn with the splitmix64
+/// finaliser.
+///
+/// Unlike the hashers in `std`, the algorithm is fixed, so the same values produce the same seed on every platform and with
+/// every version of Rust
+pub(crate) fn seed_from(values: &[u64]) -> u64 {
+    values.iter().fold(0x9E37_79B9_7F4A_7C15, |acc, &value| {
+        let z = (acc ^ value).wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        let z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    })
+}
+
 pub trait F64Ext {
     fn roughly_equals(&self, other: Self) -> bool;
     fn is_roughly_gte(&self, other: Self) -> bool;
@@ -67,6 +167,7 @@ mod tests {
     mod colour_tests;
     mod matrix_tests;
     mod point_tests;
+    mod quaternion_tests;
     mod transform_tests;
     mod vector_tests;
 }
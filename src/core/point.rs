@@ -67,6 +67,13 @@ impl Point3D {
                 )
             })
     }
+
+    /// the point `t` of the way along the line from `self` (when `t` is 0) to `other` (when `t` is 1)
+    ///
+    /// `t` is not clamped, so values outside of 0 to 1 extrapolate beyond either end of the line
+    pub fn lerp(&self, other: &Point3D, t: f64) -> Point3D {
+        *self + (*other - *self) * t
+    }
 }
 
 impl From<(f64, f64, f64)> for Point3D {
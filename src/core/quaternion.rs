@@ -0,0 +1,172 @@
+use crate::core::{Matrix4D, Normal3D, VectorMaths};
+use approx::AbsDiffEq;
+use std::ops::Mul;
+
+/// A rotation, which (unlike a rotation matrix) can be smoothly interpolated using `slerp`, e.g. to animate between key-frames.
+///
+/// Convert to a `Transform` using `Transform::from_quaternion`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Quaternion {
+    w: f64,
+    x: f64,
+    y: f64,
+    z: f64,
+}
+
+impl Quaternion {
+    /// no rotation
+    pub const IDENTITY: Quaternion = Quaternion::new(1.0, 0.0, 0.0, 0.0);
+
+    pub const fn new(w: f64, x: f64, y: f64, z: f64) -> Self {
+        Quaternion { w, x, y, z }
+    }
+
+    /// a rotation of `radians` around `axis`, in the same direction as the equivalent `Transform::rotate_x/y/z`
+    pub fn from_axis_angle(axis: Normal3D, radians: f64) -> Self {
+        let (sin, cos) = (radians / 2.0).sin_cos();
+
+        Quaternion::new(cos, axis.x() * sin, axis.y() * sin, axis.z() * sin)
+    }
+
+    pub fn normalised(&self) -> Self {
+        let magnitude = self.dot(self).sqrt();
+
+        Quaternion::new(
+            self.w / magnitude,
+            self.x / magnitude,
+            self.y / magnitude,
+            self.z / magnitude,
+        )
+    }
+
+    /// Spherical linear interpolation between rotations `a` (when `t` is 0) and `b` (when `t` is 1), which rotates at a
+    /// constant speed, taking the shortest path between the two
+    pub fn slerp(a: Quaternion, b: Quaternion, t: f64) -> Self {
+        let a = a.normalised();
+        let mut b = b.normalised();
+
+        // `b` and `-b` represent the same rotation, but interpolating towards `-b` takes the other way around
+        let mut cos_angle = a.dot(&b);
+        if cos_angle < 0.0 {
+            b = b.scaled(-1.0);
+            cos_angle = -cos_angle;
+        }
+
+        // the rotations are almost identical - dividing by the sine of the angle would be unstable, but a linear
+        // interpolation is indistinguishable
+        if cos_angle > 1.0 - f32::EPSILON as f64 {
+            return a.scaled(1.0 - t).add(&b.scaled(t)).normalised();
+        }
+
+        let angle = cos_angle.acos();
+        let sin_angle = angle.sin();
+
+        a.scaled(((1.0 - t) * angle).sin() / sin_angle)
+            .add(&b.scaled((t * angle).sin() / sin_angle))
+    }
+
+    /// the rotation represented by a pure rotation matrix (i.e. orthogonal, with a determinant of 1)
+    pub(in crate::core) fn from_matrix(m: &Matrix4D) -> Self {
+        let trace = m.m00() + m.m11() + m.m22();
+
+        // picks the largest component to divide by, for numerical stability
+        let rotation = if trace > 0.0 {
+            let s = (trace + 1.0).sqrt() * 2.0;
+            Quaternion::new(
+                s / 4.0,
+                (m.m21() - m.m12()) / s,
+                (m.m02() - m.m20()) / s,
+                (m.m10() - m.m01()) / s,
+            )
+        } else if m.m00() > m.m11() && m.m00() > m.m22() {
+            let s = (1.0 + m.m00() - m.m11() - m.m22()).sqrt() * 2.0;
+            Quaternion::new(
+                (m.m21() - m.m12()) / s,
+                s / 4.0,
+                (m.m01() + m.m10()) / s,
+                (m.m02() + m.m20()) / s,
+            )
+        } else if m.m11() > m.m22() {
+            let s = (1.0 + m.m11() - m.m00() - m.m22()).sqrt() * 2.0;
+            Quaternion::new(
+                (m.m02() - m.m20()) / s,
+                (m.m01() + m.m10()) / s,
+                s / 4.0,
+                (m.m12() + m.m21()) / s,
+            )
+        } else {
+            let s = (1.0 + m.m22() - m.m00() - m.m11()).sqrt() * 2.0;
+            Quaternion::new(
+                (m.m10() - m.m01()) / s,
+                (m.m02() + m.m20()) / s,
+                (m.m12() + m.m21()) / s,
+                s / 4.0,
+            )
+        };
+
+        rotation.normalised()
+    }
+
+    #[rustfmt::skip]
+    pub(in crate::core) fn to_matrix(self) -> Matrix4D {
+        let Quaternion { w, x, y, z } = self.normalised();
+
+        Matrix4D::new(
+            [1.0 - 2.0 * (y * y + z * z), 2.0 * (x * y - w * z),       2.0 * (x * z + w * y),       0.0],
+            [2.0 * (x * y + w * z),       1.0 - 2.0 * (x * x + z * z), 2.0 * (y * z - w * x),       0.0],
+            [2.0 * (x * z - w * y),       2.0 * (y * z + w * x),       1.0 - 2.0 * (x * x + y * y), 0.0],
+            [0.0,                         0.0,                         0.0,                         1.0],
+        )
+    }
+
+    fn dot(&self, other: &Self) -> f64 {
+        self.w * other.w + self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    fn scaled(&self, factor: f64) -> Self {
+        Quaternion::new(
+            self.w * factor,
+            self.x * factor,
+            self.y * factor,
+            self.z * factor,
+        )
+    }
+
+    fn add(&self, other: &Self) -> Self {
+        Quaternion::new(
+            self.w + other.w,
+            self.x + other.x,
+            self.y + other.y,
+            self.z + other.z,
+        )
+    }
+}
+
+/// combines two rotations - as with `Transform`s, `a * b` applies `b` first, then `a`
+impl Mul for Quaternion {
+    type Output = Quaternion;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        Quaternion::new(
+            self.w * rhs.w - self.x * rhs.x - self.y * rhs.y - self.z * rhs.z,
+            self.w * rhs.x + self.x * rhs.w + self.y * rhs.z - self.z * rhs.y,
+            self.w * rhs.y - self.x * rhs.z + self.y * rhs.w + self.z * rhs.x,
+            self.w * rhs.z + self.x * rhs.y - self.y * rhs.x + self.z * rhs.w,
+        )
+    }
+}
+
+impl AbsDiffEq for Quaternion {
+    type Epsilon = f64;
+
+    fn default_epsilon() -> Self::Epsilon {
+        f32::EPSILON as f64
+    }
+
+    fn abs_diff_eq(&self, other: &Self, epsilon: Self::Epsilon) -> bool {
+        self.w.abs_diff_eq(&other.w, epsilon)
+            && self.x.abs_diff_eq(&other.x, epsilon)
+            && self.y.abs_diff_eq(&other.y, epsilon)
+            && self.z.abs_diff_eq(&other.z, epsilon)
+    }
+}
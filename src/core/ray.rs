@@ -6,6 +6,8 @@ pub struct Ray {
     pub origin: Point3D,
     // a ray should be normalised when created, but may be stretched or squashed
     pub direction: Vector3D,
+    /// when the ray was cast during the camera's shutter interval, between 0 (open) and 1 (closed) - only affects moving objects
+    pub time: f64,
 }
 
 impl Ray {
@@ -13,9 +15,15 @@ impl Ray {
         Ray {
             origin,
             direction: direction.into(),
+            time: 0.0,
         }
     }
 
+    pub fn with_time(mut self, time: f64) -> Self {
+        self.time = time;
+        self
+    }
+
     pub fn position(&self, time: f64) -> Point3D {
         self.origin + self.direction * time
     }
@@ -27,7 +35,11 @@ impl Ray {
         let (x, y, z, _) = transformation * self.direction;
         let direction = Vector3D::new(x, y, z);
 
-        Ray { origin, direction }
+        Ray {
+            origin,
+            direction,
+            time: self.time,
+        }
     }
 }
 
@@ -68,4 +80,12 @@ mod tests {
         assert_eq!(transformed.origin, Point3D::new(2.0, 6.0, 12.0));
         assert_eq!(transformed.direction, Vector3D::new(0.0, 3.0, 0.0));
     }
+
+    #[test]
+    fn transforming_a_ray_should_preserve_its_time() {
+        let matrix = Transform::identity().translate_x(3.0);
+        let ray = Ray::new(Point3D::ORIGIN, Normal3D::POSITIVE_Y).with_time(0.75);
+
+        assert_eq!(ray.transformed(&matrix.underlying()).time, 0.75);
+    }
 }
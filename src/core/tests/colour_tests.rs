@@ -19,6 +19,84 @@ mod unit_tests {
         assert_eq!(Colour::new(-0.5, 0.4, 1.7).blue(), 1.7);
     }
 
+    #[test]
+    fn colours_should_be_constructable_from_8_bit_components() {
+        assert_eq!(Colour::from_rgb8(0, 0, 0), Colour::BLACK);
+        assert_eq!(Colour::from_rgb8(255, 255, 255), Colour::WHITE);
+        assert_eq!(Colour::from_rgb8(51, 102, 204), Colour::new(0.2, 0.4, 0.8));
+    }
+
+    #[test]
+    fn colours_should_be_parseable_from_hex_codes_with_or_without_a_hash() {
+        assert_eq!(Colour::from_hex("#ffffff").unwrap(), Colour::WHITE);
+        assert_eq!(Colour::from_hex("FF0000").unwrap(), Colour::RED);
+        assert_eq!(
+            Colour::from_hex("#3366cc").unwrap(),
+            Colour::from_rgb8(51, 102, 204)
+        );
+    }
+
+    #[test]
+    fn invalid_hex_codes_should_fail_to_parse() {
+        vec![
+            "",
+            "#",
+            "#fff",
+            "#fffffff",
+            "##ffffff",
+            "#gggggg",
+            "#+fffff",
+            "#ff ff ff",
+        ]
+        .into_iter()
+        .for_each(|hex| assert!(Colour::from_hex(hex).is_err(), "{:?}", hex));
+    }
+
+    #[test]
+    fn pure_red_should_round_trip_through_hsv() {
+        assert_eq!(Colour::RED.to_hsv(), (0.0, 1.0, 1.0));
+        assert_eq!(Colour::from_hsv(0.0, 1.0, 1.0), Colour::RED);
+    }
+
+    #[test]
+    fn a_grey_should_round_trip_through_hsv_with_no_saturation() {
+        let grey = Colour::greyscale(0.4);
+
+        assert_eq!(grey.to_hsv(), (0.0, 0.0, 0.4));
+        assert_eq!(Colour::from_hsv(0.0, 0.0, 0.4), grey);
+        // hue is meaningless without saturation
+        assert_eq!(Colour::from_hsv(200.0, 0.0, 0.4), grey);
+    }
+
+    #[test]
+    fn a_mid_tone_blue_should_round_trip_through_hsv() {
+        let blue = Colour::new(0.2, 0.4, 0.6);
+
+        let (h, s, v) = blue.to_hsv();
+        assert_abs_diff_eq!(h, 210.0);
+        assert_abs_diff_eq!(s, 2.0 / 3.0);
+        assert_abs_diff_eq!(v, 0.6);
+        assert_abs_diff_eq!(Colour::from_hsv(h, s, v), blue);
+    }
+
+    #[test]
+    fn the_primary_and_secondary_colours_should_be_60_degrees_apart() {
+        vec![
+            (0.0, Colour::RED),
+            (60.0, Colour::new(1.0, 1.0, 0.0)),
+            (120.0, Colour::GREEN),
+            (180.0, Colour::new(0.0, 1.0, 1.0)),
+            (240.0, Colour::BLUE),
+            (300.0, Colour::new(1.0, 0.0, 1.0)),
+            (360.0, Colour::RED),
+            (-120.0, Colour::BLUE),
+        ]
+        .into_iter()
+        .for_each(|(hue, expected)| {
+            assert_eq!(Colour::from_hsv(hue, 1.0, 1.0), expected, "{}", hue);
+        });
+    }
+
     #[test]
     fn adding_two_colours_should_sum_components() {
         let c1 = Colour::new(0.9, 0.6, 0.75);
@@ -62,6 +140,31 @@ mod unit_tests {
         assert_abs_diff_eq!(product.blue(), 0.04);
     }
 
+    #[test]
+    fn clamping_a_colour_should_limit_each_component_to_between_0_and_1() {
+        let colour = Colour::new(2.0, -1.0, 0.5);
+
+        assert_eq!(colour.clamp(), Colour::new(1.0, 0.0, 0.5));
+    }
+
+    #[test]
+    fn interpolating_half_way_between_black_and_white_should_be_mid_grey() {
+        assert_eq!(
+            Colour::BLACK.lerp(&Colour::WHITE, 0.5),
+            Colour::greyscale(0.5)
+        );
+    }
+
+    #[test]
+    fn interpolating_between_two_colours_should_start_at_the_first_and_end_at_the_second() {
+        let first = Colour::new(0.2, 0.8, 0.4);
+        let second = Colour::new(1.0, 0.0, 0.4);
+
+        assert_eq!(first.lerp(&second, 0.0), first);
+        assert_eq!(first.lerp(&second, 1.0), second);
+        assert_abs_diff_eq!(first.lerp(&second, 0.25), Colour::new(0.4, 0.6, 0.4));
+    }
+
     #[test]
     fn two_colours_with_an_unnoticeably_different_red_should_be_similar() {
         let first = Colour::new(0.999, 0.0, 0.0);
@@ -155,6 +258,25 @@ mod unit_tests {
 
         assert!(!first.is_similar_to(&second));
     }
+
+    #[test]
+    fn colours_brighter_than_white_should_be_allowed() {
+        assert_eq!(Colour::new(2.0, 0.0, 0.0).red(), 2.0);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "colour components must be finite")]
+    fn a_nan_colour_component_should_panic_in_debug_builds() {
+        Colour::new(f64::NAN, 0.0, 0.0);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "colour components must be finite")]
+    fn an_infinite_colour_component_should_panic_in_debug_builds() {
+        Colour::new(0.0, 0.0, f64::INFINITY);
+    }
 }
 
 mod property_tests {
@@ -251,6 +251,38 @@ fn should_be_able_to_calculate_determinant_of_a_2d_matrix() {
     assert_eq!(matrix.determinant(), 17.0);
 }
 
+#[test]
+fn inverting_a_3d_matrix_should_produce_the_transposed_cofactors_divided_by_the_determinant() {
+    #[rustfmt::skip]
+    let matrix = Matrix3D::new(
+        [1.0, 2.0, 3.0],
+        [0.0, 1.0, 4.0],
+        [5.0, 6.0, 0.0]
+    );
+
+    #[rustfmt::skip]
+    let expected = Matrix3D::new(
+        [-24.0, 18.0, 5.0],
+        [20.0, -15.0, -4.0],
+        [-5.0, 4.0, 1.0]
+    );
+
+    assert_eq!(matrix.determinant(), 1.0);
+    assert_eq!(matrix.inverse(), Some(expected));
+}
+
+#[test]
+fn a_matrix_with_a_determinant_of_0_should_not_be_invertible() {
+    assert_eq!(Matrix2D::new([1.0, 2.0], [2.0, 4.0]).inverse(), None);
+    #[rustfmt::skip]
+    let matrix = Matrix3D::new(
+        [1.0, 2.0, 3.0],
+        [4.0, 5.0, 6.0],
+        [7.0, 8.0, 9.0]
+    );
+    assert_eq!(matrix.inverse(), None);
+}
+
 #[test]
 fn should_be_able_to_take_a_2d_submatrix_of_a_3d_matrix() {
     #[rustfmt::skip]
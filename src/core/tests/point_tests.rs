@@ -51,6 +51,25 @@ mod unit_tests {
 
         assert_eq!(max, Point3D::new(2.0, 2.0, 4.0));
     }
+
+    #[test]
+    fn interpolating_between_two_points_should_move_along_the_line_between_them() {
+        let start = Point3D::new(1.0, -2.0, 4.0);
+        let end = Point3D::new(5.0, 2.0, 0.0);
+
+        assert_eq!(start.lerp(&end, 0.0), start);
+        assert_eq!(start.lerp(&end, 1.0), end);
+        assert_eq!(start.lerp(&end, 0.25), Point3D::new(2.0, -1.0, 3.0));
+    }
+
+    #[test]
+    fn interpolating_beyond_either_end_of_a_line_should_extrapolate() {
+        let start = Point3D::new(0.0, 0.0, 0.0);
+        let end = Point3D::new(2.0, 0.0, -2.0);
+
+        assert_eq!(start.lerp(&end, 1.5), Point3D::new(3.0, 0.0, -3.0));
+        assert_eq!(start.lerp(&end, -0.5), Point3D::new(-1.0, 0.0, 1.0));
+    }
 }
 
 mod property_tests {
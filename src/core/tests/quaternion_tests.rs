@@ -0,0 +1,87 @@
+use super::*;
+use approx::*;
+use std::f64::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+
+#[test]
+fn the_identity_quaternion_should_produce_the_identity_transform() {
+    assert_abs_diff_eq!(
+        Transform::from_quaternion(Quaternion::IDENTITY),
+        Transform::identity()
+    );
+}
+
+#[test]
+fn a_quaternion_should_rotate_in_the_same_direction_as_the_equivalent_transform() {
+    vec![
+        (
+            Normal3D::POSITIVE_X,
+            Transform::identity().rotate_x(PI / 3.0),
+        ),
+        (
+            Normal3D::POSITIVE_Y,
+            Transform::identity().rotate_y(PI / 3.0),
+        ),
+        (
+            Normal3D::POSITIVE_Z,
+            Transform::identity().rotate_z(PI / 3.0),
+        ),
+    ]
+    .into_iter()
+    .for_each(|(axis, expected)| {
+        let rotation = Quaternion::from_axis_angle(axis, PI / 3.0);
+        assert_abs_diff_eq!(Transform::from_quaternion(rotation), expected);
+    });
+}
+
+#[test]
+fn multiplying_quaternions_should_combine_rotations_in_the_same_order_as_transforms() {
+    let x = Quaternion::from_axis_angle(Normal3D::POSITIVE_X, FRAC_PI_2);
+    let y = Quaternion::from_axis_angle(Normal3D::POSITIVE_Y, FRAC_PI_4);
+
+    assert_abs_diff_eq!(
+        Transform::from_quaternion(y * x),
+        Transform::identity()
+            .rotate_x(FRAC_PI_2)
+            .rotate_y(FRAC_PI_4)
+    );
+}
+
+#[test]
+fn slerp_should_return_the_endpoints_at_0_and_1() {
+    let a = Quaternion::from_axis_angle(Normal3D::POSITIVE_Y, 0.3);
+    let b = Quaternion::from_axis_angle(Normal3D::POSITIVE_X, 1.2);
+
+    assert_abs_diff_eq!(Quaternion::slerp(a, b, 0.0), a);
+    assert_abs_diff_eq!(Quaternion::slerp(a, b, 1.0), b);
+}
+
+#[test]
+fn the_midpoint_of_a_90_degree_turn_should_be_a_45_degree_turn() {
+    let start = Quaternion::IDENTITY;
+    let end = Quaternion::from_axis_angle(Normal3D::POSITIVE_Y, FRAC_PI_2);
+
+    assert_abs_diff_eq!(
+        Transform::from_quaternion(Quaternion::slerp(start, end, 0.5)),
+        Transform::identity().rotate_y(FRAC_PI_4)
+    );
+}
+
+#[test]
+fn slerp_should_take_the_shortest_path_between_rotations() {
+    // a 270 degree turn is the same orientation as a -90 degree turn, so the midpoint is -45 degrees
+    let start = Quaternion::IDENTITY;
+    let end = Quaternion::from_axis_angle(Normal3D::POSITIVE_Y, 3.0 * FRAC_PI_2);
+
+    assert_abs_diff_eq!(
+        Transform::from_quaternion(Quaternion::slerp(start, end, 0.5)),
+        Transform::identity().rotate_y(-FRAC_PI_4)
+    );
+}
+
+#[test]
+fn slerp_between_almost_identical_rotations_should_not_produce_nan() {
+    let a = Quaternion::from_axis_angle(Normal3D::POSITIVE_Z, 0.5);
+    let b = Quaternion::from_axis_angle(Normal3D::POSITIVE_Z, 0.5 + 1e-12);
+
+    assert_abs_diff_eq!(Quaternion::slerp(a, b, 0.5), a);
+}
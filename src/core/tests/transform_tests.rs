@@ -304,6 +304,210 @@ mod unit_tests {
             ))
         );
     }
+
+    #[test]
+    fn an_object_at_the_origin_looking_along_negative_z_should_not_be_rotated() {
+        let transform = Transform::look_at(
+            Point3D::ORIGIN,
+            Point3D::new(0.0, 0.0, -1.0),
+            Normal3D::POSITIVE_Y,
+        );
+
+        assert_eq!(transform, Transform::identity());
+    }
+
+    #[test]
+    fn looking_at_a_target_should_point_the_negative_z_axis_of_the_object_at_the_target() {
+        let transform = Transform::look_at(
+            Point3D::new(1.0, 2.0, 3.0),
+            Point3D::new(5.0, 2.0, 3.0),
+            Normal3D::POSITIVE_Y,
+        );
+
+        assert_abs_diff_eq!(
+            transform * Point3D::new(0.0, 0.0, -1.0),
+            Point3D::new(2.0, 2.0, 3.0),
+            epsilon = 1e-12
+        );
+        assert_abs_diff_eq!(
+            transform * Vector3D::new(0.0, 1.0, 0.0),
+            Vector3D::new(0.0, 1.0, 0.0),
+            epsilon = 1e-12
+        );
+    }
+
+    #[test]
+    fn looking_at_a_target_directly_below_should_still_point_the_negative_z_axis_at_the_target() {
+        let transform = Transform::look_at(
+            Point3D::new(0.0, 5.0, 0.0),
+            Point3D::ORIGIN,
+            Normal3D::POSITIVE_Y,
+        );
+
+        assert_abs_diff_eq!(
+            transform * Point3D::new(0.0, 0.0, -1.0),
+            Point3D::new(0.0, 4.0, 0.0),
+            epsilon = 1e-12
+        );
+        // the Y axis can't point up, so points along (either way along) one of the other axes instead
+        let up = transform * Vector3D::new(0.0, 1.0, 0.0);
+        assert_abs_diff_eq!(up.y(), 0.0, epsilon = 1e-12);
+        assert_abs_diff_eq!(up.magnitude(), 1.0, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn looking_at_a_target_should_be_the_inverse_of_the_view_transform() {
+        let from = Point3D::new(1.0, 3.0, 2.0);
+        let target = Point3D::new(4.0, 3.0, 8.0);
+        let up = Normal3D::POSITIVE_Y;
+
+        assert_abs_diff_eq!(
+            Transform::look_at(from, target, up).underlying(),
+            Transform::view_transform(from, target, up).inverse(),
+            epsilon = 1e-12
+        );
+    }
+
+    #[test]
+    fn interpolating_between_two_translations_should_translate_part_of_the_way() {
+        let start = Transform::identity().translate_x(1.0);
+        let end = Transform::identity().translate_x(3.0).translate_y(-2.0);
+
+        assert_eq!(start.lerp(&end, 0.0), start);
+        assert_eq!(start.lerp(&end, 1.0), end);
+        assert_abs_diff_eq!(
+            start.lerp(&end, 0.25),
+            Transform::identity().translate_x(1.5).translate_y(-0.5)
+        );
+    }
+
+    #[test]
+    fn interpolating_half_way_through_a_half_turn_should_rotate_a_quarter_turn() {
+        let end = Transform::identity().rotate_y(PI);
+
+        assert_abs_diff_eq!(
+            Transform::identity().lerp(&end, 0.5),
+            Transform::identity().rotate_y(PI / 2.0)
+        );
+    }
+
+    #[test]
+    fn interpolating_between_scaled_rotations_should_interpolate_the_scale_and_rotation_separately()
+    {
+        let start = Transform::identity().scale_all(2.0).translate_x(1.0);
+        let end = Transform::identity()
+            .scale_all(4.0)
+            .rotate_z(PI / 2.0)
+            .translate_x(3.0);
+
+        assert_abs_diff_eq!(
+            start.lerp(&end, 0.5),
+            Transform::identity()
+                .scale_all(3.0)
+                .rotate_z(PI / 4.0)
+                .translate_x(2.0)
+        );
+    }
+
+    #[test]
+    fn a_path_from_a_mirrored_transform_should_shrink_through_the_flat_transform_without_stopping_on_it(
+    ) {
+        let start = Transform::identity().scale_all(-1.0);
+        let path = TransformPath::new(start, Transform::identity());
+
+        assert_eq!(path.at(0.5), Transform::identity());
+        assert_abs_diff_eq!(path.at(0.25), Transform::identity().scale_all(-0.5));
+    }
+
+    mod decomposing {
+        use super::*;
+
+        fn recompose(
+            translation: Point3D,
+            (x, y, z): (f64, f64, f64),
+            rotation: Matrix4D,
+        ) -> Transform {
+            Transform::translation(translation.x(), translation.y(), translation.z())
+                * Transform::new(rotation)
+                * Transform::scaling(x, y, z)
+        }
+
+        #[test]
+        fn a_translated_scaled_and_rotated_transform_should_decompose_into_its_components() {
+            let transform = Transform::identity()
+                .translate_x(1.0)
+                .translate_y(-2.0)
+                .translate_z(3.0)
+                .scale_x(2.0)
+                .scale_y(3.0)
+                .scale_z(4.0)
+                .rotate_y(PI / 3.0);
+
+            let (translation, scale, rotation) = transform.decompose();
+            // the translation is applied first, so is also scaled and rotated
+            let expected_translation = Transform::identity()
+                .scale_x(2.0)
+                .scale_y(3.0)
+                .scale_z(4.0)
+                .rotate_y(PI / 3.0)
+                * Point3D::new(1.0, -2.0, 3.0);
+            assert_abs_diff_eq!(translation, expected_translation);
+            assert_abs_diff_eq!(scale.0, 2.0, epsilon = 1e-9);
+            assert_abs_diff_eq!(scale.1, 3.0, epsilon = 1e-9);
+            assert_abs_diff_eq!(scale.2, 4.0, epsilon = 1e-9);
+            assert_abs_diff_eq!(
+                rotation,
+                Transform::identity().rotate_y(PI / 3.0).underlying
+            );
+
+            assert_abs_diff_eq!(recompose(translation, scale, rotation), transform);
+        }
+
+        #[test]
+        fn the_identity_transform_should_decompose_into_no_translation_unit_scale_and_no_rotation()
+        {
+            let (translation, scale, rotation) = Transform::identity().decompose();
+
+            assert_eq!(translation, Point3D::ORIGIN);
+            assert_eq!(scale, (1.0, 1.0, 1.0));
+            assert_eq!(rotation, Matrix4D::identity());
+        }
+
+        #[test]
+        fn a_transform_combining_several_rotations_should_recompose_to_the_original() {
+            let transform = Transform::identity()
+                .scale_all(0.5)
+                .rotate_x(0.3)
+                .rotate_z(-1.2)
+                .rotate_y(2.0)
+                .translate_x(5.0)
+                .translate_z(-1.0);
+
+            let (translation, scale, rotation) = transform.decompose();
+            assert_abs_diff_eq!(translation, Point3D::new(5.0, 0.0, -1.0));
+            assert_abs_diff_eq!(scale.0, 0.5, epsilon = 1e-9);
+            assert_abs_diff_eq!(scale.1, 0.5, epsilon = 1e-9);
+            assert_abs_diff_eq!(scale.2, 0.5, epsilon = 1e-9);
+            assert_abs_diff_eq!(rotation.determinant(), 1.0, epsilon = 1e-9);
+
+            assert_abs_diff_eq!(recompose(translation, scale, rotation), transform);
+        }
+
+        #[test]
+        fn a_mirrored_transform_should_decompose_into_a_negative_scale() {
+            let transform = Transform::identity().scale_x(-2.0).rotate_z(0.5);
+
+            let (translation, scale, rotation) = transform.decompose();
+            assert!(
+                scale.0 < 0.0 && scale.1 < 0.0 && scale.2 < 0.0,
+                "{:?}",
+                scale
+            );
+            assert_abs_diff_eq!(rotation.determinant(), 1.0, epsilon = 1e-9);
+
+            assert_abs_diff_eq!(recompose(translation, scale, rotation), transform);
+        }
+    }
 }
 
 mod property_tests {
@@ -362,4 +566,47 @@ mod property_tests {
 
         assert_eq!(expected, actual);
     }
+
+    #[quickcheck]
+    fn transforming_a_point_should_match_transforming_by_the_inverse_of_the_inverse(
+        transform: Transform,
+        point: Point3D,
+    ) {
+        let (x, y, z, _) = transform.inverse().inverse().unwrap() * point;
+        let expected = Point3D::new(x, y, z);
+
+        assert!(approx::abs_diff_eq!(
+            transform.transform_point(point),
+            expected,
+            epsilon = 1e-6
+        ));
+        assert_eq!(transform.transform_point(point), transform * point);
+    }
+
+    #[quickcheck]
+    fn transforming_a_vector_should_match_transforming_by_the_inverse_of_the_inverse(
+        transform: Transform,
+        vector: Vector3D,
+    ) {
+        let (x, y, z, _) = transform.inverse().inverse().unwrap() * vector;
+        let expected = Vector3D::new(x, y, z);
+
+        assert!(approx::abs_diff_eq!(
+            transform.transform_vector(vector),
+            expected,
+            epsilon = 1e-6
+        ));
+        assert_eq!(transform.transform_vector(vector), transform * vector);
+    }
+
+    #[quickcheck]
+    fn a_transform_should_store_the_same_matrix_as_inverting_its_inverse(transform: Transform) {
+        let expected = transform.inverse().inverse().unwrap();
+
+        assert!(approx::abs_diff_eq!(
+            transform.underlying(),
+            expected,
+            epsilon = 1e-6
+        ));
+    }
 }
@@ -154,6 +154,16 @@ mod unit_tests {
         let reflected = vector.reflect_through(normal);
         assert_abs_diff_eq!(reflected, Vector3D::new(1.0, 0.0, 0.0));
     }
+
+    #[test]
+    fn interpolating_between_two_vectors_should_interpolate_each_component() {
+        let start = Vector3D::new(0.0, 4.0, -1.0);
+        let end = Vector3D::new(8.0, 0.0, 3.0);
+
+        assert_eq!(start.lerp(&end, 0.0), start);
+        assert_eq!(start.lerp(&end, 1.0), end);
+        assert_eq!(start.lerp(&end, 0.25), Vector3D::new(2.0, 3.0, 0.0));
+    }
 }
 
 mod property_tests {
@@ -1,5 +1,7 @@
+use crate::core::F64Ext;
 use crate::core::Matrix4D;
 use crate::core::Point3D;
+use crate::core::Quaternion;
 use crate::core::{Normal3D, Vector3D, VectorMaths};
 use approx::AbsDiffEq;
 use std::fmt;
@@ -13,6 +15,8 @@ pub struct Transform {
     // calculating the inverse is relatively expensive, bearing in mind matrices are inverted millions of times per render,
     // so pre-calculating the inverse has massive performance implications
     pub(in crate::core) inverse: Matrix4D,
+    // kept alongside the inverse so transforming points and vectors doesn't need to invert the inverse every time
+    pub(in crate::core) underlying: Matrix4D,
 }
 
 impl Transform {
@@ -21,15 +25,24 @@ impl Transform {
             .inverse()
             .expect("transformation matrix is not invertible");
 
-        Self { inverse }
+        Self {
+            inverse,
+            underlying,
+        }
     }
 
     pub const fn identity() -> Self {
         Self {
             inverse: Matrix4D::identity(),
+            underlying: Matrix4D::identity(),
         }
     }
 
+    /// the rotation represented by the quaternion, e.g. to apply orientations interpolated by `Quaternion::slerp`
+    pub fn from_quaternion(rotation: Quaternion) -> Self {
+        Self::new(rotation.to_matrix())
+    }
+
     pub fn translate_x(self, x: f64) -> Self {
         let translation = Transform::translation(x, 0.0, 0.0);
 
@@ -201,10 +214,108 @@ impl Transform {
         ))
     }
 
+    /// Interpolates between two transforms, such that `t = 0` is `self` and `t = 1` is `other` - see `TransformPath`, which
+    /// should be used instead when interpolating between the same transforms more than once
+    pub fn lerp(&self, other: &Self, t: f64) -> Self {
+        TransformPath::new(*self, *other).at(t)
+    }
+
+    /// builds the transform (and its inverse) which scales, then rotates, then translates, without inverting a matrix
+    #[rustfmt::skip]
+    fn from_parts(translation: Point3D, (x, y, z): (f64, f64, f64), rotation: Quaternion) -> Self {
+        let r = rotation.to_matrix();
+        let (tx, ty, tz) = (translation.x(), translation.y(), translation.z());
+
+        let underlying = Matrix4D::new(
+            [r.m00() * x, r.m01() * y, r.m02() * z, tx],
+            [r.m10() * x, r.m11() * y, r.m12() * z, ty],
+            [r.m20() * x, r.m21() * y, r.m22() * z, tz],
+            [0.0,         0.0,         0.0,         1.0],
+        );
+
+        // the inverse of a rotation is its transpose
+        let row = |(a, b, c): (f64, f64, f64), scale: f64| {
+            [a / scale, b / scale, c / scale, -(a * tx + b * ty + c * tz) / scale]
+        };
+        let inverse = Matrix4D::new(
+            row((r.m00(), r.m10(), r.m20()), x),
+            row((r.m01(), r.m11(), r.m21()), y),
+            row((r.m02(), r.m12(), r.m22()), z),
+            [0.0, 0.0, 0.0, 1.0],
+        );
+
+        Self {
+            inverse,
+            underlying,
+        }
+    }
+
     pub fn inverse(&self) -> Matrix4D {
         self.inverse
     }
 
+    /// applies the transform to the `point`, using the stored forward matrix, rather than inverting the inverse
+    pub fn transform_point(&self, point: Point3D) -> Point3D {
+        let (x, y, z, _) = self.underlying * point;
+
+        Point3D::new(x, y, z)
+    }
+
+    /// as `transform_point`, but for a `vector`, which is unaffected by translations
+    pub fn transform_vector(&self, vector: Vector3D) -> Vector3D {
+        let (x, y, z, _) = self.underlying * vector;
+
+        Vector3D::new(x, y, z)
+    }
+
+    /// the reverse of transforming a surface normal (by the inverse transpose): converts a `normal` of the transformed
+    /// surface back into the untransformed space. The result is not normalised
+    pub fn inverse_transform_normal(&self, normal: Vector3D) -> Vector3D {
+        let (x, y, z, _) = self.underlying.transpose() * normal;
+
+        Vector3D::new(x, y, z)
+    }
+
+    /// Splits the transform into a translation, a scale along each axis, and a rotation, such that scaling, then rotating,
+    /// then translating reproduces the transform.
+    ///
+    /// The rotation is found by polar decomposition, so transforms which can't be built from a single scale, rotation,
+    /// and translation (e.g. shears, or scaling after rotating) are approximated. Reflections are returned as negative scales
+    pub fn decompose(&self) -> (Point3D, (f64, f64, f64), Matrix4D) {
+        let m = self.underlying;
+        let translation = Point3D::new(m.m03(), m.m13(), m.m23());
+        let linear = Matrix4D::new(
+            [m.m00(), m.m01(), m.m02(), 0.0],
+            [m.m10(), m.m11(), m.m12(), 0.0],
+            [m.m20(), m.m21(), m.m22(), 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        );
+
+        // averaging a matrix with its inverse transpose converges (quickly) on the closest orthogonal matrix
+        let mut rotation = linear;
+        for _ in 0..32 {
+            let next = rotation.mean(&rotation.inverse().unwrap().transpose());
+            let converged = next.abs_diff_eq(&rotation, f64::EPSILON * 4.0);
+            rotation = next;
+
+            if converged {
+                break;
+            }
+        }
+
+        if rotation.determinant() < 0.0 {
+            rotation = Self::scaling(-1.0, -1.0, -1.0).underlying * rotation;
+        }
+
+        let stretch = rotation.transpose() * linear;
+
+        (
+            translation,
+            (stretch.m00(), stretch.m11(), stretch.m22()),
+            rotation,
+        )
+    }
+
     pub fn view_transform(eye: Point3D, target: Point3D, up: Normal3D) -> Self {
         let forward = (target - eye).normalised();
         let left = forward.cross(up);
@@ -219,11 +330,48 @@ impl Transform {
 
         orientation * Transform::translation(-eye.x(), -eye.y(), -eye.z())
     }
+
+    /// Places an object at `from`, rotated such that its negative Z axis points towards `target`, and its Y axis is as
+    /// close to `up` as possible.
+    ///
+    /// This is the opposite of `view_transform`, which moves the world in front of a camera, rather than moving an object.
+    ///
+    /// If the `target` is directly above or below `from` (i.e. in the same direction as `up`), any rotation around the
+    /// line to the target would fit, so `up` is instead replaced by whichever of the X, Y, or Z axes is furthest from that line
+    pub fn look_at(from: Point3D, target: Point3D, up: Normal3D) -> Self {
+        let forward = (target - from).normalised();
+        let right = forward.cross(up);
+        let right = if right.magnitude().is_roughly_zero() {
+            let (x, y, z) = (forward.x().abs(), forward.y().abs(), forward.z().abs());
+            let fallback = if x <= y && x <= z {
+                Normal3D::POSITIVE_X
+            } else if y <= z {
+                Normal3D::POSITIVE_Y
+            } else {
+                Normal3D::POSITIVE_Z
+            };
+
+            forward.cross(fallback).normalised()
+        } else {
+            right.normalised()
+        };
+        let true_up = right.cross(forward);
+
+        // each column is one of the object's axes, as seen from the world
+        let orientation = Self::new(Matrix4D::new(
+            [right.x(), true_up.x(), -forward.x(), 0.0],
+            [right.y(), true_up.y(), -forward.y(), 0.0],
+            [right.z(), true_up.z(), -forward.z(), 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ));
+
+        Transform::translation(from.x(), from.y(), from.z()) * orientation
+    }
 }
 
 impl Debug for Transform {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        let underlying = self.inverse.inverse().unwrap();
+        let underlying = self.underlying;
 
         let stringified = format!(
             "\n| {:+.3} | {:+.3} | {:+.3} | {:+.3} |\n| {:+.3} | {:+.3} | {:+.3} | {:+.3} |\n| {:+.3} | {:+.3} | {:+.3} | {:+.3} |\n| {:+.3} | {:+.3} | {:+.3} | {:+.3} |\n",
@@ -249,6 +397,7 @@ impl Mul<Transform> for Transform {
 impl MulAssign<Transform> for Transform {
     fn mul_assign(&mut self, rhs: Transform) {
         self.inverse = rhs.inverse * self.inverse;
+        self.underlying = self.underlying * rhs.underlying;
     }
 }
 
@@ -256,9 +405,7 @@ impl Mul<Point3D> for &Transform {
     type Output = Point3D;
 
     fn mul(self, rhs: Point3D) -> Self::Output {
-        let (x, y, z, _) = self.inverse.inverse().unwrap() * rhs;
-
-        Point3D::new(x, y, z)
+        self.transform_point(rhs)
     }
 }
 
@@ -266,9 +413,7 @@ impl Mul<Vector3D> for &Transform {
     type Output = Vector3D;
 
     fn mul(self, rhs: Vector3D) -> Self::Output {
-        let (x, y, z, _) = self.inverse.inverse().unwrap() * rhs;
-
-        Vector3D::new(x, y, z)
+        self.transform_vector(rhs)
     }
 }
 
@@ -288,6 +433,111 @@ impl Mul<Point3D> for Transform {
     }
 }
 
+/// The path an object takes between two transforms, e.g. while the camera shutter is open, which can be sampled at any point
+/// along the way without decomposing or inverting either transform again.
+///
+/// Each transform is split into a translation, scale, and rotation (see `Transform::decompose`), which are interpolated
+/// separately, with the rotation interpolated by `Quaternion::slerp`, so objects don't shrink part way through a rotation.
+/// Transforms which can't be split up (e.g. shears) are interpolated element-wise instead.
+///
+/// Note: a path from a mirrored transform to one which isn't mirrored (or vice versa) must flatten the object part way through,
+/// where it can't be seen
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TransformPath {
+    start: Transform,
+    end: Transform,
+    decomposed: Option<[Parts; 2]>,
+}
+
+/// the translation, scale, and rotation of a transform - see `Transform::decompose`
+type Parts = (Point3D, (f64, f64, f64), Quaternion);
+
+impl TransformPath {
+    pub fn new(start: Transform, end: Transform) -> Self {
+        let decompose = |transform: &Transform| {
+            let (translation, scale, rotation) = transform.decompose();
+            let rotation = Quaternion::from_matrix(&rotation);
+
+            let recomposed = Transform::from_parts(translation, scale, rotation);
+            if recomposed
+                .underlying
+                .abs_diff_eq(&transform.underlying, 1e-6)
+            {
+                Some((translation, scale, rotation))
+            } else {
+                None
+            }
+        };
+
+        let decomposed = match (decompose(&start), decompose(&end)) {
+            (Some(start), Some(end)) => Some([start, end]),
+            _ => None,
+        };
+
+        TransformPath {
+            start,
+            end,
+            decomposed,
+        }
+    }
+
+    pub fn start(&self) -> Transform {
+        self.start
+    }
+
+    pub fn end(&self) -> Transform {
+        self.end
+    }
+
+    /// the transform at `t`, where 0 is the `start` of the path, and 1 is the `end`
+    pub fn at(&self, t: f64) -> Transform {
+        if t <= 0.0 {
+            return self.start;
+        } else if t >= 1.0 {
+            return self.end;
+        }
+
+        match &self.decomposed {
+            Some(
+                [(start_translation, start_scale, start_rotation), (end_translation, end_scale, end_rotation)],
+            ) => {
+                let lerp = |a: f64, b: f64| a + (b - a) * t;
+
+                let translation = Point3D::new(
+                    lerp(start_translation.x(), end_translation.x()),
+                    lerp(start_translation.y(), end_translation.y()),
+                    lerp(start_translation.z(), end_translation.z()),
+                );
+                let scale = (
+                    lerp(start_scale.0, end_scale.0),
+                    lerp(start_scale.1, end_scale.1),
+                    lerp(start_scale.2, end_scale.2),
+                );
+                let rotation = Quaternion::slerp(*start_rotation, *end_rotation, t);
+
+                // the path passes through a flat transform - the nearest end is as good as anything else
+                if scale.0 == 0.0 || scale.1 == 0.0 || scale.2 == 0.0 {
+                    return if t < 0.5 { self.start } else { self.end };
+                }
+
+                Transform::from_parts(translation, scale, rotation)
+            }
+            None => {
+                let underlying = self.start.underlying.lerp(&self.end.underlying, t);
+
+                match underlying.inverse() {
+                    Some(inverse) => Transform {
+                        inverse,
+                        underlying,
+                    },
+                    None if t < 0.5 => self.start,
+                    None => self.end,
+                }
+            }
+        }
+    }
+}
+
 impl AbsDiffEq for Transform {
     type Epsilon = f64;
 
@@ -310,7 +560,7 @@ mod test_utils {
 
     impl Transform {
         pub fn underlying(&self) -> Matrix4D {
-            self.inverse.inverse().unwrap()
+            self.underlying
         }
     }
 
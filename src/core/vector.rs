@@ -34,6 +34,13 @@ impl Vector3D {
     pub fn new(x: f64, y: f64, z: f64) -> Self {
         Vector3D(x, y, z)
     }
+
+    /// interpolates each component from `self` (when `t` is 0) to `other` (when `t` is 1)
+    ///
+    /// `t` is not clamped, so values outside of 0 to 1 extrapolate beyond either vector
+    pub fn lerp(&self, other: &Vector3D, t: f64) -> Vector3D {
+        *self + (*other - *self) * t
+    }
 }
 
 impl From<Normal3D> for Vector3D {
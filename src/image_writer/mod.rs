@@ -1,18 +1,206 @@
-use crate::renderer::Canvas;
-use image::{ImageBuffer, Rgb, RgbImage};
+use crate::renderer::{Canvas, RenderInfo};
+use anyhow::anyhow;
+use image::png::PngEncoder;
+use image::{ColorType, ImageBuffer, Rgb, RgbImage};
+use std::convert::TryInto;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+#[cfg(test)]
+mod tests;
 
 pub fn write(canvas: Canvas) -> RgbImage {
+    ImageBuffer::from_fn(canvas.width() as _, canvas.height() as _, |x, y| {
+        to_rgb(&canvas, x, y)
+    })
+}
+
+/// as `write`, but encodes each (linear) channel with the sRGB transfer function, so the image looks correct when displayed as sRGB
+pub fn write_srgb(canvas: &Canvas) -> RgbImage {
     ImageBuffer::from_fn(canvas.width() as _, canvas.height() as _, |x, y| {
         let colour = canvas.get(x as _, y as _);
 
         Rgb([
-            clamp(colour.red()),
-            clamp(colour.green()),
-            clamp(colour.blue()),
+            clamp(linear_to_srgb(colour.red())),
+            clamp(linear_to_srgb(colour.green())),
+            clamp(linear_to_srgb(colour.blue())),
         ])
     })
 }
 
+/// The raw (i.e. unclamped) colour of each pixel, as RGB triples in row-major order, preserving values above 1.0
+/// (e.g. for tone mapping or bloom in post-processing)
+pub fn write_hdr(canvas: &Canvas) -> Vec<f32> {
+    let mut pixels = Vec::with_capacity(canvas.width() * canvas.height() * 3);
+
+    for y in 0..canvas.height() {
+        for x in 0..canvas.width() {
+            let colour = canvas.get(x as _, y as _);
+            pixels.extend_from_slice(&[
+                colour.red() as f32,
+                colour.green() as f32,
+                colour.blue() as f32,
+            ]);
+        }
+    }
+
+    pixels
+}
+
+const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+
+/// encodes the canvas as a PNG, with the `info` stored as `tEXt` chunks, which can be read back with `read_png_render_info`
+pub fn write_png_with_info(canvas: Canvas, info: &RenderInfo) -> image::ImageResult<Vec<u8>> {
+    let image = write(canvas);
+
+    let mut png = Vec::new();
+    PngEncoder::new(&mut png).encode(&image, image.width(), image.height(), ColorType::Rgb8)?;
+
+    let text_chunks = info
+        .to_entries()
+        .into_iter()
+        .flat_map(|(key, value)| png_chunk(b"tEXt", format!("{}\0{}", key, value).as_bytes()))
+        .collect::<Vec<_>>();
+
+    // the IHDR chunk must come first, and always has 13 bytes of data - the text chunks can go anywhere after it
+    let after_header = PNG_SIGNATURE.len() + 12 + 13;
+    png.splice(after_header..after_header, text_chunks);
+
+    Ok(png)
+}
+
+/// reads the render info from the `tEXt` chunks of a PNG written by `write_png_with_info`
+pub fn read_png_render_info(png: &[u8]) -> anyhow::Result<RenderInfo> {
+    if !png.starts_with(PNG_SIGNATURE) {
+        anyhow::bail!("not a PNG file");
+    }
+
+    let mut text = Vec::new();
+    let mut offset = PNG_SIGNATURE.len();
+    while offset + 8 <= png.len() {
+        let length = u32::from_be_bytes(png[offset..offset + 4].try_into()?) as usize;
+        let chunk_type = &png[offset + 4..offset + 8];
+        let data = png
+            .get(offset + 8..offset + 8 + length)
+            .ok_or_else(|| anyhow!("truncated {:?} chunk", String::from_utf8_lossy(chunk_type)))?;
+
+        if chunk_type == b"tEXt" {
+            if let Some(separator) = data.iter().position(|&byte| byte == 0) {
+                text.push((
+                    std::str::from_utf8(&data[..separator])?,
+                    std::str::from_utf8(&data[separator + 1..])?,
+                ));
+            }
+        }
+
+        // length, type, data, CRC
+        offset += 4 + 4 + length + 4;
+    }
+
+    RenderInfo::from_entries(text)
+}
+
+fn png_chunk(chunk_type: &[u8; 4], data: &[u8]) -> Vec<u8> {
+    let mut crc = crc32fast::Hasher::new();
+    crc.update(chunk_type);
+    crc.update(data);
+
+    let mut chunk = Vec::with_capacity(data.len() + 12);
+    chunk.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    chunk.extend_from_slice(chunk_type);
+    chunk.extend_from_slice(data);
+    chunk.extend_from_slice(&crc.finalize().to_be_bytes());
+    chunk
+}
+
+/// Compares a rendered `canvas` to a previously written image (e.g. a "golden" reference render),
+/// after clamping the canvas colours to the same 0-255 range as the image.
+///
+/// Pixels match if every channel differs by no more than `tolerance`.
+pub fn compare_to_image(
+    canvas: &Canvas,
+    expected: &RgbImage,
+    tolerance: u8,
+) -> Result<(), Mismatch> {
+    let actual_dimensions = (canvas.width() as u32, canvas.height() as u32);
+    if actual_dimensions != expected.dimensions() {
+        return Err(Mismatch::Dimensions {
+            expected: expected.dimensions(),
+            actual: actual_dimensions,
+        });
+    }
+
+    let pixels = expected
+        .enumerate_pixels()
+        .filter(|(x, y, expected)| {
+            let actual = to_rgb(canvas, *x, *y);
+
+            actual
+                .0
+                .iter()
+                .zip(expected.0.iter())
+                .any(|(&actual, &expected)| {
+                    (actual as i16 - expected as i16).abs() > tolerance as i16
+                })
+        })
+        .map(|(x, y, _)| (x, y))
+        .collect::<Vec<_>>();
+
+    if pixels.is_empty() {
+        Ok(())
+    } else {
+        Err(Mismatch::Pixels(pixels))
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Mismatch {
+    Dimensions {
+        expected: (u32, u32),
+        actual: (u32, u32),
+    },
+    /// the (x, y) coordinates of each pixel differing by more than the tolerance
+    Pixels(Vec<(u32, u32)>),
+}
+
+impl Mismatch {
+    /// the number of mismatched pixels - if the dimensions don't match, every pixel is considered mismatched
+    pub fn count(&self) -> usize {
+        match self {
+            Mismatch::Dimensions { expected, .. } => (expected.0 * expected.1) as usize,
+            Mismatch::Pixels(pixels) => pixels.len(),
+        }
+    }
+}
+
+impl Display for Mismatch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Mismatch::Dimensions { expected, actual } => write!(
+                f,
+                "expected a {}x{} image, but the canvas is {}x{}",
+                expected.0, expected.1, actual.0, actual.1
+            ),
+            Mismatch::Pixels(pixels) => write!(
+                f,
+                "{} pixels differ from the expected image, starting at {:?}",
+                pixels.len(),
+                pixels.first().unwrap()
+            ),
+        }
+    }
+}
+
+fn to_rgb(canvas: &Canvas, x: u32, y: u32) -> Rgb<u8> {
+    let colour = canvas.get(x as _, y as _);
+
+    Rgb([
+        clamp(colour.red()),
+        clamp(colour.green()),
+        clamp(colour.blue()),
+    ])
+}
+
 fn clamp(c: f64) -> u8 {
     if c <= 0.0 {
         0
@@ -22,3 +210,11 @@ fn clamp(c: f64) -> u8 {
         (255.0 * c).round() as _
     }
 }
+
+fn linear_to_srgb(c: f64) -> f64 {
+    if c <= 0.0031308 {
+        12.92 * c
+    } else {
+        1.055 * c.powf(1.0 / 2.4) - 0.055
+    }
+}
@@ -0,0 +1,125 @@
+use super::*;
+
+mod unit_tests {
+    use super::*;
+    use crate::core::Colour;
+    use std::num::NonZeroU16;
+    use std::time::Duration;
+
+    fn canvas() -> Canvas {
+        let mut canvas =
+            Canvas::new(NonZeroU16::new(4).unwrap(), NonZeroU16::new(3).unwrap()).unwrap();
+        canvas.set(0, 0, Colour::new(1.5, 0.0, 0.0));
+        canvas.set(2, 1, Colour::new(0.0, 0.5, 0.0));
+        canvas.set(3, 2, Colour::new(-0.5, 0.25, 1.0));
+
+        canvas
+    }
+
+    #[test]
+    fn srgb_output_should_brighten_linear_mid_tones() {
+        let mut canvas = canvas();
+        canvas.set(1, 0, Colour::greyscale(0.5));
+        canvas.set(2, 0, Colour::new(0.001, 0.2, 0.8));
+
+        let image = write_srgb(&canvas);
+        assert_eq!(image.get_pixel(1, 0), &Rgb([188, 188, 188]));
+        assert_eq!(image.get_pixel(2, 0), &Rgb([3, 124, 231]));
+        // out of range values are still clamped
+        assert_eq!(image.get_pixel(0, 0), &Rgb([255, 0, 0]));
+        assert_eq!(image.get_pixel(3, 2), &Rgb([0, 137, 255]));
+
+        let linear = write(canvas);
+        assert_eq!(linear.get_pixel(1, 0), &Rgb([128, 128, 128]));
+    }
+
+    #[test]
+    fn hdr_output_should_preserve_values_above_1() {
+        let mut canvas = canvas();
+        canvas.set(1, 2, Colour::new(2.5, 0.5, 0.0));
+
+        let hdr = write_hdr(&canvas);
+        assert_eq!(hdr.len(), 4 * 3 * 3);
+        let index = (2 * 4 + 1) * 3;
+        assert_eq!(&hdr[index..index + 3], &[2.5, 0.5, 0.0]);
+        assert_eq!(&hdr[0..3], &[1.5, 0.0, 0.0]);
+
+        let ldr = write(canvas);
+        assert_eq!(ldr.get_pixel(1, 2), &Rgb([255, 128, 0]));
+    }
+
+    #[test]
+    fn a_canvas_should_match_its_own_written_image() {
+        let image = write(canvas());
+
+        assert_eq!(compare_to_image(&canvas(), &image, 0), Ok(()));
+    }
+
+    #[test]
+    fn pixels_differing_by_more_than_the_tolerance_should_be_reported() {
+        let image = write(canvas());
+
+        let mut changed = canvas();
+        changed.set(1, 1, Colour::new(0.0, 0.0, 0.02));
+        changed.set(2, 2, Colour::new(0.1, 0.0, 0.0));
+
+        let mismatch = compare_to_image(&changed, &image, 5);
+        assert_eq!(mismatch, Err(Mismatch::Pixels(vec![(2, 2)])));
+        assert_eq!(mismatch.unwrap_err().count(), 1);
+    }
+
+    #[test]
+    fn colours_out_of_range_should_be_clamped_before_comparing() {
+        let image = write(canvas());
+
+        let mut changed = canvas();
+        changed.set(0, 0, Colour::new(100.0, -3.0, 0.0));
+
+        assert_eq!(compare_to_image(&changed, &image, 0), Ok(()));
+    }
+
+    #[test]
+    fn an_image_with_different_dimensions_should_not_match() {
+        let image = RgbImage::new(3, 4);
+
+        assert_eq!(
+            compare_to_image(&canvas(), &image, u8::MAX),
+            Err(Mismatch::Dimensions {
+                expected: (3, 4),
+                actual: (4, 3)
+            })
+        );
+    }
+
+    #[test]
+    fn render_info_should_round_trip_through_png_text_chunks() {
+        let info = RenderInfo {
+            width: 4,
+            height: 3,
+            samples: 4,
+            seed: Some(42),
+            render_time: Duration::from_millis(250),
+        };
+
+        let png = write_png_with_info(canvas(), &info).unwrap();
+        let read = read_png_render_info(&png);
+        assert!(read.is_ok(), "{}", read.unwrap_err());
+        assert_eq!(read.unwrap(), info);
+    }
+
+    #[test]
+    fn a_png_with_render_info_should_still_decode_to_the_same_image() {
+        let info = RenderInfo {
+            width: 4,
+            height: 3,
+            samples: 1,
+            seed: None,
+            render_time: Duration::from_millis(10),
+        };
+
+        let png = write_png_with_info(canvas(), &info).unwrap();
+        let decoded = image::load_from_memory(&png).unwrap().to_rgb8();
+
+        assert_eq!(compare_to_image(&canvas(), &decoded, 0), Ok(()));
+    }
+}
@@ -5,11 +5,15 @@ pub mod scene;
 pub mod image_writer;
 pub mod ppm_writer;
 
+pub mod ply_parser;
+pub mod stl_parser;
 pub mod wavefront_parser;
 pub mod yaml_parser;
 
 #[cfg(test)]
 pub mod util {
+    use std::path::{Path, PathBuf};
+
     /// default f64 generator generates NaNs, enormous values, and minute values, all of which break
     /// the calculations and test assertions, and none of which are reasonable input values
     /// ("garbage in, garbage out" is a reasonable stance for a ray tracer)
@@ -24,4 +28,37 @@ pub mod util {
             ReasonableF64(thread_rng().gen_range(-1000.0..1000.0))
         }
     }
+
+    /// A new, empty temporary directory for tests which read files, which is deleted (along with everything in it) when dropped
+    pub struct TestDirectory(tempfile::TempDir);
+
+    impl Default for TestDirectory {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    impl TestDirectory {
+        pub fn new() -> Self {
+            TestDirectory(tempfile::tempdir().expect("failed to create a temporary directory"))
+        }
+
+        pub fn path(&self) -> &Path {
+            self.0.path()
+        }
+
+        /// writes the `contents` to a file called `name` in the directory, and returns the path of the file
+        pub fn write(&self, name: &str, contents: impl AsRef<[u8]>) -> PathBuf {
+            let path = self.path().join(name);
+            std::fs::write(&path, contents).unwrap();
+            path
+        }
+
+        /// as `write`, but encodes the `image` in the format matching the extension of the `name`
+        pub fn write_image(&self, name: &str, image: &image::RgbImage) -> PathBuf {
+            let path = self.path().join(name);
+            image.save(&path).unwrap();
+            path
+        }
+    }
 }
@@ -0,0 +1,272 @@
+use std::fs;
+use std::path::PathBuf;
+
+use crate::core::{Point3D, Vector3D, VectorMaths, EPSILON};
+use crate::scene::Object;
+use crate::wavefront_parser::fan;
+use anyhow::*;
+
+#[cfg(test)]
+mod tests;
+
+pub struct PlyParser {
+    resource_path: PathBuf,
+}
+
+impl PlyParser {
+    pub fn new(resource_path: PathBuf) -> Self {
+        Self { resource_path }
+    }
+
+    /// loads an ASCII PLY file as a group of triangles
+    pub fn load(&self, file_name: &str) -> anyhow::Result<Object> {
+        if !file_name.ends_with(".ply") {
+            bail!("{} is not a .ply file", file_name);
+        };
+
+        let file = self.resource_path.join(file_name);
+        println!("loading PLY file {}", file.to_str().unwrap());
+        let contents = fs::read_to_string(file)?;
+
+        parse_ply(&contents)
+    }
+}
+
+#[derive(Debug)]
+struct Element {
+    name: String,
+    count: usize,
+    properties: Vec<Property>,
+}
+
+#[derive(Debug)]
+enum Property {
+    Scalar(String),
+    List(String),
+}
+
+/// the values of a single line in the body of the file
+#[derive(Default)]
+struct Row {
+    scalars: Vec<(String, f64)>,
+    lists: Vec<(String, Vec<f64>)>,
+}
+
+impl Row {
+    fn scalar(&self, name: &str) -> Option<f64> {
+        self.scalars
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| *v)
+    }
+
+    fn list(&self, name: &str) -> Option<&[f64]> {
+        self.lists
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.as_slice())
+    }
+}
+
+fn parse_ply(contents: &str) -> anyhow::Result<Object> {
+    let mut lines = contents
+        .lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty());
+
+    let elements = parse_header(&mut lines)?;
+
+    let mut vertices = vec![];
+    let mut normals = vec![];
+    let mut faces = vec![];
+
+    for element in &elements {
+        for _ in 0..element.count {
+            let (line_number, line) = lines.next().ok_or_else(|| {
+                anyhow!(
+                    "unexpected end of file while reading `{}` elements",
+                    element.name
+                )
+            })?;
+            let row = parse_row(element, line, line_number)?;
+
+            match element.name.as_str() {
+                "vertex" => {
+                    let (x, y, z) = read_triple(&row, ["x", "y", "z"])
+                        .ok_or_else(|| anyhow!("vertex on line {} has no position", line_number))?;
+                    vertices.push(Point3D::new(x, y, z));
+
+                    if let Some((x, y, z)) = read_triple(&row, ["nx", "ny", "nz"]) {
+                        let normal = Vector3D::new(x, y, z);
+                        // a zero normal has no direction, so can't be used to smooth any triangles using this vertex
+                        normals.push(Some(normal).filter(|normal| normal.magnitude() > EPSILON));
+                    }
+                }
+                "face" => {
+                    let indices = row
+                        .list("vertex_indices")
+                        .or_else(|| row.list("vertex_index"))
+                        .ok_or_else(|| {
+                            anyhow!("face on line {} has no vertex indices", line_number)
+                        })?;
+                    faces.push((line_number, indices.to_vec()));
+                }
+                // other elements (e.g. `edge`) aren't needed to build a mesh
+                _ => (),
+            }
+        }
+    }
+
+    // normals are only usable if every vertex has one
+    let use_normals = !normals.is_empty() && normals.len() == vertices.len();
+
+    let mut triangles = vec![];
+    for (line_number, face) in faces {
+        let face = face
+            .into_iter()
+            .map(|index| {
+                if index.fract() != 0.0 {
+                    Err(anyhow!(
+                        "vertex reference `{}` in face on line {} is not an integer",
+                        index,
+                        line_number
+                    ))
+                } else if index >= 0.0 && (index as usize) < vertices.len() {
+                    Ok(index as usize)
+                } else {
+                    Err(anyhow!(
+                        "invalid vertex reference `{}` in face on line {}",
+                        index,
+                        line_number
+                    ))
+                }
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        for [a, b, c] in fan(&face) {
+            let vertex_normals = if use_normals {
+                normals[a].zip(normals[b]).zip(normals[c])
+            } else {
+                None
+            };
+
+            // as with STL files, a triangle with a zero vertex normal is left flat
+            let triangle = match vertex_normals {
+                Some(((normal_a, normal_b), normal_c)) => Object::smooth_triangle(
+                    vertices[a],
+                    vertices[b],
+                    vertices[c],
+                    normal_a.normalised(),
+                    normal_b.normalised(),
+                    normal_c.normalised(),
+                ),
+                None => Object::triangle(vertices[a], vertices[b], vertices[c]),
+            };
+
+            triangles.push(triangle);
+        }
+    }
+
+    Ok(Object::group(triangles))
+}
+
+fn parse_header<'a>(
+    lines: &mut impl Iterator<Item = (usize, &'a str)>,
+) -> anyhow::Result<Vec<Element>> {
+    if lines.next().map(|(_, line)| line) != Some("ply") {
+        bail!("not a valid PLY file");
+    }
+
+    let mut elements: Vec<Element> = vec![];
+
+    for (line_number, line) in lines {
+        let mut tokens = line.split_whitespace();
+
+        match tokens.next() {
+            Some("format") => {
+                let format = tokens.next();
+                if format != Some("ascii") {
+                    bail!(
+                        "unsupported PLY format `{}`; only ASCII files are supported",
+                        format.unwrap_or_default()
+                    );
+                }
+            }
+            Some("element") => {
+                let (name, count) = match (tokens.next(), tokens.next()) {
+                    (Some(name), Some(count)) => (name, count),
+                    _ => bail!("expected `element <name> <count>` on line {}", line_number),
+                };
+                let count = count
+                    .parse()
+                    .with_context(|| format!("invalid element count on line {}", line_number))?;
+
+                elements.push(Element {
+                    name: name.to_owned(),
+                    count,
+                    properties: vec![],
+                });
+            }
+            Some("property") => {
+                let element = elements.last_mut().ok_or_else(|| {
+                    anyhow!("property on line {} is not part of an element", line_number)
+                })?;
+
+                // the property types aren't needed, as every value is read as an `f64`
+                let property = match tokens.next() {
+                    Some("list") => tokens.nth(2).map(|name| Property::List(name.to_owned())),
+                    Some(_) => tokens.next().map(|name| Property::Scalar(name.to_owned())),
+                    None => None,
+                }
+                .ok_or_else(|| anyhow!("invalid property on line {}", line_number))?;
+
+                element.properties.push(property);
+            }
+            Some("end_header") => return Ok(elements),
+            // `comment` and `obj_info` carry no information
+            _ => (),
+        }
+    }
+
+    bail!("missing `end_header`")
+}
+
+fn parse_row(element: &Element, line: &str, line_number: usize) -> anyhow::Result<Row> {
+    let mut tokens = line.split_whitespace();
+    let mut next = || -> anyhow::Result<f64> {
+        tokens
+            .next()
+            .ok_or_else(|| {
+                anyhow!(
+                    "`{}` on line {} has too few values",
+                    element.name,
+                    line_number
+                )
+            })?
+            .parse()
+            .with_context(|| format!("invalid number on line {}", line_number))
+    };
+
+    let mut row = Row::default();
+    for property in &element.properties {
+        match property {
+            Property::Scalar(name) => row.scalars.push((name.clone(), next()?)),
+            Property::List(name) => {
+                let length = next()? as usize;
+                let values = (0..length).map(|_| next()).collect::<Result<_>>()?;
+                row.lists.push((name.clone(), values));
+            }
+        }
+    }
+
+    Ok(row)
+}
+
+fn read_triple(row: &Row, names: [&str; 3]) -> Option<(f64, f64, f64)> {
+    Some((
+        row.scalar(names[0])?,
+        row.scalar(names[1])?,
+        row.scalar(names[2])?,
+    ))
+}
@@ -0,0 +1,215 @@
+use super::*;
+use crate::core::{Normal3D, Ray};
+use crate::scene::HitData;
+use approx::*;
+
+const TETRAHEDRON: &str = "ply
+format ascii 1.0
+comment a regular tetrahedron, with normals pointing away from the centre
+element vertex 4
+property float x
+property float y
+property float z
+property float nx
+property float ny
+property float nz
+element face 4
+property list uchar int vertex_indices
+end_header
+1 1 1 1 1 1
+1 -1 -1 1 -1 -1
+-1 1 -1 -1 1 -1
+-1 -1 1 -1 -1 1
+3 0 1 2
+3 0 3 1
+3 0 2 3
+3 1 3 2
+";
+
+#[test]
+fn a_file_with_only_a_header_should_produce_an_empty_group() {
+    let input = "ply
+format ascii 1.0
+element vertex 0
+property float x
+property float y
+property float z
+element face 0
+property list uchar int vertex_indices
+end_header
+";
+
+    let group = parse_ply(input);
+    assert!(group.is_ok(), "{}", group.unwrap_err());
+    assert!(group.unwrap().children().is_empty());
+}
+
+#[test]
+fn a_tetrahedron_with_vertex_normals_should_produce_smooth_triangles() {
+    let tetrahedron = parse_ply(TETRAHEDRON);
+    assert!(tetrahedron.is_ok(), "{}", tetrahedron.unwrap_err());
+    let tetrahedron = tetrahedron.unwrap();
+
+    assert_eq!(tetrahedron.children().len(), 4);
+
+    // close to the first vertex, so the interpolated normal is close to the first vertex normal, rather than the face normal
+    let point = Point3D::new(0.8, 0.8, 0.6);
+    let face_normal = Vector3D::new(1.0, 1.0, -1.0).normalised();
+    let ray = Ray::new(point + face_normal * 5.0, -face_normal);
+
+    let intersections = tetrahedron.intersect(&ray);
+    let hit = intersections.hit(None).unwrap();
+    let hit_data = HitData::from(&ray, hit, intersections);
+    assert_abs_diff_eq!(
+        hit_data.normal,
+        Vector3D::new(0.8, 0.8, 0.6).normalised(),
+        epsilon = 1e-9
+    );
+}
+
+#[test]
+fn polygon_faces_should_be_split_into_triangles() {
+    let input = "ply
+format ascii 1.0
+element vertex 5
+property float x
+property float y
+property float z
+element face 1
+property list uchar int vertex_indices
+end_header
+0 0 0
+1 0 0
+1.5 1 0
+0.5 1.5 0
+-0.5 1 0
+5 0 1 2 3 4
+";
+
+    let pentagon = parse_ply(input);
+    assert!(pentagon.is_ok(), "{}", pentagon.unwrap_err());
+    let pentagon = pentagon.unwrap();
+
+    assert_eq!(pentagon.children().len(), 3);
+    let ray = Ray::new(Point3D::new(0.5, 1.0, -1.0), Normal3D::POSITIVE_Z);
+    assert_eq!(pentagon.intersect(&ray).len(), 1);
+}
+
+#[test]
+fn elements_other_than_vertices_and_faces_should_be_skipped() {
+    let input = "ply
+format ascii 1.0
+element vertex 3
+property float x
+property float y
+property float z
+property uchar red
+element face 1
+property list uchar int vertex_index
+element edge 1
+property int vertex1
+property int vertex2
+end_header
+0 0 0 255
+1 0 0 255
+0 1 0 255
+3 0 1 2
+0 1
+";
+
+    let triangle = parse_ply(input);
+    assert!(triangle.is_ok(), "{}", triangle.unwrap_err());
+    assert_eq!(triangle.unwrap().children().len(), 1);
+}
+
+#[test]
+fn a_face_referencing_a_missing_vertex_should_fail_to_parse() {
+    let input = "ply
+format ascii 1.0
+element vertex 3
+property float x
+property float y
+property float z
+element face 1
+property list uchar int vertex_indices
+end_header
+0 0 0
+1 0 0
+0 1 0
+3 0 1 3
+";
+
+    assert!(parse_ply(input).is_err());
+}
+
+#[test]
+fn a_face_with_a_non_integer_vertex_reference_should_fail_to_parse() {
+    let input = "ply
+format ascii 1.0
+element vertex 3
+property float x
+property float y
+property float z
+element face 1
+property list uchar float vertex_indices
+end_header
+0 0 0
+1 0 0
+0 1 0
+3 0 1.5 2
+";
+
+    let error = parse_ply(input).unwrap_err().to_string();
+    assert_eq!(
+        error,
+        "vertex reference `1.5` in face on line 13 is not an integer"
+    );
+}
+
+#[test]
+fn a_triangle_with_a_zero_vertex_normal_should_be_flat() {
+    let input = "ply
+format ascii 1.0
+element vertex 3
+property float x
+property float y
+property float z
+property float nx
+property float ny
+property float nz
+element face 1
+property list uchar int vertex_indices
+end_header
+0 0 0 0 0 0
+1 0 0 0.6 0 -0.8
+0 1 0 0 0.6 -0.8
+3 0 1 2
+";
+
+    let triangle = parse_ply(input);
+    assert!(triangle.is_ok(), "{}", triangle.unwrap_err());
+    let triangle = triangle.unwrap();
+
+    let ray = Ray::new(Point3D::new(0.25, 0.25, -1.0), Normal3D::POSITIVE_Z);
+    let intersections = triangle.intersect(&ray);
+    let hit = intersections.hit(None).unwrap();
+    let hit_data = HitData::from(&ray, hit, intersections);
+    assert_abs_diff_eq!(hit_data.normal, Normal3D::NEGATIVE_Z);
+}
+
+#[test]
+fn a_binary_ply_file_should_fail_to_parse() {
+    let input = "ply
+format binary_little_endian 1.0
+element vertex 0
+end_header
+";
+
+    assert!(parse_ply(input).is_err());
+}
+
+#[test]
+fn loading_a_file_without_the_ply_extension_should_fail() {
+    let parser = PlyParser::new(PathBuf::new());
+    assert!(parser.load("model.obj").is_err());
+}
@@ -1,4 +1,5 @@
-use crate::renderer::Canvas;
+use crate::renderer::{Canvas, RenderInfo};
+use anyhow::*;
 
 #[cfg(test)]
 mod tests;
@@ -6,17 +7,69 @@ mod tests;
 const MAX_COLOUR_VALUE: usize = 255;
 
 pub fn write_ppm(canvas: &Canvas) -> String {
-    let header = ppm_header(canvas.width(), canvas.height());
+    let header = ppm_header("P3", canvas.width(), canvas.height(), "");
     let pixels = pixel_data(canvas);
 
     format!("{}{}", header, pixels)
 }
 
-fn ppm_header(width: usize, height: usize) -> String {
-    // P3 - PPM version magic number
+/// writes a binary (`P6`) PPM, which is much smaller than the ASCII output of `write_ppm`, particularly for large images
+pub fn write_ppm_binary(canvas: &Canvas) -> Vec<u8> {
+    let header = ppm_header("P6", canvas.width(), canvas.height(), "");
+
+    let mut ppm = Vec::with_capacity(header.len() + canvas.width() * canvas.height() * 3);
+    ppm.extend_from_slice(header.as_bytes());
+
+    for y in 0..canvas.height() {
+        for x in 0..canvas.width() {
+            let colour = canvas.get(x as _, y as _);
+
+            ppm.push(ppm_colour_value(colour.red()) as u8);
+            ppm.push(ppm_colour_value(colour.green()) as u8);
+            ppm.push(ppm_colour_value(colour.blue()) as u8);
+        }
+    }
+
+    ppm
+}
+
+/// writes the `info` as comments in the header, which can be read back with `read_render_info`
+pub fn write_ppm_with_info(canvas: &Canvas, info: &RenderInfo) -> String {
+    let comments = info
+        .to_entries()
+        .into_iter()
+        .map(|(key, value)| format!("# {}: {}\n", key, value))
+        .collect::<String>();
+
+    let header = ppm_header("P3", canvas.width(), canvas.height(), &comments);
+    let pixels = pixel_data(canvas);
+
+    format!("{}{}", header, pixels)
+}
+
+/// reads the render info from the comments in the header of a PPM written by `write_ppm_with_info`
+pub fn read_render_info(ppm: &str) -> anyhow::Result<RenderInfo> {
+    let mut lines = ppm.lines();
+    if lines.next() != Some("P3") {
+        bail!("not a P3 PPM file");
+    }
+
+    let entries = lines
+        .take_while(|line| line.starts_with('#'))
+        .filter_map(|line| line.trim_start_matches('#').trim().split_once(": "));
+
+    RenderInfo::from_entries(entries)
+}
+
+fn ppm_header(magic: &str, width: usize, height: usize, comments: &str) -> String {
+    // P3 or P6 - PPM version magic number (ASCII or binary pixel data, respectively)
+    // comments - optional lines starting with `#`
     // width height - output size
     // 255 - colour range (0 - 255)
-    format!("P3\n{} {}\n{}\n", width, height, MAX_COLOUR_VALUE)
+    format!(
+        "{}\n{}{} {}\n{}\n",
+        magic, comments, width, height, MAX_COLOUR_VALUE
+    )
 }
 
 fn pixel_data(canvas: &Canvas) -> String {
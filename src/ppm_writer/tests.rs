@@ -4,6 +4,7 @@ mod unit_tests {
     use super::*;
     use crate::core::Colour;
     use std::num::NonZeroU16;
+    use std::time::Duration;
 
     #[test]
     fn should_generate_correct_header() {
@@ -105,4 +106,116 @@ mod unit_tests {
 
         assert_eq!(ppm.chars().last(), Some('\n'))
     }
+
+    #[test]
+    fn should_write_render_info_as_header_comments() {
+        let canvas = Canvas::new(NonZeroU16::new(5).unwrap(), NonZeroU16::new(3).unwrap()).unwrap();
+        let info = RenderInfo {
+            width: 5,
+            height: 3,
+            samples: 16,
+            seed: Some(4),
+            render_time: Duration::from_millis(1234),
+        };
+
+        let ppm = write_ppm_with_info(&canvas, &info);
+        let header = ppm
+            .lines()
+            .take(7)
+            .map(|line| format!("{}\n", line))
+            .collect::<String>();
+
+        assert_eq!(
+            header,
+            "P3
+# Resolution: 5x3
+# Samples: 16
+# Seed: 4
+# Render time: 1234ms
+5 3
+255
+"
+        );
+        assert_eq!(
+            ppm.lines().skip(7).collect::<Vec<_>>(),
+            write_ppm(&canvas).lines().skip(3).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn render_info_should_round_trip_through_the_ppm_header() {
+        let canvas = Canvas::new(NonZeroU16::new(4).unwrap(), NonZeroU16::new(2).unwrap()).unwrap();
+
+        vec![
+            RenderInfo {
+                width: 4,
+                height: 2,
+                samples: 1,
+                seed: None,
+                render_time: Duration::from_millis(0),
+            },
+            RenderInfo {
+                width: 4,
+                height: 2,
+                samples: 25,
+                seed: Some(u64::MAX),
+                render_time: Duration::from_secs(3600),
+            },
+        ]
+        .into_iter()
+        .for_each(|info| {
+            let ppm = write_ppm_with_info(&canvas, &info);
+            let read = read_render_info(&ppm);
+            assert!(read.is_ok(), "{}", read.unwrap_err());
+            assert_eq!(read.unwrap(), info);
+        });
+    }
+
+    #[test]
+    fn reading_render_info_from_a_ppm_without_comments_should_fail() {
+        let ppm = write_ppm(
+            &Canvas::new(NonZeroU16::new(5).unwrap(), NonZeroU16::new(3).unwrap()).unwrap(),
+        );
+
+        assert!(read_render_info(&ppm).is_err());
+    }
+
+    #[test]
+    fn a_binary_ppm_should_round_trip_the_header_and_pixel_data() {
+        let mut canvas =
+            Canvas::new(NonZeroU16::new(5).unwrap(), NonZeroU16::new(3).unwrap()).unwrap();
+        canvas.set(0, 0, Colour::new(1.5, 0.0, 0.0));
+        canvas.set(2, 1, Colour::new(0.0, 0.5, 0.0));
+        canvas.set(4, 2, Colour::new(-0.5, 0.25, 1.0));
+
+        let ppm = write_ppm_binary(&canvas);
+
+        let header = "P6\n5 3\n255\n";
+        assert_eq!(&ppm[..header.len()], header.as_bytes());
+
+        let pixels = &ppm[header.len()..];
+        assert_eq!(pixels.len(), 5 * 3 * 3);
+
+        vec![(0, 0), (2, 1), (4, 2), (1, 1)]
+            .into_iter()
+            .for_each(|(x, y)| {
+                let colour = canvas.get(x, y);
+                let offset = (y as usize * 5 + x as usize) * 3;
+
+                assert_eq!(
+                    &pixels[offset..offset + 3],
+                    &[
+                        ppm_colour_value(colour.red()) as u8,
+                        ppm_colour_value(colour.green()) as u8,
+                        ppm_colour_value(colour.blue()) as u8,
+                    ],
+                    "{:?}",
+                    (x, y)
+                );
+            });
+
+        assert_eq!(&pixels[0..3], &[255, 0, 0]);
+        assert_eq!(&pixels[(5 + 2) * 3..(5 + 2) * 3 + 3], &[0, 128, 0]);
+        assert_eq!(&pixels[(2 * 5 + 4) * 3..], &[0, 64, 255]);
+    }
 }
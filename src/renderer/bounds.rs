@@ -0,0 +1,102 @@
+use super::*;
+use crate::core::Colour;
+use crate::scene::World;
+
+/// the colour of the edges of each box, based on how deeply it's nested - deeper boxes reuse the colours
+const DEPTH_COLOURS: [Colour; 6] = [
+    Colour::WHITE,
+    Colour::RED,
+    Colour::GREEN,
+    Colour::BLUE,
+    Colour::new(1.0, 1.0, 0.0),
+    Colour::new(0.0, 1.0, 1.0),
+];
+
+/// Draws the edges of the bounding box of every object in the `world` (including every group, CSG, and shape nested inside them),
+/// as seen by the `camera`, onto a black canvas, to help diagnose how the world has been split into groups.
+///
+/// The colour of each box depends on how deeply it's nested. Unbounded objects (e.g. planes) are skipped, as are edges
+/// which pass behind the camera
+pub fn render_bounds(world: &World, camera: &Camera) -> Canvas {
+    let mut canvas =
+        Canvas::new(camera.width(), camera.height()).expect("Camera dimensions are too large");
+
+    let all_bounds = world.objects().flat_map(|object| object.nested_bounds());
+    for (depth, bounds) in all_bounds.filter(|(_, bounds)| !bounds.is_unbounded()) {
+        let colour = DEPTH_COLOURS[depth % DEPTH_COLOURS.len()];
+
+        for (start, end) in bounds.edges().iter() {
+            if let (Some(start), Some(end)) = (camera.project(*start), camera.project(*end)) {
+                draw_line(&mut canvas, start, end, colour);
+            }
+        }
+    }
+
+    canvas
+}
+
+/// draws the part of the line between the pixel coordinates `start` and `end` which lies within the `canvas`
+fn draw_line(canvas: &mut Canvas, start: (f64, f64), end: (f64, f64), colour: Colour) {
+    let width = canvas.width() as f64;
+    let height = canvas.height() as f64;
+
+    let (start, end) = match clip(start, end, width, height) {
+        Some(clipped) => clipped,
+        None => return,
+    };
+
+    let (dx, dy) = (end.0 - start.0, end.1 - start.1);
+    let steps = dx.abs().max(dy.abs()).ceil().max(1.0);
+
+    for step in 0..=steps as usize {
+        let fraction = step as f64 / steps;
+        let x = (start.0 + dx * fraction).floor().clamp(0.0, width - 1.0);
+        let y = (start.1 + dy * fraction).floor().clamp(0.0, height - 1.0);
+
+        canvas.set(x as u16, y as u16, colour);
+    }
+}
+
+/// Liang-Barsky clipping of a line to the rectangle from `(0, 0)` to `(width, height)` - returns `None` if the line lies
+/// entirely outside of the rectangle
+fn clip(
+    start: (f64, f64),
+    end: (f64, f64),
+    width: f64,
+    height: f64,
+) -> Option<((f64, f64), (f64, f64))> {
+    let (dx, dy) = (end.0 - start.0, end.1 - start.1);
+    let mut entry: f64 = 0.0;
+    let mut exit: f64 = 1.0;
+
+    // each pair is the (negated) change along the line, and the distance from the start to one edge of the rectangle
+    let edges = [
+        (-dx, start.0),
+        (dx, width - start.0),
+        (-dy, start.1),
+        (dy, height - start.1),
+    ];
+
+    for (change, distance) in edges {
+        if change == 0.0 {
+            // parallel to this edge - either entirely inside it, or entirely outside
+            if distance < 0.0 {
+                return None;
+            }
+        } else {
+            let t = distance / change;
+            if change < 0.0 {
+                entry = entry.max(t);
+            } else {
+                exit = exit.min(t);
+            }
+        }
+    }
+
+    if entry > exit {
+        return None;
+    }
+
+    let at = |t: f64| (start.0 + dx * t, start.1 + dy * t);
+    Some((at(entry), at(exit)))
+}
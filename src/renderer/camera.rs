@@ -1,5 +1,6 @@
 use crate::core::Ray;
-use crate::core::{Point3D, Transform, VectorMaths};
+use crate::core::{Normal3D, Point3D, Transform, Vector3D, VectorMaths};
+use crate::renderer::Samples;
 use std::num::NonZeroU16;
 
 #[derive(Debug, PartialEq)]
@@ -10,6 +11,15 @@ pub struct Camera {
     pub(super) pixel_size: f64,
     half_canvas_width: f64,
     half_canvas_height: f64,
+    projection: Projection,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum Projection {
+    /// every ray starts at the camera, and spreads out across the field of view
+    Perspective,
+    /// every ray travels in the same direction, starting from a different point on the view plane
+    Orthographic,
 }
 
 impl Camera {
@@ -40,6 +50,30 @@ impl Camera {
             pixel_size,
             half_canvas_width,
             half_canvas_height,
+            projection: Projection::Perspective,
+        }
+    }
+
+    /// A camera with a parallel projection, such that objects don't get smaller as they move further from the camera.
+    ///
+    /// `view_width` is the width of the area visible to the camera, in world units
+    pub fn orthographic(
+        width: NonZeroU16,
+        height: NonZeroU16,
+        view_width: f64,
+        transform: Transform,
+    ) -> Self {
+        let aspect_ratio = width.get() as f64 / height.get() as f64;
+        let half_canvas_width = view_width / 2.0;
+
+        Camera {
+            width,
+            height,
+            transform,
+            pixel_size: view_width / width.get() as f64,
+            half_canvas_width,
+            half_canvas_height: half_canvas_width / aspect_ratio,
+            projection: Projection::Orthographic,
         }
     }
 
@@ -52,6 +86,16 @@ impl Camera {
 
         let inverse = self.transform.inverse();
 
+        if self.projection == Projection::Orthographic {
+            let (x, y, z, _) = inverse * Point3D::new(world_x, world_y, 0.0);
+            let origin = Point3D::new(x, y, z);
+
+            let (x, y, z, _) = inverse * Vector3D::new(0.0, 0.0, -1.0);
+            let direction = Vector3D::new(x, y, z).normalised();
+
+            return Ray::new(origin, direction);
+        }
+
         let (x, y, z, _) = &inverse * Point3D::new(world_x, world_y, -1.0);
         let pixel = Point3D::new(x, y, z);
 
@@ -62,6 +106,102 @@ impl Camera {
         Ray::new(origin, direction)
     }
 
+    /// Every ray the renderer may cast through the pixel at `(x, y)` for the given `samples`, including the time each ray is cast
+    /// at (for motion blur) - i.e. the corners of the sample grid, followed by the inner samples.
+    ///
+    /// Note: the renderer skips the inner samples when the corners are all similar, whereas this always includes them
+    pub fn rays<'a>(
+        &'a self,
+        x: u16,
+        y: u16,
+        samples: &'a Samples,
+    ) -> impl Iterator<Item = Ray> + 'a {
+        samples
+            .corner_offsets()
+            .chain(samples.inner_offsets())
+            .map(move |&offset| {
+                let (x_offset, y_offset) = samples.jittered(x, y, offset);
+
+                self.ray_at(x, y, x_offset, y_offset)
+                    .with_time(samples.shutter_time(x_offset, y_offset))
+            })
+    }
+
+    /// The inverse of `ray_at` - the (fractional) pixel coordinates of the given world-space `point`, which may lie outside of the
+    /// image, or `None` if the point is behind a perspective camera
+    pub fn project(&self, point: Point3D) -> Option<(f64, f64)> {
+        let point = self.transform * point;
+        let (x, y, z) = (point.x(), point.y(), point.z());
+
+        let (world_x, world_y) = match self.projection {
+            Projection::Orthographic => (x, y),
+            // the view plane is 1 unit in front of the camera, looking down the negative z axis
+            Projection::Perspective if z < 0.0 => (x / -z, y / -z),
+            Projection::Perspective => return None,
+        };
+
+        Some((
+            (self.half_canvas_width - world_x) / self.pixel_size,
+            (self.half_canvas_height - world_y) / self.pixel_size,
+        ))
+    }
+
+    /// The six world-space planes bounding the volume visible to the camera, in the order left, right, top, bottom, near, far.
+    ///
+    /// Each plane is an inward-facing normal and a distance, such that a point lies inside the plane if
+    /// `normal · point + distance >= 0`. The near plane passes through the camera (or the view plane, for an orthographic
+    /// camera), and the camera has no far limit, so the far plane is infinitely distant
+    pub fn frustum_planes(&self) -> [(Normal3D, f64); 6] {
+        let inverse = self.transform.inverse();
+        let to_world = |x: f64, y: f64, z: f64| {
+            let (x, y, z, _) = inverse * Point3D::new(x, y, z);
+            Point3D::new(x, y, z)
+        };
+
+        // the corners of the view plane, 1 unit in front of the camera
+        let (w, h) = (self.half_canvas_width, self.half_canvas_height);
+        let corner = |x_sign: f64, y_sign: f64| (x_sign * w, y_sign * h);
+        // the point each ray through the corner of the view plane starts from
+        let origin = |(x, y): (f64, f64)| match self.projection {
+            Projection::Perspective => to_world(0.0, 0.0, 0.0),
+            Projection::Orthographic => to_world(x, y, 0.0),
+        };
+        let inside = to_world(0.0, 0.0, -1.0);
+
+        let plane = |on: Point3D, first: Point3D, second: Point3D| {
+            let normal = (first - on).cross(second - on).normalised();
+            let normal = if normal.dot(inside - on) < 0.0 {
+                -normal
+            } else {
+                normal
+            };
+
+            (normal, -normal.dot(on - Point3D::ORIGIN))
+        };
+        let side = |(first, second): ((f64, f64), (f64, f64))| {
+            plane(
+                origin(first),
+                to_world(first.0, first.1, -1.0),
+                to_world(second.0, second.1, -1.0),
+            )
+        };
+
+        let near = plane(
+            to_world(0.0, 0.0, 0.0),
+            to_world(1.0, 0.0, 0.0),
+            to_world(0.0, 1.0, 0.0),
+        );
+
+        [
+            side((corner(1.0, 1.0), corner(1.0, -1.0))),
+            side((corner(-1.0, 1.0), corner(-1.0, -1.0))),
+            side((corner(1.0, 1.0), corner(-1.0, 1.0))),
+            side((corner(1.0, -1.0), corner(-1.0, -1.0))),
+            near,
+            (-near.0, f64::INFINITY),
+        ]
+    }
+
     pub fn width(&self) -> NonZeroU16 {
         self.width
     }
@@ -1,6 +1,7 @@
 use crate::core::Colour;
 use indicatif::{ProgressBar, ProgressStyle};
 use std::num::NonZeroU16;
+use std::sync::atomic::{AtomicBool, AtomicU16, Ordering};
 
 pub struct Canvas(Vec<Vec<Colour>>);
 
@@ -61,14 +62,69 @@ impl Canvas {
         self.0[y][x] = colour
     }
 
+    /// adds the colour of each pixel of `other` to the same pixel of this canvas, e.g. to accumulate multiple passes
+    ///
+    /// # Panics
+    /// Panics if the canvases aren't the same size
+    pub fn add(&mut self, other: &Canvas) {
+        self.assert_same_size(other);
+
+        self.pixels_mut()
+            .zip(other.pixels())
+            .for_each(|(pixel, other)| *pixel = *pixel + *other);
+    }
+
+    /// multiplies the colour of every pixel by `factor`, e.g. to average accumulated passes
+    pub fn scale(&mut self, factor: f64) {
+        self.pixels_mut().for_each(|pixel| *pixel = *pixel * factor);
+    }
+
+    /// a new canvas, with each pixel interpolated between this canvas (`t = 0`) and `other` (`t = 1`) - see `Colour::lerp`
+    ///
+    /// # Panics
+    /// Panics if the canvases aren't the same size
+    pub fn blend(&self, other: &Canvas, t: f64) -> Canvas {
+        self.assert_same_size(other);
+
+        let rows = self
+            .0
+            .iter()
+            .zip(other.0.iter())
+            .map(|(row, other_row)| {
+                row.iter()
+                    .zip(other_row.iter())
+                    .map(|(pixel, other)| pixel.lerp(other, t))
+                    .collect()
+            })
+            .collect();
+
+        Canvas(rows)
+    }
+
+    fn pixels(&self) -> impl Iterator<Item = &Colour> {
+        self.0.iter().flatten()
+    }
+
+    fn pixels_mut(&mut self) -> impl Iterator<Item = &mut Colour> {
+        self.0.iter_mut().flatten()
+    }
+
+    fn assert_same_size(&self, other: &Canvas) {
+        assert!(
+            self.width() == other.width() && self.height() == other.height(),
+            "cannot combine a {}x{} canvas with a {}x{} canvas",
+            self.width(),
+            self.height(),
+            other.width(),
+            other.height()
+        );
+    }
+
     pub fn draw<F>(&mut self, show_progress: bool, f: F)
     where
         F: Fn(u16, u16) -> Colour,
         F: Sync + Send,
     {
-        use indicatif::ParallelProgressIterator;
-        use rayon::prelude::*;
-
         let progress_bar = if show_progress {
             ProgressBar::new(self.height() as u64)
                 .with_style(ProgressStyle::default_bar().template("Rendering: {percent}%"))
@@ -76,14 +132,54 @@ impl Canvas {
             ProgressBar::hidden()
         };
 
-        self.0
-            .par_iter_mut()
-            .progress_with(progress_bar)
-            .enumerate()
-            .for_each(|(y, row)| {
-                for (x, pixel) in row.iter_mut().enumerate() {
-                    *pixel = f(x as _, y as _)
+        self.draw_with_progress(f, |_, _| progress_bar.inc(1));
+        progress_bar.finish();
+    }
+
+    /// as `draw`, but calls `on_row` with `(rows_done, total_rows)` after each row is drawn - rows are drawn in parallel,
+    /// so `on_row` may be called from any thread, in any order
+    pub fn draw_with_progress<F, P>(&mut self, f: F, on_row: P)
+    where
+        F: Fn(u16, u16) -> Colour,
+        F: Sync + Send,
+        P: Fn(u16, u16) + Sync,
+    {
+        use rayon::prelude::*;
+
+        let total_rows = self.height() as u16;
+        let rows_done = AtomicU16::new(0);
+
+        self.0.par_iter_mut().enumerate().for_each(|(y, row)| {
+            for (x, pixel) in row.iter_mut().enumerate() {
+                *pixel = f(x as _, y as _)
+            }
+
+            let rows_done = rows_done.fetch_add(1, Ordering::Relaxed) + 1;
+            on_row(rows_done, total_rows);
+        })
+    }
+
+    /// As `draw`, but stops drawing as soon as `cancel` is set, leaving any remaining pixels unchanged.
+    /// Returns `true` if every pixel was drawn, or `false` if the drawing was cancelled.
+    ///
+    /// Note: `cancel` is checked before each pixel, so each thread stops after at most one more pixel
+    pub fn draw_cancellable<F>(&mut self, f: F, cancel: &AtomicBool) -> bool
+    where
+        F: Fn(u16, u16) -> Colour,
+        F: Sync + Send,
+    {
+        use rayon::prelude::*;
+
+        self.0.par_iter_mut().enumerate().all(|(y, row)| {
+            for (x, pixel) in row.iter_mut().enumerate() {
+                if cancel.load(Ordering::Relaxed) {
+                    return false;
                 }
-            })
+
+                *pixel = f(x as _, y as _)
+            }
+
+            true
+        })
     }
 }
@@ -1,5 +1,11 @@
 mod render;
-pub use render::{render, Samples};
+pub use render::{
+    render, render_cancellable, render_progressive, render_region, render_with_options,
+    render_with_progress, render_with_stats, Rect, RenderOptions, RenderStats, Samples,
+};
+
+mod bounds;
+pub use bounds::render_bounds;
 
 mod camera;
 pub use camera::Camera;
@@ -7,6 +13,12 @@ pub use camera::Camera;
 mod canvas;
 pub use canvas::Canvas;
 
+mod render_info;
+pub use render_info::RenderInfo;
+
+mod tone_map;
+pub use tone_map::{tone_map, ToneMap};
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -14,4 +26,5 @@ mod tests {
     mod camera_tests;
     mod canvas_tests;
     mod render_tests;
+    mod tone_map_tests;
 }
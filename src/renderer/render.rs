@@ -1,45 +1,291 @@
 use super::*;
-use crate::core::Colour;
-use crate::scene::World;
+use crate::core::{seed_from, Colour};
+use crate::scene::{Counters, World};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 use smallvec::SmallVec;
 use std::fmt;
 use std::fmt::{Display, Formatter};
-use std::num::NonZeroU8;
+use std::num::{NonZeroU16, NonZeroU32, NonZeroU8, NonZeroUsize};
 use std::slice::Iter;
+use std::sync::atomic::AtomicBool;
 
 /// # Parameters
 /// `show_progress`: set to `true` when using e.g. `cargo run` for real-time progress updates;
 ///                  set to `false` when running benchmarks, otherwise it'll cripple performance due to stdout locking
 pub fn render(world: &World, camera: &Camera, samples: &Samples, show_progress: bool) -> Canvas {
+    render_with_options(
+        world,
+        camera,
+        samples,
+        show_progress,
+        &RenderOptions::default(),
+    )
+}
+
+/// as `render`, but with additional control over how the render is run - see `RenderOptions`
+pub fn render_with_options(
+    world: &World,
+    camera: &Camera,
+    samples: &Samples,
+    show_progress: bool,
+    options: &RenderOptions,
+) -> Canvas {
+    if let Some(threads) = options.threads {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(threads.get())
+            .build()
+            .expect("failed to create render thread pool");
+
+        return pool.install(|| render_in_current_pool(world, camera, samples, show_progress));
+    }
+
+    render_in_current_pool(world, camera, samples, show_progress)
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct RenderOptions {
+    /// the maximum number of threads used to render the image - uses every core when `None`
+    pub threads: Option<NonZeroUsize>,
+}
+
+impl RenderOptions {
+    pub fn with_threads(mut self, threads: NonZeroUsize) -> Self {
+        self.threads = Some(threads);
+        self
+    }
+}
+
+/// As `render`, but calls `on_row` with `(rows_done, total_rows)` each time a row of the image is completed,
+/// e.g. to drive a custom progress bar.
+///
+/// Note: rows are rendered in parallel, so `on_row` may be called from any thread, and the rows are not necessarily
+/// completed in order (although `rows_done` always counts up from 1 to `total_rows`, the calls may not be observed in that order)
+pub fn render_with_progress<F>(
+    world: &World,
+    camera: &Camera,
+    samples: &Samples,
+    on_row: F,
+) -> Canvas
+where
+    F: Fn(u16, u16) + Sync,
+{
     let mut canvas =
         Canvas::new(camera.width(), camera.height()).expect("Camera dimensions are too large");
 
-    canvas.draw(show_progress, |x, y| {
-        let mut corners = samples.corner_offsets();
-        let (x_offset, y_offset) = corners.next().unwrap();
-        let top_left = world.colour_at(camera.ray_at(x, y, *x_offset, *y_offset));
+    canvas.draw_with_progress(
+        |x, y| render_pixel(world, camera, samples, x, y, None),
+        on_row,
+    );
 
-        let average_samples = |acc: Colour, (x_offset, y_offset): &(f64, f64)| {
-            let sample = world.colour_at(camera.ray_at(x, y, *x_offset, *y_offset));
-            acc.average(sample)
-        };
+    canvas
+}
+
+/// As `render`, but stops rendering as soon as `cancel` is set (e.g. from another thread, when the scene changes mid-render),
+/// returning the partially rendered image, where any pixels which weren't rendered are black.
+///
+/// The returned `bool` is `true` if the render was completed, or `false` if it was cancelled
+pub fn render_cancellable(
+    world: &World,
+    camera: &Camera,
+    samples: &Samples,
+    cancel: &AtomicBool,
+) -> (Canvas, bool) {
+    let mut canvas =
+        Canvas::new(camera.width(), camera.height()).expect("Camera dimensions are too large");
 
-        let corner_avg = corners.fold(top_left, average_samples);
+    let completed = canvas.draw_cancellable(
+        |x, y| render_pixel(world, camera, samples, x, y, None),
+        cancel,
+    );
 
-        if samples.inner_samples() == 0 || corner_avg.is_similar_to(&top_left) {
-            return top_left;
+    (canvas, completed)
+}
+
+/// Renders the image in passes, taking one more sample of every pixel in each pass, and averaging all of the passes so far,
+/// such that the image starts noisy (or jagged) and is refined over time - e.g. for a live preview.
+///
+/// `on_pass` is called with the image so far and the number of passes completed, after each pass. The samples are taken from
+/// the smallest `Samples::grid` with at least `max_samples` samples, corners first, so `max_samples` should be a square number
+/// to sample every part of each pixel evenly. Unlike `render`, every sample is always taken, and weighted equally
+pub fn render_progressive(
+    world: &World,
+    camera: &Camera,
+    max_samples: NonZeroU32,
+    mut on_pass: impl FnMut(&Canvas, u32),
+) -> Canvas {
+    let grid_size = (max_samples.get() as f64).sqrt().ceil().min(u8::MAX as f64) as u8;
+    let samples = Samples::grid(NonZeroU8::new(grid_size).unwrap());
+    let offsets = samples
+        .corner_offsets()
+        .chain(samples.inner_offsets())
+        .take(max_samples.get() as usize);
+
+    let mut image =
+        Canvas::new(camera.width(), camera.height()).expect("Camera dimensions are too large");
+    let mut pass =
+        Canvas::new(camera.width(), camera.height()).expect("Camera dimensions are too large");
+
+    for (index, &(x_offset, y_offset)) in offsets.enumerate() {
+        let time = samples.shutter_time(x_offset, y_offset);
+        pass.draw(false, |x, y| {
+            world.colour_at(camera.ray_at(x, y, x_offset, y_offset).with_time(time))
+        });
+
+        // a running mean, so each pass is weighted equally
+        let passes = index as u32 + 1;
+        image = image.blend(&pass, 1.0 / passes as f64);
+        on_pass(&image, passes);
+    }
+
+    image
+}
+
+/// A rectangular region of the image, in pixels, where `(x, y)` is the top left corner of the region
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Rect {
+    pub x: u16,
+    pub y: u16,
+    pub width: NonZeroU16,
+    pub height: NonZeroU16,
+}
+
+impl Rect {
+    pub fn new(x: u16, y: u16, width: NonZeroU16, height: NonZeroU16) -> Self {
+        Rect {
+            x,
+            y,
+            width,
+            height,
         }
+    }
+}
+
+/// As `render`, but only renders the given `region` of the full image seen by the `camera`, returning a canvas the size of the `region`,
+/// such that rendering several regions (e.g. on different machines) and combining them produces exactly the same image as `render`
+///
+/// # Panics
+/// Panics if the `region` extends beyond the width or height of the `camera`
+pub fn render_region(world: &World, camera: &Camera, samples: &Samples, region: Rect) -> Canvas {
+    assert!(
+        region.x as u32 + region.width.get() as u32 <= camera.width().get() as u32
+            && region.y as u32 + region.height.get() as u32 <= camera.height().get() as u32,
+        "{:?} does not fit within the {}x{} image",
+        region,
+        camera.width(),
+        camera.height()
+    );
+
+    let mut canvas =
+        Canvas::new(region.width, region.height).expect("Region dimensions are too large");
+
+    canvas.draw(false, |x, y| {
+        render_pixel(world, camera, samples, region.x + x, region.y + y, None)
+    });
+
+    canvas
+}
+
+/// Counts of the work done while rendering an image, to help tune performance - see `render_with_stats`
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct RenderStats {
+    /// rays cast from the camera - one for each pixel, plus any additional anti-aliasing samples
+    pub primary_rays: u64,
+    pub reflection_rays: u64,
+    pub refraction_rays: u64,
+    /// rays cast from a surface towards each light (or each point on an area light) to check for shadows, including any rays
+    /// bent by refractive materials on the way to the light, when caustics are enabled
+    pub shadow_rays: u64,
+    /// rays cast over the hemisphere around each surface to measure ambient occlusion
+    pub ambient_occlusion_rays: u64,
+    /// the number of times a ray was tested against the bounding box of an object or group
+    pub bbox_tests: u64,
+}
+
+/// As `render`, but also counts the rays cast, and bounding boxes tested, while rendering.
+///
+/// Note: counting slows rendering down, so this is only intended for performance tuning. The counts only include this render,
+/// even if the same `world` is rendered elsewhere at the same time
+pub fn render_with_stats(
+    world: &World,
+    camera: &Camera,
+    samples: &Samples,
+) -> (Canvas, RenderStats) {
+    let counters = Counters::default();
 
-        samples.inner_offsets().fold(corner_avg, average_samples)
+    let mut canvas =
+        Canvas::new(camera.width(), camera.height()).expect("Camera dimensions are too large");
+    canvas.draw(false, |x, y| {
+        render_pixel(world, camera, samples, x, y, Some(&counters))
+    });
+
+    let stats = RenderStats {
+        primary_rays: counters.primary_rays.into_inner(),
+        reflection_rays: counters.reflection_rays.into_inner(),
+        refraction_rays: counters.refraction_rays.into_inner(),
+        shadow_rays: counters.shadow_rays.into_inner(),
+        ambient_occlusion_rays: counters.ambient_occlusion_rays.into_inner(),
+        bbox_tests: counters.bbox_tests.into_inner(),
+    };
+
+    (canvas, stats)
+}
+
+fn render_in_current_pool(
+    world: &World,
+    camera: &Camera,
+    samples: &Samples,
+    show_progress: bool,
+) -> Canvas {
+    let mut canvas =
+        Canvas::new(camera.width(), camera.height()).expect("Camera dimensions are too large");
+
+    canvas.draw(show_progress, |x, y| {
+        render_pixel(world, camera, samples, x, y, None)
     });
 
     canvas
 }
 
+fn render_pixel(
+    world: &World,
+    camera: &Camera,
+    samples: &Samples,
+    x: u16,
+    y: u16,
+    counters: Option<&Counters>,
+) -> Colour {
+    let mut corners = samples.corner_offsets();
+    let (x_offset, y_offset) = corners.next().unwrap();
+    let sample_at = |x_offset: f64, y_offset: f64| {
+        let (x_offset, y_offset) = samples.jittered(x, y, (x_offset, y_offset));
+        let ray = camera
+            .ray_at(x, y, x_offset, y_offset)
+            .with_time(samples.shutter_time(x_offset, y_offset));
+
+        world.colour_at_counted(ray, counters)
+    };
+    let top_left = sample_at(*x_offset, *y_offset);
+
+    let average_samples = |acc: Colour, (x_offset, y_offset): &(f64, f64)| {
+        acc.average(sample_at(*x_offset, *y_offset))
+    };
+
+    let corner_avg = corners.fold(top_left, average_samples);
+
+    if samples.inner_samples() == 0 || corner_avg.is_similar_to(&top_left) {
+        return top_left;
+    }
+
+    samples.inner_offsets().fold(corner_avg, average_samples)
+}
+
 #[derive(Debug, PartialEq)]
 pub struct Samples {
     inner: Vec<(f64, f64)>,
     corners: SmallVec<[(f64, f64); 4]>,
+    /// if set, each sample is moved to a random point within its cell of the grid - see `Samples::stratified`
+    jitter_seed: Option<u64>,
 }
 
 impl Samples {
@@ -50,6 +296,7 @@ impl Samples {
         Self {
             inner: vec![],
             corners,
+            jitter_seed: None,
         }
     }
 
@@ -86,6 +333,19 @@ impl Samples {
         Self {
             inner: offsets,
             corners,
+            jitter_seed: None,
+        }
+    }
+
+    /// As `grid`, but each sample is placed at a random point within its cell of the grid, rather than at the centre of the cell,
+    /// and the points are different for every pixel. This replaces the regular patterns (aliasing) a grid produces on fine
+    /// details with less noticeable noise.
+    ///
+    /// The points only depend on the `seed` and the pixel, so rendering the same scene always produces the same image
+    pub fn stratified(grid_size: NonZeroU8, seed: u64) -> Self {
+        Self {
+            jitter_seed: Some(seed),
+            ..Self::grid(grid_size)
         }
     }
 
@@ -97,13 +357,46 @@ impl Samples {
         self.corners.iter()
     }
 
-    fn samples(&self) -> usize {
+    /// moves the `offset` of a sample of the pixel at `(x, y)` to a random point within the same cell of the grid, if the
+    /// samples are stratified
+    pub(super) fn jittered(&self, x: u16, y: u16, (x_offset, y_offset): (f64, f64)) -> (f64, f64) {
+        let seed = match self.jitter_seed {
+            Some(seed) => seed,
+            None => return (x_offset, y_offset),
+        };
+
+        let mut rng = StdRng::seed_from_u64(seed_from(&[
+            seed,
+            x as u64,
+            y as u64,
+            x_offset.to_bits(),
+            y_offset.to_bits(),
+        ]));
+
+        let cell_size = 1.0 / (self.samples() as f64).sqrt().round();
+        (
+            x_offset + (rng.gen::<f64>() - 0.5) * cell_size,
+            y_offset + (rng.gen::<f64>() - 0.5) * cell_size,
+        )
+    }
+
+    pub(super) fn samples(&self) -> usize {
         self.inner.len() + self.corners.len()
     }
 
     fn inner_samples(&self) -> usize {
         self.inner.len()
     }
+
+    /// spreads the samples evenly over the time the shutter is open (for motion blur), such that each sample in the grid
+    /// is taken at a different time, in the same order as the grid is read (i.e. left to right, then top to bottom)
+    pub(super) fn shutter_time(&self, x_offset: f64, y_offset: f64) -> f64 {
+        let grid_size = (self.samples() as f64).sqrt().round();
+        let column = (x_offset * grid_size).floor();
+        let row = (y_offset * grid_size).floor();
+
+        (row * grid_size + column + 0.5) / (grid_size * grid_size)
+    }
 }
 
 impl Display for Samples {
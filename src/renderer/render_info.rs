@@ -0,0 +1,87 @@
+use super::*;
+use anyhow::*;
+use std::time::Duration;
+
+/// Details of how an image was rendered, which can be embedded in the written image (see `ppm_writer` and `image_writer`)
+/// to make it easier to reproduce the render later
+#[derive(Debug, Clone, PartialEq)]
+pub struct RenderInfo {
+    pub width: usize,
+    pub height: usize,
+    /// the maximum number of rays cast per pixel
+    pub samples: usize,
+    /// the seed used for any randomness in the scene (e.g. area lights), if any
+    pub seed: Option<u64>,
+    pub render_time: Duration,
+}
+
+impl RenderInfo {
+    pub fn new(camera: &Camera, samples: &Samples, render_time: Duration) -> Self {
+        RenderInfo {
+            width: camera.width().get() as _,
+            height: camera.height().get() as _,
+            samples: samples.samples(),
+            seed: None,
+            render_time,
+        }
+    }
+
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// key-value pairs, in the order they should be written
+    pub(crate) fn to_entries(&self) -> Vec<(&'static str, String)> {
+        let mut entries = vec![
+            ("Resolution", format!("{}x{}", self.width, self.height)),
+            ("Samples", self.samples.to_string()),
+        ];
+        if let Some(seed) = self.seed {
+            entries.push(("Seed", seed.to_string()));
+        }
+        entries.push(("Render time", format!("{}ms", self.render_time.as_millis())));
+
+        entries
+    }
+
+    /// the inverse of `to_entries` - unrecognised keys are ignored
+    pub(crate) fn from_entries<'a>(
+        entries: impl IntoIterator<Item = (&'a str, &'a str)>,
+    ) -> anyhow::Result<Self> {
+        let mut resolution = None;
+        let mut samples = None;
+        let mut seed = None;
+        let mut render_time = None;
+
+        for (key, value) in entries {
+            match key {
+                "Resolution" => {
+                    let (width, height) = value
+                        .split_once('x')
+                        .ok_or_else(|| anyhow!("invalid resolution {:?}", value))?;
+                    resolution = Some((width.parse()?, height.parse()?));
+                }
+                "Samples" => samples = Some(value.parse()?),
+                "Seed" => seed = Some(value.parse()?),
+                "Render time" => {
+                    let millis = value
+                        .strip_suffix("ms")
+                        .ok_or_else(|| anyhow!("invalid render time {:?}", value))?;
+                    render_time = Some(Duration::from_millis(millis.parse()?));
+                }
+                _ => (),
+            }
+        }
+
+        let (width, height) = resolution.ok_or_else(|| anyhow!("missing `Resolution`"))?;
+
+        Ok(RenderInfo {
+            width,
+            height,
+            samples: samples.ok_or_else(|| anyhow!("missing `Samples`"))?,
+            seed,
+            render_time: render_time.ok_or_else(|| anyhow!("missing `Render time`"))?,
+        })
+    }
+}
@@ -79,3 +79,254 @@ fn a_ray_through_a_transformed_camera_should_have_the_inverse_transform_applied(
         Vector3D::new(SQRT_2 / 2.0, 0.0, -SQRT_2 / 2.0)
     )
 }
+
+#[test]
+fn projecting_a_point_should_give_the_pixel_whose_ray_passes_through_it() {
+    let transform = Transform::identity()
+        .translate_y(-2.0)
+        .translate_z(5.0)
+        .rotate_y(PI / 4.0);
+    let camera = Camera::new(
+        NonZeroU16::new(201).unwrap(),
+        NonZeroU16::new(101).unwrap(),
+        PI / 2.0,
+        transform,
+    );
+
+    let ray = camera.ray_at(30, 70, 0.5, 0.5);
+    let (x, y) = camera.project(ray.position(10.0)).unwrap();
+
+    assert_abs_diff_eq!(x, 30.5, epsilon = 1e-9);
+    assert_abs_diff_eq!(y, 70.5, epsilon = 1e-9);
+}
+
+#[test]
+fn a_single_sample_should_produce_one_ray_through_the_centre_of_the_pixel() {
+    let camera = Camera::new(
+        NonZeroU16::new(201).unwrap(),
+        NonZeroU16::new(101).unwrap(),
+        PI / 2.0,
+        Transform::identity(),
+    );
+
+    let rays = camera.rays(10, 20, &Samples::single()).collect::<Vec<_>>();
+    assert_eq!(rays, vec![camera.ray_at(10, 20, 0.5, 0.5).with_time(0.5)]);
+}
+
+#[test]
+fn a_sample_grid_should_produce_a_ray_for_every_sample_at_a_different_time() {
+    let camera = Camera::new(
+        NonZeroU16::new(201).unwrap(),
+        NonZeroU16::new(101).unwrap(),
+        PI / 2.0,
+        Transform::identity(),
+    );
+    let samples = Samples::grid(nonzero_ext::nonzero!(3u8));
+
+    let rays = camera.rays(10, 20, &samples).collect::<Vec<_>>();
+    assert_eq!(rays.len(), 9);
+    assert_eq!(
+        rays[0],
+        camera
+            .ray_at(10, 20, 1.0 / 6.0, 1.0 / 6.0)
+            .with_time(1.0 / 18.0)
+    );
+
+    let mut times = rays.iter().map(|ray| ray.time).collect::<Vec<_>>();
+    times.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    times.dedup();
+    assert_eq!(times.len(), 9);
+}
+
+#[test]
+fn a_point_behind_the_camera_should_not_be_projected() {
+    let camera = Camera::new(
+        NonZeroU16::new(100).unwrap(),
+        NonZeroU16::new(100).unwrap(),
+        PI / 2.0,
+        Transform::identity(),
+    );
+
+    assert_eq!(camera.project(Point3D::new(0.0, 0.0, 1.0)), None);
+}
+
+mod orthographic {
+    use super::*;
+    use crate::core::Normal3D;
+
+    #[test]
+    fn an_orthographic_camera_should_calculate_pixel_size_from_the_view_width() {
+        let camera = Camera::orthographic(
+            NonZeroU16::new(200).unwrap(),
+            NonZeroU16::new(125).unwrap(),
+            4.0,
+            Transform::identity(),
+        );
+        assert_abs_diff_eq!(camera.pixel_size, 0.02);
+    }
+
+    #[test]
+    fn a_ray_through_the_centre_of_an_orthographic_camera_should_travel_along_negative_z_from_the_world_origin(
+    ) {
+        let camera = Camera::orthographic(
+            NonZeroU16::new(201).unwrap(),
+            NonZeroU16::new(101).unwrap(),
+            4.0,
+            Transform::identity(),
+        );
+
+        let ray = camera.ray_at(100, 50, 0.5, 0.5);
+        assert_abs_diff_eq!(ray.origin, Point3D::new(0.0, 0.0, 0.0));
+        assert_abs_diff_eq!(ray.direction, Vector3D::new(0.0, 0.0, -1.0))
+    }
+
+    #[test]
+    fn a_ray_through_a_corner_of_an_orthographic_camera_should_start_at_the_corner_of_the_view_and_travel_along_negative_z(
+    ) {
+        let camera = Camera::orthographic(
+            NonZeroU16::new(200).unwrap(),
+            NonZeroU16::new(100).unwrap(),
+            4.0,
+            Transform::identity(),
+        );
+
+        let ray = camera.ray_at(0, 0, 0.5, 0.5);
+        assert_abs_diff_eq!(ray.origin, Point3D::new(1.99, 0.99, 0.0));
+        assert_abs_diff_eq!(ray.direction, Vector3D::new(0.0, 0.0, -1.0))
+    }
+
+    #[test]
+    fn every_ray_from_a_transformed_orthographic_camera_should_travel_in_the_same_direction() {
+        let camera = Camera::orthographic(
+            NonZeroU16::new(100).unwrap(),
+            NonZeroU16::new(100).unwrap(),
+            10.0,
+            Transform::view_transform(
+                Point3D::new(5.0, 5.0, 5.0),
+                Point3D::ORIGIN,
+                Normal3D::POSITIVE_Y,
+            ),
+        );
+
+        let expected = Vector3D::new(-1.0, -1.0, -1.0) / 3.0_f64.sqrt();
+        vec![(0, 0), (99, 0), (0, 99), (99, 99), (50, 50)]
+            .into_iter()
+            .for_each(|(x, y)| {
+                let ray = camera.ray_at(x, y, 0.5, 0.5);
+                assert_abs_diff_eq!(ray.direction, expected);
+            });
+
+        let centre = camera.ray_at(50, 50, 0.0, 0.0);
+        assert_abs_diff_eq!(centre.origin, Point3D::new(5.0, 5.0, 5.0));
+    }
+
+    #[test]
+    fn projecting_a_point_with_an_orthographic_camera_should_ignore_its_distance() {
+        let camera = Camera::orthographic(
+            NonZeroU16::new(200).unwrap(),
+            NonZeroU16::new(100).unwrap(),
+            4.0,
+            Transform::identity(),
+        );
+
+        let near = camera.project(Point3D::new(1.0, 0.5, -1.0)).unwrap();
+        let far = camera.project(Point3D::new(1.0, 0.5, -100.0)).unwrap();
+
+        assert_eq!(near, far);
+        assert_abs_diff_eq!(near.0, 50.0);
+        assert_abs_diff_eq!(near.1, 25.0);
+    }
+}
+
+mod frustum {
+    use super::*;
+    use crate::core::{Normal3D, VectorMaths};
+
+    fn is_inside(planes: &[(Normal3D, f64); 6], point: Point3D) -> bool {
+        planes
+            .iter()
+            .all(|(normal, distance)| normal.dot(point - Point3D::ORIGIN) + distance >= 0.0)
+    }
+
+    #[test]
+    fn a_point_in_front_of_the_camera_should_be_inside_every_plane_of_the_frustum() {
+        let camera = Camera::new(
+            NonZeroU16::new(100).unwrap(),
+            NonZeroU16::new(50).unwrap(),
+            PI / 2.0,
+            Transform::identity(),
+        );
+
+        assert!(is_inside(
+            &camera.frustum_planes(),
+            Point3D::new(0.0, 0.0, -5.0)
+        ));
+    }
+
+    #[test]
+    fn a_point_behind_the_camera_should_be_outside_of_the_near_plane() {
+        let camera = Camera::new(
+            NonZeroU16::new(100).unwrap(),
+            NonZeroU16::new(100).unwrap(),
+            PI / 2.0,
+            Transform::identity(),
+        );
+
+        let (normal, distance) = camera.frustum_planes()[4];
+        assert_abs_diff_eq!(normal, Normal3D::NEGATIVE_Z);
+        assert_abs_diff_eq!(distance, 0.0);
+        assert!(!is_inside(
+            &camera.frustum_planes(),
+            Point3D::new(0.0, 0.0, 1.0)
+        ));
+    }
+
+    #[test]
+    fn the_side_planes_of_the_frustum_should_pass_through_the_edges_of_the_field_of_view() {
+        let camera = Camera::new(
+            NonZeroU16::new(100).unwrap(),
+            NonZeroU16::new(100).unwrap(),
+            PI / 2.0,
+            Transform::identity(),
+        );
+        let planes = camera.frustum_planes();
+
+        assert!(is_inside(&planes, Point3D::new(4.9, 4.9, -5.0)));
+        assert!(!is_inside(&planes, Point3D::new(5.1, 0.0, -5.0)));
+        assert!(!is_inside(&planes, Point3D::new(-5.1, 0.0, -5.0)));
+        assert!(!is_inside(&planes, Point3D::new(0.0, 5.1, -5.0)));
+        assert!(!is_inside(&planes, Point3D::new(0.0, -5.1, -5.0)));
+    }
+
+    #[test]
+    fn the_frustum_planes_should_follow_the_camera_transform() {
+        let camera = Camera::new(
+            NonZeroU16::new(100).unwrap(),
+            NonZeroU16::new(100).unwrap(),
+            PI / 2.0,
+            Transform::view_transform(
+                Point3D::new(0.0, 0.0, 10.0),
+                Point3D::new(0.0, 0.0, 20.0),
+                Normal3D::POSITIVE_Y,
+            ),
+        );
+        let planes = camera.frustum_planes();
+
+        assert!(is_inside(&planes, Point3D::new(0.0, 0.0, 15.0)));
+        assert!(!is_inside(&planes, Point3D::new(0.0, 0.0, 5.0)));
+    }
+
+    #[test]
+    fn the_side_planes_of_an_orthographic_frustum_should_not_spread_out() {
+        let camera = Camera::orthographic(
+            NonZeroU16::new(100).unwrap(),
+            NonZeroU16::new(100).unwrap(),
+            4.0,
+            Transform::identity(),
+        );
+        let planes = camera.frustum_planes();
+
+        assert!(is_inside(&planes, Point3D::new(1.9, 1.9, -100.0)));
+        assert!(!is_inside(&planes, Point3D::new(2.1, 0.0, -100.0)));
+    }
+}
@@ -68,3 +68,101 @@ fn should_be_able_to_set_pixel_colour() {
     canvas.set(2, 3, Colour::RED);
     assert_eq!(canvas.get(2, 3), Colour::RED);
 }
+
+#[test]
+fn drawing_should_stop_at_the_next_pixel_once_cancelled() {
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    let mut canvas =
+        Canvas::new(NonZeroU16::new(10).unwrap(), NonZeroU16::new(4).unwrap()).unwrap();
+    let cancel = AtomicBool::new(false);
+
+    let completed = canvas.draw_cancellable(
+        |x, y| {
+            if (x, y) == (5, 2) {
+                cancel.store(true, Ordering::Relaxed);
+            }
+
+            Colour::WHITE
+        },
+        &cancel,
+    );
+
+    assert!(!completed);
+    assert_eq!(canvas.get(5, 2), Colour::WHITE);
+    (6..10).for_each(|x| assert_eq!(canvas.get(x, 2), Colour::BLACK));
+}
+
+#[test]
+fn drawing_without_cancelling_should_draw_every_pixel() {
+    use std::sync::atomic::AtomicBool;
+
+    let mut canvas =
+        Canvas::new(NonZeroU16::new(10).unwrap(), NonZeroU16::new(4).unwrap()).unwrap();
+
+    assert!(canvas.draw_cancellable(|_, _| Colour::WHITE, &AtomicBool::new(false)));
+    for y in 0..4 {
+        for x in 0..10 {
+            assert_eq!(canvas.get(x, y), Colour::WHITE);
+        }
+    }
+}
+
+mod combining {
+    use super::*;
+
+    fn filled(width: u16, height: u16, colour: Colour) -> Canvas {
+        let mut canvas = Canvas::new(
+            NonZeroU16::new(width).unwrap(),
+            NonZeroU16::new(height).unwrap(),
+        )
+        .unwrap();
+        canvas.draw(false, |_, _| colour);
+
+        canvas
+    }
+
+    #[test]
+    fn adding_two_canvases_should_sum_the_colour_of_each_pixel() {
+        let mut canvas = filled(3, 2, Colour::new(0.25, 0.5, 0.0));
+        canvas.set(1, 1, Colour::WHITE);
+        let mut other = filled(3, 2, Colour::new(0.25, 0.0, 1.0));
+        other.set(2, 0, Colour::RED);
+
+        canvas.add(&other);
+
+        assert_eq!(canvas.get(0, 0), Colour::new(0.5, 0.5, 1.0));
+        assert_eq!(canvas.get(1, 1), Colour::new(1.25, 1.0, 2.0));
+        assert_eq!(canvas.get(2, 0), Colour::new(1.25, 0.5, 0.0));
+    }
+
+    #[test]
+    fn scaling_a_canvas_by_a_half_should_halve_every_pixel() {
+        let mut canvas = filled(3, 2, Colour::new(0.5, 1.0, 0.25));
+        canvas.set(2, 1, Colour::WHITE);
+
+        canvas.scale(0.5);
+
+        assert_eq!(canvas.get(0, 0), Colour::new(0.25, 0.5, 0.125));
+        assert_eq!(canvas.get(2, 1), Colour::greyscale(0.5));
+    }
+
+    #[test]
+    fn blending_two_canvases_should_interpolate_each_pixel() {
+        let canvas = filled(2, 2, Colour::BLACK);
+        let other = filled(2, 2, Colour::WHITE);
+
+        let blended = canvas.blend(&other, 0.25);
+
+        assert_eq!(blended.get(1, 1), Colour::greyscale(0.25));
+        assert_eq!(canvas.get(1, 1), Colour::BLACK);
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot combine a 3x2 canvas with a 2x3 canvas")]
+    fn adding_canvases_of_different_sizes_should_panic() {
+        let mut canvas = filled(3, 2, Colour::BLACK);
+
+        canvas.add(&filled(2, 3, Colour::BLACK));
+    }
+}
@@ -3,7 +3,7 @@ use super::*;
 mod rendering {
     use super::*;
     use crate::core::{Colour, Normal3D, Point3D, Transform};
-    use crate::scene::World;
+    use crate::scene::{Object, World};
     use approx::*;
     use std::f64::consts::PI;
     use std::num::NonZeroU16;
@@ -32,6 +32,136 @@ mod rendering {
 
         assert_abs_diff_eq!(expected, actual);
     }
+
+    #[test]
+    fn the_parallel_edges_of_a_cuboid_should_stay_parallel_with_an_orthographic_camera_but_not_a_perspective_camera(
+    ) {
+        // a long cuboid running away from the camera, seen from above, such that the left and right edges
+        // of the top face are parallel, but get further from the camera further up the image
+        let mut world = World::empty();
+        world.settings.ambient_light = Colour::WHITE;
+        world.add(Object::cube().transformed(Transform::identity().scale_z(5.0)));
+
+        let view_transform = Transform::view_transform(
+            Point3D::new(0.0, 6.0, -12.0),
+            Point3D::ORIGIN,
+            Normal3D::POSITIVE_Y,
+        );
+        let size = NonZeroU16::new(100).unwrap();
+
+        // the width, in pixels, of the cuboid on each row of the image it appears in
+        let widths = |camera: &Camera| {
+            let canvas = render(&world, camera, &Samples::single(), false);
+
+            (0..100)
+                .map(|y| {
+                    (0..100)
+                        .filter(|x| canvas.get(*x, y) != Colour::BLACK)
+                        .count()
+                })
+                .filter(|width| *width > 0)
+                .collect::<Vec<_>>()
+        };
+
+        let orthographic = widths(&Camera::orthographic(size, size, 16.0, view_transform));
+        let orthographic_range =
+            orthographic.iter().max().unwrap() - orthographic.iter().min().unwrap();
+        assert!(orthographic.len() > 10);
+        assert!(orthographic_range <= 1, "{:?}", orthographic);
+
+        let perspective = widths(&Camera::new(size, size, PI / 3.0, view_transform));
+        let perspective_range =
+            perspective.iter().max().unwrap() - perspective.iter().min().unwrap();
+        assert!(perspective.len() > 10);
+        assert!(perspective_range > 5, "{:?}", perspective);
+    }
+}
+
+mod options {
+    use super::*;
+    use crate::core::{Normal3D, Point3D, Transform};
+    use crate::scene::World;
+    use std::f64::consts::PI;
+    use std::num::{NonZeroU16, NonZeroUsize};
+
+    #[test]
+    fn rendering_on_a_single_thread_should_produce_the_same_image_as_rendering_on_every_core() {
+        let camera = Camera::new(
+            NonZeroU16::new(40).unwrap(),
+            NonZeroU16::new(30).unwrap(),
+            PI / 3.0,
+            Transform::view_transform(
+                Point3D::new(0.0, 1.5, -5.0),
+                Point3D::ORIGIN,
+                Normal3D::POSITIVE_Y,
+            ),
+        );
+        let world = World::default();
+        let samples = Samples::grid(nonzero_ext::nonzero!(4u8));
+
+        let single_threaded = render_with_options(
+            &world,
+            &camera,
+            &samples,
+            false,
+            &RenderOptions::default().with_threads(NonZeroUsize::new(1).unwrap()),
+        );
+        let multi_threaded = render(&world, &camera, &samples, false);
+
+        for y in 0..30 {
+            for x in 0..40 {
+                assert_eq!(
+                    single_threaded.get(x, y),
+                    multi_threaded.get(x, y),
+                    "{:?}",
+                    (x, y)
+                );
+            }
+        }
+    }
+}
+
+mod progress {
+    use super::*;
+    use crate::core::{Normal3D, Point3D, Transform};
+    use crate::scene::World;
+    use std::f64::consts::PI;
+    use std::num::NonZeroU16;
+    use std::sync::Mutex;
+
+    #[test]
+    fn rendering_with_progress_should_report_each_row_once_and_produce_the_same_image() {
+        let camera = Camera::new(
+            NonZeroU16::new(20).unwrap(),
+            NonZeroU16::new(15).unwrap(),
+            PI / 3.0,
+            Transform::view_transform(
+                Point3D::new(0.0, 1.5, -5.0),
+                Point3D::ORIGIN,
+                Normal3D::POSITIVE_Y,
+            ),
+        );
+        let world = World::default();
+
+        let progress = Mutex::new(Vec::new());
+        let canvas = render_with_progress(&world, &camera, &Samples::single(), |done, total| {
+            progress.lock().unwrap().push((done, total))
+        });
+
+        let mut progress = progress.into_inner().unwrap();
+        progress.sort();
+        assert_eq!(
+            progress,
+            (1..=15).map(|done| (done, 15)).collect::<Vec<_>>()
+        );
+
+        let expected = render(&world, &camera, &Samples::single(), false);
+        for y in 0..15 {
+            for x in 0..20 {
+                assert_eq!(canvas.get(x, y), expected.get(x, y), "{:?}", (x, y));
+            }
+        }
+    }
 }
 
 mod samples {
@@ -110,4 +240,576 @@ mod samples {
 
         assert_eq!(corners.next(), None);
     }
+
+    #[test]
+    fn a_single_sample_should_be_taken_half_way_through_the_shutter_time() {
+        let samples = Samples::single();
+
+        assert_eq!(samples.shutter_time(0.5, 0.5), 0.5);
+    }
+
+    #[test]
+    fn each_sample_in_a_grid_should_be_taken_at_a_different_time() {
+        let samples = Samples::grid(nonzero_ext::nonzero!(2u8));
+        let times = samples
+            .corner_offsets()
+            .map(|(x, y)| samples.shutter_time(*x, *y))
+            .collect::<Vec<_>>();
+
+        assert_eq!(times, vec![0.125, 0.375, 0.625, 0.875]);
+    }
+
+    mod stratified {
+        use super::*;
+        use crate::core::{Colour, Transform};
+        use crate::scene::{Material, Object, World};
+        use std::num::NonZeroU16;
+
+        /// a white square against a black background, with its left edge running down the middle of a column of pixels (e.g.
+        /// the edge between two squares of a checker pattern) - a grid has the same error in every pixel along the edge
+        fn vertical_edge() -> (World, Camera) {
+            let mut world = World::empty();
+            world.add(
+                Object::cube()
+                    .with_material(Material {
+                        emission: Colour::WHITE,
+                        ..Material::matte(Colour::BLACK)
+                    })
+                    .transformed(
+                        Transform::identity()
+                            .scale_x(50.0)
+                            .scale_y(100.0)
+                            .translate_x(50.37)
+                            .translate_z(-5.0),
+                    ),
+            );
+
+            let camera = Camera::orthographic(
+                NonZeroU16::new(8).unwrap(),
+                NonZeroU16::new(64).unwrap(),
+                8.0,
+                Transform::identity(),
+            );
+
+            (world, camera)
+        }
+
+        /// the mean brightness of every ray cast through each pixel
+        fn coverage(world: &World, camera: &Camera, samples: &Samples) -> Vec<f64> {
+            (0..camera.height().get())
+                .flat_map(|y| (0..camera.width().get()).map(move |x| (x, y)))
+                .map(|(x, y)| {
+                    let brightness = camera
+                        .rays(x, y, samples)
+                        .map(|ray| world.colour_at(ray).red())
+                        .sum::<f64>();
+
+                    brightness / samples.samples() as f64
+                })
+                .collect()
+        }
+
+        #[test]
+        fn stratified_samples_should_stay_within_their_cell_of_the_grid() {
+            let samples = Samples::stratified(nonzero_ext::nonzero!(4u8), 0);
+
+            samples
+                .corner_offsets()
+                .chain(samples.inner_offsets())
+                .for_each(|&(x, y)| {
+                    let (jittered_x, jittered_y) = samples.jittered(3, 7, (x, y));
+
+                    assert!((jittered_x - x).abs() <= 0.125, "{} -> {}", x, jittered_x);
+                    assert!((jittered_y - y).abs() <= 0.125, "{} -> {}", y, jittered_y);
+                    assert_eq!(
+                        samples.shutter_time(jittered_x, jittered_y),
+                        samples.shutter_time(x, y)
+                    );
+                });
+        }
+
+        #[test]
+        fn stratified_samples_should_be_deterministic_for_each_pixel_and_seed() {
+            let samples = Samples::stratified(nonzero_ext::nonzero!(2u8), 0);
+            let offset = (0.25, 0.25);
+
+            assert_eq!(
+                samples.jittered(1, 2, offset),
+                samples.jittered(1, 2, offset)
+            );
+            assert_ne!(
+                samples.jittered(1, 2, offset),
+                samples.jittered(2, 1, offset)
+            );
+            assert_ne!(
+                samples.jittered(1, 2, offset),
+                Samples::stratified(nonzero_ext::nonzero!(2u8), 1).jittered(1, 2, offset)
+            );
+        }
+
+        #[test]
+        fn a_grid_should_not_be_jittered() {
+            let samples = Samples::grid(nonzero_ext::nonzero!(2u8));
+
+            assert_eq!(samples.jittered(1, 2, (0.25, 0.75)), (0.25, 0.75));
+        }
+
+        #[test]
+        fn stratified_sampling_should_estimate_the_coverage_of_an_edge_more_accurately_than_a_grid_with_as_many_samples(
+        ) {
+            let (world, camera) = vertical_edge();
+            let exact = coverage(&world, &camera, &Samples::grid(nonzero_ext::nonzero!(32u8)));
+
+            let squared_error = |samples: Samples| {
+                coverage(&world, &camera, &samples)
+                    .iter()
+                    .zip(exact.iter())
+                    .map(|(estimate, exact)| (estimate - exact).powi(2))
+                    .sum::<f64>()
+            };
+
+            let grid = squared_error(Samples::grid(nonzero_ext::nonzero!(4u8)));
+            let stratified = squared_error(Samples::stratified(nonzero_ext::nonzero!(4u8), 0));
+            assert!(
+                stratified < grid,
+                "stratified: {}, grid: {}",
+                stratified,
+                grid
+            );
+        }
+    }
+}
+
+mod cancellation {
+    use super::*;
+    use crate::core::{Colour, Normal3D, Point3D, Transform};
+    use crate::scene::World;
+    use std::f64::consts::PI;
+    use std::num::NonZeroU16;
+    use std::sync::atomic::AtomicBool;
+
+    fn camera() -> Camera {
+        Camera::new(
+            NonZeroU16::new(20).unwrap(),
+            NonZeroU16::new(15).unwrap(),
+            PI / 3.0,
+            Transform::view_transform(
+                Point3D::new(0.0, 0.0, -5.0),
+                Point3D::ORIGIN,
+                Normal3D::POSITIVE_Y,
+            ),
+        )
+    }
+
+    #[test]
+    fn a_render_cancelled_before_starting_should_return_an_empty_canvas() {
+        let world = World::default();
+
+        let (canvas, completed) = render_cancellable(
+            &world,
+            &camera(),
+            &Samples::single(),
+            &AtomicBool::new(true),
+        );
+
+        assert!(!completed);
+        for y in 0..15 {
+            for x in 0..20 {
+                assert_eq!(canvas.get(x, y), Colour::BLACK, "{:?}", (x, y));
+            }
+        }
+    }
+
+    #[test]
+    fn a_render_which_is_not_cancelled_should_produce_the_same_image_as_render() {
+        let world = World::default();
+
+        let (canvas, completed) = render_cancellable(
+            &world,
+            &camera(),
+            &Samples::single(),
+            &AtomicBool::new(false),
+        );
+        assert!(completed);
+
+        let expected = render(&world, &camera(), &Samples::single(), false);
+        for y in 0..15 {
+            for x in 0..20 {
+                assert_eq!(canvas.get(x, y), expected.get(x, y), "{:?}", (x, y));
+            }
+        }
+        // make sure the image isn't blank
+        assert_ne!(canvas.get(10, 7), Colour::BLACK);
+    }
+}
+
+mod progressive {
+    use super::*;
+    use crate::core::{Normal3D, Point3D, Transform};
+    use crate::scene::World;
+    use approx::*;
+    use std::f64::consts::PI;
+    use std::num::{NonZeroU16, NonZeroU32};
+
+    /// zoomed in on the sphere in the default world, so the colour only changes slightly within each pixel
+    fn camera() -> Camera {
+        Camera::new(
+            NonZeroU16::new(6).unwrap(),
+            NonZeroU16::new(6).unwrap(),
+            PI / 300.0,
+            Transform::view_transform(
+                Point3D::new(0.0, 0.0, -5.0),
+                Point3D::ORIGIN,
+                Normal3D::POSITIVE_Y,
+            ),
+        )
+    }
+
+    #[test]
+    fn a_progressive_render_should_call_back_after_every_pass() {
+        let mut passes = vec![];
+
+        render_progressive(
+            &World::default(),
+            &camera(),
+            NonZeroU32::new(9).unwrap(),
+            |_, pass| passes.push(pass),
+        );
+
+        assert_eq!(passes, (1..=9).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn a_single_pass_progressive_render_should_match_a_single_sample_render() {
+        let world = World::default();
+        let canvas = render_progressive(&world, &camera(), NonZeroU32::new(1).unwrap(), |_, _| {});
+
+        let expected = render(&world, &camera(), &Samples::single(), false);
+        for y in 0..6 {
+            for x in 0..6 {
+                assert_eq!(canvas.get(x, y), expected.get(x, y), "{:?}", (x, y));
+            }
+        }
+    }
+
+    #[test]
+    fn a_completed_progressive_render_should_match_a_grid_sampled_render() {
+        let world = World::default();
+        let canvas = render_progressive(&world, &camera(), NonZeroU32::new(16).unwrap(), |_, _| {});
+
+        let expected = render(
+            &world,
+            &camera(),
+            &Samples::grid(nonzero_ext::nonzero!(4u8)),
+            false,
+        );
+        // `render` skips the inner samples of pixels whose corners are similar, so the colours are only the same to within
+        // one level of an 8-bit image
+        for y in 0..6 {
+            for x in 0..6 {
+                assert_abs_diff_eq!(canvas.get(x, y), expected.get(x, y), epsilon = 1.0 / 255.0);
+            }
+        }
+    }
+}
+
+mod regions {
+    use super::*;
+    use crate::core::{Normal3D, Point3D, Transform};
+    use crate::ppm_writer::write_ppm_binary;
+    use crate::scene::World;
+    use std::f64::consts::PI;
+    use std::num::{NonZeroU16, NonZeroU8};
+
+    fn camera() -> Camera {
+        Camera::new(
+            NonZeroU16::new(21).unwrap(),
+            NonZeroU16::new(15).unwrap(),
+            PI / 3.0,
+            Transform::view_transform(
+                Point3D::new(0.0, 1.5, -5.0),
+                Point3D::ORIGIN,
+                Normal3D::POSITIVE_Y,
+            ),
+        )
+    }
+
+    fn region(x: u16, y: u16, width: u16, height: u16) -> Rect {
+        Rect::new(
+            x,
+            y,
+            NonZeroU16::new(width).unwrap(),
+            NonZeroU16::new(height).unwrap(),
+        )
+    }
+
+    #[test]
+    fn rendering_four_quadrants_should_reassemble_into_the_full_image() {
+        let world = World::default();
+        let camera = camera();
+        let samples = Samples::grid(NonZeroU8::new(2).unwrap());
+
+        // deliberately uneven, so the quadrants are different sizes
+        let quadrants = vec![
+            region(0, 0, 11, 8),
+            region(11, 0, 10, 8),
+            region(0, 8, 11, 7),
+            region(11, 8, 10, 7),
+        ];
+
+        let mut combined = Canvas::new(camera.width(), camera.height()).unwrap();
+        for quadrant in quadrants {
+            let canvas = render_region(&world, &camera, &samples, quadrant);
+            assert_eq!(canvas.width(), quadrant.width.get() as usize);
+            assert_eq!(canvas.height(), quadrant.height.get() as usize);
+
+            for y in 0..quadrant.height.get() {
+                for x in 0..quadrant.width.get() {
+                    combined.set(quadrant.x + x, quadrant.y + y, canvas.get(x, y));
+                }
+            }
+        }
+
+        let full = render(&world, &camera, &samples, false);
+        assert_eq!(write_ppm_binary(&combined), write_ppm_binary(&full));
+    }
+
+    #[test]
+    #[should_panic]
+    fn rendering_a_region_outside_of_the_image_should_panic() {
+        render_region(
+            &World::default(),
+            &camera(),
+            &Samples::single(),
+            region(15, 10, 10, 10),
+        );
+    }
+}
+
+mod stats {
+    use super::*;
+    use crate::core::{Colour, Normal3D, Point3D, Transform};
+    use crate::scene::{AoSettings, Light, Material, MaterialKind, Object, World};
+    use std::f64::consts::PI;
+    use std::num::NonZeroU16;
+
+    fn camera() -> Camera {
+        Camera::new(
+            NonZeroU16::new(20).unwrap(),
+            NonZeroU16::new(15).unwrap(),
+            PI / 3.0,
+            Transform::view_transform(
+                Point3D::new(0.0, 0.0, -5.0),
+                Point3D::ORIGIN,
+                Normal3D::POSITIVE_Y,
+            ),
+        )
+    }
+
+    fn single_sphere(material: Material) -> World {
+        let mut world = World::empty();
+        world.add(Object::sphere().with_material(material));
+        world.lights.push(Light::point(
+            Colour::WHITE,
+            Point3D::new(-10.0, 10.0, -10.0),
+        ));
+
+        world
+    }
+
+    #[test]
+    fn a_single_sphere_scene_should_cast_one_primary_ray_per_pixel_and_no_secondary_rays() {
+        let world = single_sphere(Material::default());
+
+        let (canvas, stats) = render_with_stats(&world, &camera(), &Samples::single());
+
+        assert_eq!(stats.primary_rays, 20 * 15);
+        assert_eq!(stats.reflection_rays, 0);
+        assert_eq!(stats.refraction_rays, 0);
+
+        // each pixel which hits the sphere casts one shadow ray to the single light
+        let hits = (0..15)
+            .flat_map(|y| (0..20).map(move |x| (x, y)))
+            .filter(|&(x, y)| canvas.get(x, y) != Colour::BLACK)
+            .count();
+        assert_eq!(stats.shadow_rays, hits as u64);
+        assert!(hits > 0);
+        // every primary ray tests at least the bounds of the sphere
+        assert!(stats.bbox_tests >= stats.primary_rays, "{:?}", stats);
+    }
+
+    #[test]
+    fn reflective_and_transparent_materials_should_cast_secondary_rays() {
+        let world = single_sphere(Material {
+            reflective: 0.5,
+            transparency: 0.5,
+            refractive: 1.5,
+            ..Default::default()
+        });
+
+        let (_, stats) = render_with_stats(&world, &camera(), &Samples::single());
+
+        assert_eq!(stats.primary_rays, 20 * 15);
+        assert!(stats.reflection_rays > 0, "{:?}", stats);
+        assert!(stats.refraction_rays > 0, "{:?}", stats);
+    }
+
+    #[test]
+    fn rendering_with_stats_twice_should_produce_the_same_stats() {
+        let world = single_sphere(Material::default());
+
+        let (_, first) = render_with_stats(&world, &camera(), &Samples::single());
+        render(&world, &camera(), &Samples::single(), false);
+        let (_, second) = render_with_stats(&world, &camera(), &Samples::single());
+
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn rendering_with_stats_should_produce_the_same_image_as_render() {
+        let world = single_sphere(Material {
+            kind: MaterialKind::Solid(Colour::new(0.2, 0.4, 0.8)),
+            ..Default::default()
+        });
+
+        let (canvas, _) = render_with_stats(&world, &camera(), &Samples::single());
+        let expected = render(&world, &camera(), &Samples::single(), false);
+        for y in 0..15 {
+            for x in 0..20 {
+                assert_eq!(canvas.get(x, y), expected.get(x, y), "{:?}", (x, y));
+            }
+        }
+    }
+
+    #[test]
+    fn rendering_the_same_world_at_the_same_time_should_not_affect_the_stats() {
+        let world = single_sphere(Material::default());
+        let (_, expected) = render_with_stats(&world, &camera(), &Samples::single());
+
+        let (_, stats) = std::thread::scope(|scope| {
+            let other = scope.spawn(|| render_with_stats(&world, &camera(), &Samples::single()));
+            render(&world, &camera(), &Samples::single(), false);
+            let stats = render_with_stats(&world, &camera(), &Samples::single());
+
+            assert_eq!(other.join().unwrap().1, expected);
+            stats
+        });
+
+        assert_eq!(stats, expected);
+    }
+
+    #[test]
+    fn ambient_occlusion_should_count_each_ray_cast_over_the_hemisphere() {
+        let mut world = single_sphere(Material::default());
+        world.settings.ambient_occlusion = Some(AoSettings {
+            samples: 4,
+            radius: 1.0,
+        });
+
+        let (_, stats) = render_with_stats(&world, &camera(), &Samples::single());
+
+        // each pixel which hits the sphere casts one shadow ray, and 4 ambient occlusion rays
+        assert!(stats.shadow_rays > 0, "{:?}", stats);
+        assert_eq!(stats.ambient_occlusion_rays, stats.shadow_rays * 4);
+    }
+
+    #[test]
+    fn finding_the_nearest_hit_in_a_group_should_count_each_bounding_box_once() {
+        let mut world = World::empty();
+        world.add(Object::group(vec![
+            Object::sphere(),
+            Object::sphere().transformed(Transform::identity().translate_z(3.0)),
+        ]));
+        let camera = Camera::new(
+            NonZeroU16::new(1).unwrap(),
+            NonZeroU16::new(1).unwrap(),
+            PI / 3.0,
+            Transform::view_transform(
+                Point3D::new(0.0, 0.0, -5.0),
+                Point3D::ORIGIN,
+                Normal3D::POSITIVE_Y,
+            ),
+        );
+
+        let (_, stats) = render_with_stats(&world, &camera, &Samples::single());
+
+        // there are no lights, so only the camera ray is cast - it tests the group, then both spheres,
+        // and the second sphere is skipped as it's beyond the first
+        assert_eq!(stats.primary_rays, 1);
+        assert_eq!(stats.shadow_rays, 0);
+        assert_eq!(stats.bbox_tests, 3);
+    }
+}
+
+mod bounds {
+    use super::*;
+    use crate::core::{Colour, Normal3D, Point3D, Transform};
+    use crate::scene::{Object, World};
+    use std::f64::consts::PI;
+    use std::num::NonZeroU16;
+
+    #[test]
+    fn the_bounds_of_a_unit_cube_at_the_origin_should_be_drawn_in_the_centre_of_the_image() {
+        let mut world = World::empty();
+        world.add(Object::cube());
+
+        let camera = Camera::new(
+            NonZeroU16::new(101).unwrap(),
+            NonZeroU16::new(101).unwrap(),
+            PI / 3.0,
+            Transform::view_transform(
+                Point3D::new(0.0, 0.0, -5.0),
+                Point3D::ORIGIN,
+                Normal3D::POSITIVE_Y,
+            ),
+        );
+
+        let canvas = render_bounds(&world, &camera);
+
+        let drawn = (0..101u16)
+            .flat_map(|y| (0..101u16).map(move |x| (x, y)))
+            .filter(|&(x, y)| canvas.get(x, y) != Colour::BLACK)
+            .collect::<Vec<_>>();
+        assert!(!drawn.is_empty());
+
+        let min_x = drawn.iter().map(|(x, _)| *x).min().unwrap();
+        let max_x = drawn.iter().map(|(x, _)| *x).max().unwrap();
+        let min_y = drawn.iter().map(|(_, y)| *y).min().unwrap();
+        let max_y = drawn.iter().map(|(_, y)| *y).max().unwrap();
+
+        // the near face of the cube is 4 units from the camera, so spans a little under half of the image in each direction
+        for (min, max) in [(min_x, max_x), (min_y, max_y)] {
+            assert!(
+                (min as i32 + max as i32 - 100).abs() <= 1,
+                "{} {}",
+                min,
+                max
+            );
+            assert!((max - min) > 40 && (max - min) < 50, "{} {}", min, max);
+        }
+        // only the edges are drawn
+        assert_eq!(canvas.get(50, 50), Colour::BLACK);
+    }
+
+    #[test]
+    fn unbounded_objects_should_not_be_drawn() {
+        let mut world = World::empty();
+        world.add(Object::plane());
+
+        let camera = Camera::new(
+            NonZeroU16::new(20).unwrap(),
+            NonZeroU16::new(20).unwrap(),
+            PI / 3.0,
+            Transform::view_transform(
+                Point3D::new(0.0, 1.0, -5.0),
+                Point3D::ORIGIN,
+                Normal3D::POSITIVE_Y,
+            ),
+        );
+
+        let canvas = render_bounds(&world, &camera);
+        (0..20u16)
+            .flat_map(|y| (0..20u16).map(move |x| (x, y)))
+            .for_each(|(x, y)| assert_eq!(canvas.get(x, y), Colour::BLACK));
+    }
 }
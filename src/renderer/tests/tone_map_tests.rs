@@ -0,0 +1,68 @@
+use super::*;
+use crate::core::Colour;
+use approx::*;
+use std::num::NonZeroU16;
+
+fn canvas_of(colours: &[Colour]) -> Canvas {
+    let mut canvas = Canvas::new(
+        NonZeroU16::new(colours.len() as _).unwrap(),
+        NonZeroU16::new(1).unwrap(),
+    )
+    .unwrap();
+    colours
+        .iter()
+        .enumerate()
+        .for_each(|(x, colour)| canvas.set(x as _, 0, *colour));
+
+    canvas
+}
+
+#[test]
+fn reinhard_tone_mapping_should_compress_each_channel_independently() {
+    let mut canvas = canvas_of(&[Colour::new(4.0, 0.0, 1.0), Colour::greyscale(9.0)]);
+    tone_map(&mut canvas, ToneMap::Reinhard);
+
+    assert_abs_diff_eq!(canvas.get(0, 0), Colour::new(0.8, 0.0, 0.5));
+    assert_abs_diff_eq!(canvas.get(1, 0), Colour::greyscale(0.9));
+}
+
+#[test]
+fn aces_tone_mapping_should_keep_black_and_saturate_bright_values_to_white() {
+    let mut canvas = canvas_of(&[Colour::BLACK, Colour::greyscale(1000.0)]);
+    tone_map(&mut canvas, ToneMap::Aces);
+
+    assert_eq!(canvas.get(0, 0), Colour::BLACK);
+    assert_eq!(canvas.get(1, 0), Colour::WHITE);
+}
+
+#[test]
+fn aces_tone_mapping_should_preserve_the_order_of_brightness() {
+    let inputs = [0.01, 0.1, 0.5, 1.0, 2.0, 4.0];
+    let mut canvas = canvas_of(
+        &inputs
+            .iter()
+            .map(|&c| Colour::greyscale(c))
+            .collect::<Vec<_>>(),
+    );
+    tone_map(&mut canvas, ToneMap::Aces);
+
+    let outputs = (0..inputs.len())
+        .map(|x| canvas.get(x as _, 0).red())
+        .collect::<Vec<_>>();
+    assert!(
+        outputs.windows(2).all(|pair| pair[0] < pair[1]),
+        "{:?}",
+        outputs
+    );
+    assert!(outputs.iter().all(|&c| c > 0.0 && c < 1.0), "{:?}", outputs);
+}
+
+#[test]
+fn tone_mapping_should_treat_negative_values_as_black() {
+    [ToneMap::Reinhard, ToneMap::Aces].iter().for_each(|&mode| {
+        let mut canvas = canvas_of(&[Colour::new(-1.0, -0.5, 0.0)]);
+        tone_map(&mut canvas, mode);
+
+        assert_eq!(canvas.get(0, 0), Colour::BLACK, "{:?}", mode);
+    });
+}
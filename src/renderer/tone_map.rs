@@ -0,0 +1,43 @@
+use super::Canvas;
+use crate::core::Colour;
+
+/// Curves which compress colours above 1.0 back into the displayable range, rather than clamping them to flat white
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ToneMap {
+    /// `c / (1 + c)` - never quite reaches white, and noticeably darkens the mid-tones
+    Reinhard,
+    /// an approximation of the ACES filmic curve, which keeps more contrast in the mid-tones than `Reinhard`
+    Aces,
+}
+
+impl ToneMap {
+    fn apply(&self, channel: f64) -> f64 {
+        let c = channel.max(0.0);
+
+        match self {
+            ToneMap::Reinhard => c / (1.0 + c),
+            // see https://knarkowicz.wordpress.com/2016/01/06/aces-filmic-tone-mapping-curve/
+            ToneMap::Aces => ((c * (2.51 * c + 0.03)) / (c * (2.43 * c + 0.59) + 0.14)).min(1.0),
+        }
+    }
+}
+
+/// Applies the tone mapping curve to each channel of each pixel - intended to be used on a rendered canvas before it's
+/// written (e.g. by `image_writer::write`)
+pub fn tone_map(canvas: &mut Canvas, mode: ToneMap) {
+    for y in 0..canvas.height() as u16 {
+        for x in 0..canvas.width() as u16 {
+            let colour = canvas.get(x, y);
+
+            canvas.set(
+                x,
+                y,
+                Colour::new(
+                    mode.apply(colour.red()),
+                    mode.apply(colour.green()),
+                    mode.apply(colour.blue()),
+                ),
+            );
+        }
+    }
+}
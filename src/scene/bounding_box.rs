@@ -54,6 +54,23 @@ impl BoundingBox {
             && max.z() >= point.z()
     }
 
+    pub fn centre(&self) -> Point3D {
+        Point3D::new(
+            (self.min.x() + self.max.x()) / 2.0,
+            (self.min.y() + self.max.y()) / 2.0,
+            (self.min.z() + self.max.z()) / 2.0,
+        )
+    }
+
+    /// i.e. reaches the `LIMIT` in any direction, as with planes
+    pub fn is_unbounded(&self) -> bool {
+        let reaches_limit = |value: f64| value.abs() >= Self::LIMIT;
+
+        [self.min, self.max]
+            .iter()
+            .any(|p| reaches_limit(p.x()) || reaches_limit(p.y()) || reaches_limit(p.z()))
+    }
+
     #[allow(dead_code)]
     pub fn excludes(&self, point: Point3D) -> bool {
         !self.contains(point)
@@ -97,7 +114,54 @@ impl BoundingBox {
         BoundingBox::new(Point3D::min(points), Point3D::max(points))
     }
 
+    /// the 12 edges of the box, as pairs of corners
+    pub fn edges(&self) -> [(Point3D, Point3D); 12] {
+        let corner = |x: f64, y: f64, z: f64| Point3D::new(x, y, z);
+        let (min, max) = (self.min, self.max);
+
+        let bottom = [
+            corner(min.x(), min.y(), min.z()),
+            corner(max.x(), min.y(), min.z()),
+            corner(max.x(), min.y(), max.z()),
+            corner(min.x(), min.y(), max.z()),
+        ];
+        let top = bottom.map(|point| corner(point.x(), max.y(), point.z()));
+
+        [
+            (bottom[0], bottom[1]),
+            (bottom[1], bottom[2]),
+            (bottom[2], bottom[3]),
+            (bottom[3], bottom[0]),
+            (top[0], top[1]),
+            (top[1], top[2]),
+            (top[2], top[3]),
+            (top[3], top[0]),
+            (bottom[0], top[0]),
+            (bottom[1], top[1]),
+            (bottom[2], top[2]),
+            (bottom[3], top[3]),
+        ]
+    }
+
+    /// whether the infinite line along the `ray` passes through the box - note that this includes boxes behind the ray origin
     pub fn intersected_by(&self, ray: &Ray) -> bool {
+        self.slab_range(ray).is_some()
+    }
+
+    /// The `t` value at which the `ray` enters the box, or `None` if the ray misses the box, or the box is entirely behind
+    /// the ray origin. If the ray origin is inside the box, the entry `t` is negative
+    pub fn intersection_t(&self, ray: &Ray) -> Option<f64> {
+        let (t_near, t_far) = self.slab_range(ray)?;
+
+        if t_far < 0.0 {
+            None
+        } else {
+            Some(t_near)
+        }
+    }
+
+    /// the range of `t` values for which the line along the `ray` is inside the box, if it passes through the box at all
+    fn slab_range(&self, ray: &Ray) -> Option<(f64, f64)> {
         // roughly adapted from https://www.scratchapixel.com/lessons/3d-basic-rendering/minimal-ray-tracer-rendering-simple-shapes/ray-box-intersection
         // note that the "optimised" version shown at the link above doesn't appear to be significantly faster when translated into rust
         // (including using unsafe functions to bypass array bounds checking) and definitely isn't fast enough to justify the excessively terse code
@@ -122,7 +186,7 @@ impl BoundingBox {
         // bitwise or yields a significant performance improvement over short-circuiting or;
         // it possibly plays better with the branch predictor
         if (t_min_x > t_max_y) | (t_min_y > t_max_x) {
-            return false;
+            return None;
         }
 
         let t_min = t_min_x.max(t_min_y);
@@ -138,10 +202,10 @@ impl BoundingBox {
         };
 
         if (t_min > t_max_z) | (t_min_z > t_max) {
-            return false;
+            return None;
         }
 
-        return true;
+        Some((t_min.max(t_min_z), t_max.min(t_max_z)))
     }
 
     pub fn split(&self) -> (Self, Self) {
@@ -176,12 +240,10 @@ impl BoundingBox {
         }
     }
 
-    #[cfg(test)]
     pub fn min(&self) -> Point3D {
         self.min
     }
 
-    #[cfg(test)]
     pub fn max(&self) -> Point3D {
         self.max
     }
@@ -0,0 +1,76 @@
+use super::*;
+use crate::core::Point3D;
+use std::cmp::Ordering;
+
+/// Recursively partitions `children` into a binary tree of groups, splitting each set of children in half along the axis
+/// in which their centres are most spread out, until each group contains no more than `leaf_size` children.
+///
+/// Children with unbounded bounding boxes (e.g. planes) are left at the top level, as any group containing them would
+/// also be unbounded, and therefore always intersected
+pub(in crate::scene) fn partition(children: Vec<Object>, leaf_size: usize) -> Vec<Object> {
+    let (mut unbounded, bounded): (Vec<_>, Vec<_>) = children
+        .into_iter()
+        .partition(|child| child.bounds.is_unbounded());
+
+    unbounded.extend(split(bounded, leaf_size.max(2)));
+    unbounded
+}
+
+fn split(mut children: Vec<Object>, leaf_size: usize) -> Vec<Object> {
+    if children.len() <= leaf_size {
+        return children;
+    }
+
+    let axis = longest_axis(&children);
+    let centre_on_axis = |object: &Object| {
+        let centre = object.bounds.centre();
+        [centre.x(), centre.y(), centre.z()][axis]
+    };
+
+    let halfway = children.len() / 2;
+    children.select_nth_unstable_by(halfway, |a, b| {
+        centre_on_axis(a)
+            .partial_cmp(&centre_on_axis(b))
+            .unwrap_or(Ordering::Equal)
+    });
+    let right = children.split_off(halfway);
+
+    vec![as_node(children, leaf_size), as_node(right, leaf_size)]
+}
+
+fn as_node(children: Vec<Object>, leaf_size: usize) -> Object {
+    let mut children = split(children, leaf_size);
+
+    if children.len() == 1 {
+        children.remove(0)
+    } else {
+        Object::group(children)
+    }
+}
+
+/// the index of the axis (X = 0, Y = 1, Z = 2) along which the centres of the `children` are most spread out
+fn longest_axis(children: &[Object]) -> usize {
+    let centres = children
+        .iter()
+        .map(|child| child.bounds.centre())
+        .collect::<Vec<_>>();
+    let extent = |component: fn(&Point3D) -> f64| {
+        let (min, max) = centres
+            .iter()
+            .map(component)
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), next| {
+                (min.min(next), max.max(next))
+            });
+
+        max - min
+    };
+
+    let (x, y, z) = (extent(Point3D::x), extent(Point3D::y), extent(Point3D::z));
+    if x >= y && x >= z {
+        0
+    } else if y >= z {
+        1
+    } else {
+        2
+    }
+}
@@ -1,4 +1,4 @@
-use crate::core::{Colour, F64Ext, Normal3D, Point3D, Ray, Vector3D, VectorMaths};
+use crate::core::{Colour, Normal3D, Point3D, Ray, Vector3D, VectorMaths, EPSILON};
 use crate::scene::{LightSample, Object};
 use smallvec::SmallVec;
 
@@ -6,21 +6,73 @@ use smallvec::SmallVec;
 pub struct Intersection<'with> {
     pub t: f64,
     pub with: &'with Object,
+    /// the instance the ray passed through to hit `with`, if it's part of a shared object - see `Object::instance`
+    pub instance: Option<&'with Object>,
 }
 
 impl<'with> Intersection<'with> {
     pub fn new(t: f64, with: &'with Object) -> Intersection {
-        Intersection { t, with }
+        Intersection {
+            t,
+            with,
+            instance: None,
+        }
+    }
+
+    /// whether this intersection is in front of the ray origin, and isn't a hit on the `last` object within `bias` of the ray
+    /// origin - see `Intersections::hit_beyond`
+    pub(in crate::scene) fn is_hit_beyond(&self, last: Option<u32>, bias: f64) -> bool {
+        self.t >= 0.0 && (Some(self.with.id()) != last || self.t.abs() > bias)
+    }
+
+    /// whether both intersections are with the same object, and through the same instance (if any), as each instance of a
+    /// shared object is a separate object in the scene, despite sharing the same geometry
+    pub(in crate::scene) fn is_with_same_object(&self, other: &Intersection) -> bool {
+        self.with.id() == other.with.id()
+            && self.instance.map(Object::id) == other.instance.map(Object::id)
+    }
+
+    /// the world space normal of the object hit, at the world space `point`
+    pub(in crate::scene) fn normal_at(&self, point: Point3D, time: f64) -> Normal3D {
+        match self.instance {
+            Some(instance) => {
+                let normal = self
+                    .with
+                    .normal_at_time(instance.to_shared_space(point, time), time);
+                instance.normal_from_shared_space(normal, time)
+            }
+            None => self.with.normal_at_time(point, time),
+        }
+    }
+
+    /// the colour of the object hit at the world space `point`, without lighting - see `Object::raw_colour_at`
+    pub(in crate::scene) fn raw_colour_at(&self, point: Point3D, time: f64) -> Colour {
+        let point = match self.instance {
+            Some(instance) => instance.to_shared_space(point, time),
+            None => point,
+        };
+        let normal = self.with.normal_at_time(point, time);
+
+        self.with.raw_colour_at_time(point, Some(normal), time)
     }
 }
 
 pub struct HitData<'obj> {
     pub object: &'obj Object,
+    /// see `Intersection::instance`
+    pub instance: Option<&'obj Object>,
     pub eye: Normal3D,
     pub normal: Normal3D,
     pub point: Point3D,
+    /// whether the ray hit the surface from inside the object, i.e. the ray was travelling through the object
+    pub inside: bool,
+    /// the innermost object the ray was travelling through before the hit, if any - whose material's refractive index is
+    /// the `entered_refractive`
+    pub medium: Option<&'obj Object>,
     pub entered_refractive: f64,
     pub exited_refractive: f64,
+    /// the time (between 0 and 1) while the shutter was open that the ray was cast at
+    pub time: f64,
 }
 
 impl<'obj> HitData<'obj> {
@@ -31,7 +83,7 @@ impl<'obj> HitData<'obj> {
     ) -> Self {
         let point = ray.position(intersection.t);
         let eye = -ray.direction.normalised();
-        let normal = intersection.with.normal_at(point);
+        let normal = intersection.normal_at(point, ray.time);
 
         let inside = normal.dot(eye) < 0.0;
 
@@ -40,32 +92,31 @@ impl<'obj> HitData<'obj> {
         // calculate refraction changes from entering one material and exiting another (including the empty space)
         let mut entered_refractive = 1.0;
         let mut exited_refractive = 1.0;
-        let mut containers: Vec<&Object> = vec![];
+        let mut medium = None;
+        let mut containers: Vec<&Intersection> = vec![];
 
         for i in intersections.0.iter() {
-            if i.t == intersection.t && i.with.id() == intersection.with.id() {
+            if i.t == intersection.t && i.is_with_same_object(&intersection) {
                 // intersection from entering object
-                if let Some(&last) = containers.last() {
-                    entered_refractive = last.material.refractive;
+                if let Some(last) = containers.last() {
+                    entered_refractive = last.with.material.refractive;
+                    medium = Some(last.with);
                 }
             }
 
             if let Some(index) = containers
                 .iter()
-                .cloned()
-                .enumerate()
-                .find(|(_, obj)| obj.id() == i.with.id())
-                .map(|(idx, _)| idx)
+                .position(|container| container.is_with_same_object(i))
             {
                 containers.remove(index); // exiting transparent object
             } else {
-                containers.push(i.with); // entering transparent object
+                containers.push(i); // entering transparent object
             }
 
-            if i.t == intersection.t && i.with.id() == intersection.with.id() {
+            if i.t == intersection.t && i.is_with_same_object(&intersection) {
                 // intersection from exiting object
-                if let Some(&last) = containers.last() {
-                    exited_refractive = last.material.refractive;
+                if let Some(last) = containers.last() {
+                    exited_refractive = last.with.material.refractive;
                     break;
                 }
             }
@@ -73,16 +124,45 @@ impl<'obj> HitData<'obj> {
 
         HitData {
             object: intersection.with,
+            instance: intersection.instance,
             eye,
             normal,
             point,
+            inside,
+            medium,
             entered_refractive,
             exited_refractive,
+            time: ray.time,
+        }
+    }
+
+    /// hit data for an arbitrary point on an object, without casting a ray - intended for testing shading in isolation.
+    /// `refractives` are the refractive indices of the materials being (entered, exited)
+    #[cfg(test)]
+    pub fn synthetic(
+        object: &'obj Object,
+        point: Point3D,
+        eye: Normal3D,
+        normal: Normal3D,
+        (entered_refractive, exited_refractive): (f64, f64),
+    ) -> Self {
+        HitData {
+            object,
+            instance: None,
+            eye,
+            normal,
+            point,
+            inside: false,
+            medium: None,
+            entered_refractive,
+            exited_refractive,
+            time: 0.0,
         }
     }
 
     pub fn colour(&self, direct_light: Colour, light_source: &LightSample) -> Colour {
-        self.object.colour_at(
+        self.object.lit_colour(
+            self.raw_colour(),
             self.point,
             direct_light,
             self.eye,
@@ -91,30 +171,91 @@ impl<'obj> HitData<'obj> {
         )
     }
 
-    pub fn reflection(&self) -> ReflectionData {
-        let ratio = self.entered_refractive / self.exited_refractive;
-        let cos_i = self.eye.dot(self.normal);
-        let sin2_t = ratio.powi(2) * (1.0 - cos_i.powi(2));
+    /// the colour of the object at the hit point, without lighting - see `Object::raw_colour_at`
+    pub fn raw_colour(&self) -> Colour {
+        let (point, normal) = match self.instance {
+            Some(instance) => {
+                let point = instance.to_shared_space(self.point, self.time);
+                (point, self.object.normal_at_time(point, self.time))
+            }
+            None => (self.point, self.normal),
+        };
 
-        ReflectionData {
-            cos_i,
-            ratio,
-            sin2_t,
-        }
+        self.object
+            .raw_colour_at_time(point, Some(normal), self.time)
+    }
+
+    /// How light is split between reflection and refraction where the ray crosses from the material it was travelling
+    /// through into the material it hit - see `ReflectionData`.
+    ///
+    /// e.g. the fraction of light reflected by glass, when looking straight at it:
+    ///
+    /// ```
+    /// use ray_tracer::core::{Normal3D, Point3D, Ray};
+    /// use ray_tracer::scene::{HitData, Material, Object};
+    ///
+    /// let glass = Object::sphere().with_material(Material::glass());
+    /// let ray = Ray::new(Point3D::new(0.0, 0.0, -5.0), Normal3D::POSITIVE_Z);
+    ///
+    /// let intersections = glass.intersect(&ray);
+    /// let hit = intersections.hit(None).unwrap();
+    /// let hit_data = HitData::from(&ray, hit, intersections);
+    ///
+    /// let reflection = hit_data.reflection();
+    /// assert!(!reflection.is_total());
+    ///
+    /// // from air (1.0) into glass (1.5)
+    /// let reflectance = reflection.reflectance(hit_data.entered_refractive, hit_data.exited_refractive);
+    /// assert!((reflectance - 0.04).abs() < 1e-9);
+    /// ```
+    pub fn reflection(&self) -> ReflectionData {
+        ReflectionData::new(
+            self.eye,
+            self.normal,
+            self.entered_refractive,
+            self.exited_refractive,
+        )
     }
 }
 
+/// The angles involved in light crossing the boundary between two materials, from which the direction of the refracted
+/// ray, and the fraction of light reflected rather than refracted, can be calculated
 pub struct ReflectionData {
+    /// the cosine of the angle between the eye and the normal
     pub cos_i: f64,
+    /// the ratio of the refractive indices, i.e. `entered / exited`
     pub ratio: f64,
+    /// the square of the sine of the angle between the refracted ray and the (negated) normal
     pub sin2_t: f64,
 }
 
 impl ReflectionData {
+    /// `eye` and `normal` are the directions towards the viewer and out of the surface (on the same side as the eye),
+    /// and the refractive indices are those of the materials the ray is leaving (`entered`) and entering (`exited`) - see `HitData`
+    pub fn new(
+        eye: Normal3D,
+        normal: Normal3D,
+        entered_refractive: f64,
+        exited_refractive: f64,
+    ) -> Self {
+        let ratio = entered_refractive / exited_refractive;
+        let cos_i = eye.dot(normal);
+        let sin2_t = ratio.powi(2) * (1.0 - cos_i.powi(2));
+
+        ReflectionData {
+            cos_i,
+            ratio,
+            sin2_t,
+        }
+    }
+
+    /// true if the light is entirely reflected (total internal reflection), such that there is no refracted ray
     pub fn is_total(&self) -> bool {
         self.sin2_t > 1.0
     }
 
+    /// the direction of the refracted ray, which isn't normalised
+    ///
     /// note: reflection must not be total (sin2_t must not be > 1.0)
     pub fn refraction_vector(&self, normal: Normal3D, eye: Normal3D) -> Vector3D {
         debug_assert!(self.sin2_t <= 1.0);
@@ -122,7 +263,8 @@ impl ReflectionData {
         normal * (self.ratio * self.cos_i - self.cos_t()) - (eye * self.ratio)
     }
 
-    /// `shlick` approximation of fresnel
+    /// the fraction of light which is reflected, rather than refracted, using the `schlick` approximation of fresnel -
+    /// the refractive indices must be the same as those given to `new`
     pub fn reflectance(&self, entered_refractive: f64, exited_refractive: f64) -> f64 {
         if self.is_total() {
             return 1.0;
@@ -193,6 +335,12 @@ impl<'scene> Intersections<'scene> {
         self
     }
 
+    /// removes any intersections with a `t` value greater than `max_t`
+    pub fn clipped_beyond(mut self, max_t: f64) -> Self {
+        self.0.retain(|intersection| intersection.t <= max_t);
+        self
+    }
+
     pub fn len(&self) -> usize {
         self.0.len()
     }
@@ -202,15 +350,23 @@ impl<'scene> Intersections<'scene> {
     }
 
     pub fn hit(&self, last: Option<u32>) -> Option<Intersection<'scene>> {
+        self.hit_beyond(last, EPSILON)
+    }
+
+    /// as `hit`, but ignores any intersections with the `last` object hit within `bias` of the ray origin, rather than only
+    /// those at (roughly) zero - see `WorldSettings::shadow_bias`
+    pub fn hit_beyond(&self, last: Option<u32>, bias: f64) -> Option<Intersection<'scene>> {
         self.0
             .iter()
-            .filter(|&intersect| {
-                Some(intersect.with.id()) != last || intersect.t.is_not_roughly_zero()
-            })
-            .find(|&intersect| intersect.t >= 0.0)
+            .find(|&intersect| intersect.is_hit_beyond(last, bias))
             .cloned()
     }
 
+    /// every intersection in front of the ray origin (i.e. with a non-negative `t`), nearest first
+    pub fn all_hits(&self) -> impl Iterator<Item = &Intersection<'scene>> {
+        self.0.iter().filter(|intersect| intersect.t >= 0.0)
+    }
+
     pub fn append(&mut self, mut other: Intersections<'scene>) {
         self.0.append(&mut other.0);
         self.sort();
@@ -220,6 +376,10 @@ impl<'scene> Intersections<'scene> {
         self.0.get(index)
     }
 
+    pub fn iter(&self) -> impl Iterator<Item = &Intersection<'scene>> {
+        self.0.iter()
+    }
+
     pub fn into_iter(self) -> impl Iterator<Item = Intersection<'scene>> {
         self.0.into_iter()
     }
@@ -1,5 +1,5 @@
 use crate::core::Colour;
-use crate::core::{Point3D, Vector3D};
+use crate::core::{Normal3D, Point3D, Vector3D, VectorMaths};
 use itertools::Itertools;
 use rand::rngs::StdRng;
 use rand::{Rng, SeedableRng};
@@ -13,14 +13,76 @@ pub struct Light {
 
 #[derive(Debug, PartialEq, Clone)]
 enum Kind {
-    Point([Point3D; 1]),
-    Area { samples: Vec<Point3D> },
+    Point([Point3D; 1], Falloff),
+    Area {
+        samples: Vec<Point3D>,
+        /// the edges of the cell around each sample, if each sample should be randomly offset within its cell
+        jitter: Option<(Vector3D, Vector3D)>,
+    },
+    Spot {
+        position: [Point3D; 1],
+        direction: Normal3D,
+        /// cosines of the angles, to avoid calculating `acos` for every point lit
+        cos_inner_angle: f64,
+        cos_outer_angle: f64,
+    },
 }
 
 impl Light {
     pub fn point(colour: Colour, position: Point3D) -> Self {
         Light {
-            kind: Kind::Point([position]),
+            kind: Kind::Point([position], Falloff::NONE),
+            colour,
+        }
+    }
+
+    /// Create a point light which gets dimmer further away from the light, such that the intensity at distance `d` is
+    /// scaled by `1 / (constant + linear * d + quadratic * d^2)`.
+    ///
+    /// e.g. `(0.0, 0.0, 1.0)` is physically accurate (inverse square) falloff, while `(1.0, 0.0, 0.0)` is equivalent to `Light::point`
+    pub fn point_with_falloff(
+        colour: Colour,
+        position: Point3D,
+        constant: f64,
+        linear: f64,
+        quadratic: f64,
+    ) -> Self {
+        Light {
+            kind: Kind::Point(
+                [position],
+                Falloff {
+                    constant,
+                    linear,
+                    quadratic,
+                },
+            ),
+            colour,
+        }
+    }
+
+    /// Create a Spot light, which casts light from a single point like a point light, but only within a cone around `direction`.
+    ///
+    /// Points within `inner_angle` (in radians) of `direction` are lit at full intensity; the intensity smoothly falls off
+    /// to nothing between `inner_angle` and `outer_angle`, and points outside of `outer_angle` are not lit at all
+    pub fn spot(
+        colour: Colour,
+        position: Point3D,
+        direction: Vector3D,
+        inner_angle: f64,
+        outer_angle: f64,
+    ) -> Self {
+        assert!(
+            inner_angle <= outer_angle,
+            "the inner angle of a spot light cannot be larger than the outer angle"
+        );
+
+        Light {
+            kind: Kind::Spot {
+                position: [position],
+                direction: direction.normalised(),
+                cos_inner_angle: inner_angle.cos(),
+                cos_outer_angle: outer_angle.cos(),
+            },
             colour,
         }
     }
@@ -34,7 +96,7 @@ impl Light {
     /// # Notes
     /// Light is cast in all directions equally, i.e. there is no distinction between the "front" face and the "back" face.
     /// A fixed number of samples are taken from the surface, given by `u_steps * v_steps`.
-    /// Shadows are not perfectly soft, and will show banding, depending on the sample count.
+    /// Shadows are not perfectly soft, and will be noisy, depending on the sample count.
     /// Taking higher `u_steps` and `v_steps` samples will result in better shadows, at the cost of
     /// exponentially increasing rendering time.
     ///
@@ -45,7 +107,6 @@ impl Light {
     /// `v` - A 3D Vector defining the "left" edge
     /// `u_steps` - the number of samples to take from the "bottom" edge
     /// `v_steps` - the number of samples to take from the "left" edge
-    /// `seed` - used to randomly offset the sampled locations - providing the same seed ensures rendering is deterministic
     pub fn area(
         colour: Colour,
         bottom_left: Point3D,
@@ -53,37 +114,193 @@ impl Light {
         v: Vector3D,
         u_steps: NonZeroU8,
         v_steps: NonZeroU8,
-        seed: u64,
     ) -> Self {
         let cell_u = u / (u_steps.get() as f64);
         let cell_v = v / (v_steps.get() as f64);
 
-        let mut rng = StdRng::seed_from_u64(seed);
+        // The offsets within each cell aren't chosen here, as the same offsets for every point lit produces obvious banding.
+        // Instead, `World` offsets the samples using a random number generator seeded by the point being lit, so that
+        // rendering is deterministic regardless of which thread renders which point, but neighbouring points use unrelated
+        // offsets, blending the banding into (much less noticeable) noise
+        let centre = bottom_left + cell_u * 0.5 + cell_v * 0.5;
 
-        // This reduces the obvious banding from using a constant offset, but the banding is still visible.
-        // The issue is that this needs to be deterministic, so shadows don't move around when rerendering the same image,
-        // but not _look_ deterministic, e.g. the shadows should blend smoothly into each other.
-        // Producing deterministic "random" data is very challenging in a multi-threaded renderer, as
-        // the order the threads execute in is effectively random. It may be possible to move the random offset
-        // into `world` and use e.g. the target Point as a seed, but this would force the threads to synchronise
-        // with each other, and substantially impact performance
-        let mut offset = || bottom_left + cell_u * rng.gen::<f64>() + cell_v * rng.gen::<f64>();
+        let samples = (0..u_steps.get())
+            .cartesian_product(0..v_steps.get())
+            .map(|(u, v)| centre + cell_u * u as f64 + cell_v * v as f64)
+            .collect();
+
+        Light {
+            kind: Kind::Area {
+                samples,
+                jitter: Some((cell_u, cell_v)),
+            },
+            colour,
+        }
+    }
+
+    /// Create an Area light which samples the exact centre of each cell, rather than a random point within each cell.
+    ///
+    /// Shadows will show obvious banding rather than the noise of `Light::area`, but the sample positions are evenly spaced
+    /// and the same for every point lit. See `Light::area` for details of the arguments
+    pub fn area_fixed(
+        colour: Colour,
+        bottom_left: Point3D,
+        u: Vector3D,
+        v: Vector3D,
+        u_steps: NonZeroU8,
+        v_steps: NonZeroU8,
+    ) -> Self {
+        let cell_u = u / (u_steps.get() as f64);
+        let cell_v = v / (v_steps.get() as f64);
+        let centre = bottom_left + cell_u * 0.5 + cell_v * 0.5;
 
         let samples = (0..u_steps.get())
             .cartesian_product(0..v_steps.get())
-            .map(|(u, v)| offset() + cell_u * u as f64 + cell_v * v as f64)
+            .map(|(u, v)| centre + cell_u * u as f64 + cell_v * v as f64)
+            .collect();
+
+        Light {
+            kind: Kind::Area {
+                samples,
+                jitter: None,
+            },
+            colour,
+        }
+    }
+
+    /// Create an Area light whose `u_steps * v_steps` samples are spread over the light using a Poisson disk, rather than
+    /// one sample per cell of a grid.
+    ///
+    /// Samples are never closer together than a minimum distance, without being aligned to rows or columns, which reduces
+    /// the banding of `Light::area_fixed` at the same sample count, without the noise of `Light::area`.
+    /// `seed` determines the sample positions - see `Light::area` for details of the other arguments
+    pub fn area_poisson(
+        colour: Colour,
+        bottom_left: Point3D,
+        u: Vector3D,
+        v: Vector3D,
+        u_steps: NonZeroU8,
+        v_steps: NonZeroU8,
+        seed: u64,
+    ) -> Self {
+        let (width, height) = (u.magnitude(), v.magnitude());
+        let count = u_steps.get() as usize * v_steps.get() as usize;
+
+        let samples = poisson_disk(width, height, count, seed)
+            .into_iter()
+            .map(|(x, y)| bottom_left + u * x + v * y)
+            .collect();
+
+        Light {
+            kind: Kind::Area {
+                samples,
+                jitter: None,
+            },
+            colour,
+        }
+    }
+
+    /// Create a Spherical light, which casts soft shadows like an Area light, but from the surface of a sphere
+    /// (e.g. a light bulb) rather than from a flat rectangle.
+    ///
+    /// # Notes
+    /// `samples` points are taken uniformly from the whole surface of the sphere, so some samples will be on the far side
+    /// of the sphere from any point being lit. The sphere itself doesn't cast shadows, and isn't visible in the scene.
+    ///
+    /// # Arguments
+    /// `colour` - The full intensity colour of the light
+    /// `centre` - The centre of the sphere
+    /// `radius` - The radius of the sphere
+    /// `samples` - the number of points to sample from the surface
+    /// `seed` - used to randomly choose the sampled points - providing the same seed ensures rendering is deterministic
+    pub fn sphere(
+        colour: Colour,
+        centre: Point3D,
+        radius: f64,
+        samples: NonZeroU8,
+        seed: u64,
+    ) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
+
+        let samples = (0..samples.get())
+            .map(|_| {
+                // uniformly distributed over the surface, as the area of a slice of a sphere is proportional to its height
+                let y = 1.0 - 2.0 * rng.gen::<f64>();
+                let angle = 2.0 * std::f64::consts::PI * rng.gen::<f64>();
+                let slice_radius = (1.0 - y * y).sqrt();
+
+                centre
+                    + Vector3D::new(slice_radius * angle.cos(), y, slice_radius * angle.sin())
+                        * radius
+            })
             .collect();
 
         Light {
-            kind: Kind::Area { samples },
+            kind: Kind::Area {
+                samples,
+                jitter: None,
+            },
             colour,
         }
     }
 
+    /// the position of every sample of the light, and the number of samples - the samples of an area light created by
+    /// `Light::area` are the centre of each cell, which `jittered_samples` randomly offsets
     pub fn samples(&self) -> (impl Iterator<Item = &Point3D>, usize) {
         match &self.kind {
-            Kind::Point(point) => (point.iter(), 1),
-            Kind::Area { samples } => (samples.iter(), samples.len()),
+            Kind::Point(point, _) => (point.iter(), 1),
+            Kind::Area { samples, .. } => (samples.iter(), samples.len()),
+            Kind::Spot { position, .. } => (position.iter(), 1),
+        }
+    }
+
+    /// the same samples as `samples`, except the samples of an area light created by `Light::area` are moved to a random
+    /// point within their cell
+    pub fn jittered_samples<'a>(
+        &'a self,
+        rng: &'a mut impl Rng,
+    ) -> impl Iterator<Item = Point3D> + 'a {
+        let jitter = match &self.kind {
+            Kind::Area { jitter, .. } => *jitter,
+            _ => None,
+        };
+
+        self.samples().0.map(move |sample| match jitter {
+            Some((cell_u, cell_v)) => {
+                *sample + cell_u * (rng.gen::<f64>() - 0.5) + cell_v * (rng.gen::<f64>() - 0.5)
+            }
+            None => *sample,
+        })
+    }
+
+    /// the proportion of the light's intensity which reaches `point`, ignoring shadows - only spot lights and
+    /// point lights with falloff are ever less than 1
+    pub fn intensity_at(&self, point: Point3D) -> f64 {
+        match &self.kind {
+            Kind::Point([position], falloff) => falloff.at((point - *position).magnitude()),
+            Kind::Area { .. } => 1.0,
+            Kind::Spot {
+                position: [position],
+                direction,
+                cos_inner_angle,
+                cos_outer_angle,
+            } => {
+                let to_point = point - *position;
+                if to_point.magnitude() == 0.0 {
+                    return 1.0;
+                }
+                let cos_angle = to_point.normalised().dot(*direction);
+
+                if cos_angle >= *cos_inner_angle {
+                    1.0
+                } else if cos_angle <= *cos_outer_angle {
+                    0.0
+                } else {
+                    // smoothstep
+                    let t = (cos_angle - cos_outer_angle) / (cos_inner_angle - cos_outer_angle);
+                    t * t * (3.0 - 2.0 * t)
+                }
+            }
         }
     }
 
@@ -92,6 +309,65 @@ impl Light {
     }
 }
 
+/// Dart throwing within a `width` by `height` rectangle: random candidates closer than the minimum distance to an existing
+/// sample are rejected, and the minimum distance shrinks whenever too many candidates in a row are rejected, so exactly
+/// `count` samples are always produced. Each sample is returned as fractions of the width and height
+fn poisson_disk(width: f64, height: f64, count: usize, seed: u64) -> Vec<(f64, f64)> {
+    const ATTEMPTS: usize = 30;
+
+    let mut rng = StdRng::seed_from_u64(seed);
+    // close to the densest spacing dart throwing can reach, which is quite a bit sparser than a perfect packing
+    let mut min_distance = 0.75 * (width * height / count as f64).sqrt();
+    let mut samples: Vec<(f64, f64)> = Vec::with_capacity(count);
+    let mut rejected = 0;
+
+    while samples.len() < count {
+        let (x, y) = (rng.gen::<f64>(), rng.gen::<f64>());
+        let far_enough = samples.iter().all(|(other_x, other_y)| {
+            let distance_squared =
+                ((x - other_x) * width).powi(2) + ((y - other_y) * height).powi(2);
+            distance_squared >= min_distance.powi(2)
+        });
+
+        if far_enough {
+            samples.push((x, y));
+            rejected = 0;
+        } else {
+            rejected += 1;
+            if rejected == ATTEMPTS {
+                min_distance *= 0.9;
+                rejected = 0;
+            }
+        }
+    }
+
+    samples
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+struct Falloff {
+    constant: f64,
+    linear: f64,
+    quadratic: f64,
+}
+
+impl Falloff {
+    /// constant intensity, regardless of distance
+    const NONE: Falloff = Falloff {
+        constant: 1.0,
+        linear: 0.0,
+        quadratic: 0.0,
+    };
+
+    fn at(&self, distance: f64) -> f64 {
+        if *self == Falloff::NONE {
+            return 1.0;
+        }
+
+        1.0 / (self.constant + self.linear * distance + self.quadratic * distance.powi(2))
+    }
+}
+
 #[derive(Debug, PartialEq, Clone)]
 pub struct LightSample {
     pub position: Point3D,
@@ -109,7 +385,7 @@ mod tests {
     use super::*;
 
     #[test]
-    fn sampling_an_area_light_should_take_one_sample_from_each_cell() {
+    fn jittering_an_area_light_should_take_one_sample_from_each_cell() {
         let area = Light::area(
             Colour::WHITE,
             Point3D::ORIGIN,
@@ -117,42 +393,294 @@ mod tests {
             Vector3D::new(0.0, 0.0, 1.0),
             nonzero_ext::nonzero!(4u8),
             nonzero_ext::nonzero!(2u8),
-            0,
         );
 
-        let mut samples = area.samples().0;
-        assert_eq!(
-            samples.next().unwrap(),
-            &Point3D::new(0.3655567079318523, 0.0, 0.3867300921766191)
-        );
-        assert_eq!(
-            samples.next().unwrap(),
-            &Point3D::new(0.012922317116677517, 0.0, 0.7920796309681001)
-        );
-        assert_eq!(
-            samples.next().unwrap(),
-            &Point3D::new(0.6310853162862626, 0.0, 0.38599345287341036)
-        );
-        assert_eq!(
-            samples.next().unwrap(),
-            &Point3D::new(0.6093484553357547, 0.0, 0.8968567283345681)
+        let centres = area.samples().0.copied().collect::<Vec<_>>();
+        let mut rng = StdRng::seed_from_u64(0);
+        let jittered = area.jittered_samples(&mut rng).collect::<Vec<_>>();
+
+        assert_eq!(jittered.len(), 8);
+        assert_ne!(jittered, centres);
+        centres
+            .into_iter()
+            .zip(jittered)
+            .for_each(|(centre, sample)| {
+                assert!((sample.x() - centre.x()).abs() <= 0.25, "{:?}", sample);
+                assert_eq!(sample.y(), 0.0);
+                assert!((sample.z() - centre.z()).abs() <= 0.25, "{:?}", sample);
+            });
+    }
+
+    #[test]
+    fn an_area_light_should_be_sampled_from_the_centre_of_each_cell_before_jittering() {
+        let area = Light::area(
+            Colour::WHITE,
+            Point3D::ORIGIN,
+            Vector3D::new(2.0, 0.0, 0.0),
+            Vector3D::new(0.0, 0.0, 1.0),
+            nonzero_ext::nonzero!(4u8),
+            nonzero_ext::nonzero!(2u8),
         );
-        assert_eq!(
-            samples.next().unwrap(),
-            &Point3D::new(1.3746692145496366, 0.0, 0.48446887400119504)
+        let fixed = Light::area_fixed(
+            Colour::WHITE,
+            Point3D::ORIGIN,
+            Vector3D::new(2.0, 0.0, 0.0),
+            Vector3D::new(0.0, 0.0, 1.0),
+            nonzero_ext::nonzero!(4u8),
+            nonzero_ext::nonzero!(2u8),
         );
+
+        assert!(area.samples().0.eq(fixed.samples().0));
+    }
+
+    #[test]
+    fn jittering_a_light_without_cells_should_not_move_the_samples() {
+        let light = Light::point(Colour::WHITE, Point3D::new(1.0, 2.0, 3.0));
+        let mut rng = StdRng::seed_from_u64(0);
+
         assert_eq!(
-            samples.next().unwrap(),
-            &Point3D::new(1.4981586781621425, 0.0, 0.5696882153025913)
+            light.jittered_samples(&mut rng).collect::<Vec<_>>(),
+            vec![Point3D::new(1.0, 2.0, 3.0)]
         );
-        assert_eq!(
-            samples.next().unwrap(),
-            &Point3D::new(1.556432499904909, 0.0, 0.4824178498227347)
+    }
+
+    #[test]
+    fn sampling_a_fixed_area_light_should_take_the_centre_of_each_cell() {
+        let area = Light::area_fixed(
+            Colour::WHITE,
+            Point3D::ORIGIN,
+            Vector3D::new(2.0, 0.0, 0.0),
+            Vector3D::new(0.0, 0.0, 1.0),
+            nonzero_ext::nonzero!(4u8),
+            nonzero_ext::nonzero!(2u8),
         );
+
+        let (samples, n_samples) = area.samples();
+        assert_eq!(n_samples, 8);
         assert_eq!(
-            samples.next().unwrap(),
-            &Point3D::new(1.5247000272159905, 0.0, 0.7128510331304977)
+            samples.copied().collect::<Vec<_>>(),
+            vec![
+                Point3D::new(0.25, 0.0, 0.25),
+                Point3D::new(0.25, 0.0, 0.75),
+                Point3D::new(0.75, 0.0, 0.25),
+                Point3D::new(0.75, 0.0, 0.75),
+                Point3D::new(1.25, 0.0, 0.25),
+                Point3D::new(1.25, 0.0, 0.75),
+                Point3D::new(1.75, 0.0, 0.25),
+                Point3D::new(1.75, 0.0, 0.75),
+            ]
         );
-        assert!(samples.next().is_none());
+    }
+
+    mod poisson_disk_area_lights {
+        use super::*;
+
+        fn poisson(seed: u64) -> Light {
+            Light::area_poisson(
+                Colour::WHITE,
+                Point3D::ORIGIN,
+                Vector3D::new(2.0, 0.0, 0.0),
+                Vector3D::new(0.0, 0.0, 1.0),
+                nonzero_ext::nonzero!(8u8),
+                nonzero_ext::nonzero!(8u8),
+                seed,
+            )
+        }
+
+        fn closest_pair_distance(light: &Light) -> f64 {
+            closest_distance(&light.samples().0.copied().collect::<Vec<_>>())
+        }
+
+        fn closest_distance(samples: &[Point3D]) -> f64 {
+            samples
+                .iter()
+                .tuple_combinations()
+                .map(|(first, second)| (*first - *second).magnitude())
+                .fold(f64::INFINITY, f64::min)
+        }
+
+        #[test]
+        fn a_poisson_disk_area_light_should_take_every_sample_from_within_the_light() {
+            let light = poisson(0);
+            let (samples, n_samples) = light.samples();
+
+            assert_eq!(n_samples, 64);
+            samples.for_each(|sample| {
+                assert!(sample.x() >= 0.0 && sample.x() <= 2.0, "{:?}", sample);
+                assert_eq!(sample.y(), 0.0);
+                assert!(sample.z() >= 0.0 && sample.z() <= 1.0, "{:?}", sample);
+            });
+        }
+
+        #[test]
+        fn poisson_disk_samples_should_maintain_a_minimum_distance_from_each_other() {
+            // half the spacing of a perfect grid with the same number of samples
+            let min_distance = 0.5 * (2.0_f64 / 64.0).sqrt();
+
+            (0..10).for_each(|seed| {
+                let closest = closest_pair_distance(&poisson(seed));
+                assert!(closest >= min_distance, "seed {}: {}", seed, closest);
+            });
+        }
+
+        #[test]
+        fn poisson_disk_samples_should_be_spread_more_evenly_than_jittered_grid_samples() {
+            let jittered = Light::area(
+                Colour::WHITE,
+                Point3D::ORIGIN,
+                Vector3D::new(2.0, 0.0, 0.0),
+                Vector3D::new(0.0, 0.0, 1.0),
+                nonzero_ext::nonzero!(8u8),
+                nonzero_ext::nonzero!(8u8),
+            );
+            let jittered = jittered
+                .jittered_samples(&mut StdRng::seed_from_u64(0))
+                .collect::<Vec<_>>();
+
+            assert!(closest_pair_distance(&poisson(0)) > closest_distance(&jittered));
+        }
+
+        #[test]
+        fn poisson_disk_area_lights_with_the_same_seed_should_have_the_same_samples() {
+            assert_eq!(poisson(3), poisson(3));
+            assert_ne!(poisson(3), poisson(4));
+        }
+    }
+
+    mod sphere_lights {
+        use super::*;
+        use approx::*;
+
+        fn bulb() -> Light {
+            Light::sphere(
+                Colour::WHITE,
+                Point3D::new(1.0, 2.0, 3.0),
+                0.5,
+                nonzero_ext::nonzero!(32u8),
+                7,
+            )
+        }
+
+        #[test]
+        fn every_sample_of_a_sphere_light_should_lie_on_the_surface_of_the_sphere() {
+            let light = bulb();
+            let (samples, n_samples) = light.samples();
+
+            assert_eq!(n_samples, 32);
+            let samples = samples.copied().collect::<Vec<_>>();
+            assert_eq!(samples.len(), 32);
+            samples.into_iter().for_each(|sample| {
+                assert_abs_diff_eq!(
+                    (sample - Point3D::new(1.0, 2.0, 3.0)).magnitude(),
+                    0.5,
+                    epsilon = 1e-12
+                )
+            });
+        }
+
+        #[test]
+        fn a_sphere_light_should_be_sampled_from_all_sides() {
+            let light = bulb();
+            let samples = light.samples().0.copied().collect::<Vec<_>>();
+
+            let above = samples.iter().filter(|s| s.y() > 2.0).count();
+            let right = samples.iter().filter(|s| s.x() > 1.0).count();
+            assert!(above > 0 && above < 32, "{}", above);
+            assert!(right > 0 && right < 32, "{}", right);
+        }
+
+        #[test]
+        fn sphere_lights_with_the_same_seed_should_have_the_same_samples() {
+            assert_eq!(bulb(), bulb());
+            assert_ne!(
+                bulb(),
+                Light::sphere(
+                    Colour::WHITE,
+                    Point3D::new(1.0, 2.0, 3.0),
+                    0.5,
+                    nonzero_ext::nonzero!(32u8),
+                    8,
+                )
+            );
+        }
+
+        #[test]
+        fn a_sphere_light_should_be_fully_intense_everywhere() {
+            assert_eq!(bulb().intensity_at(Point3D::new(100.0, 0.0, 0.0)), 1.0);
+        }
+    }
+
+    mod spot_lights {
+        use super::*;
+        use std::f64::consts::PI;
+
+        fn spot() -> Light {
+            // shining straight down, fully lit within 30 degrees, unlit beyond 45 degrees
+            Light::spot(
+                Colour::WHITE,
+                Point3D::new(0.0, 10.0, 0.0),
+                Vector3D::new(0.0, -1.0, 0.0),
+                PI / 6.0,
+                PI / 4.0,
+            )
+        }
+
+        #[test]
+        fn a_point_inside_the_inner_cone_of_a_spot_light_should_be_fully_lit() {
+            let light = spot();
+
+            assert_eq!(light.intensity_at(Point3D::ORIGIN), 1.0);
+            assert_eq!(light.intensity_at(Point3D::new(4.0, 1.5, 0.0)), 1.0);
+        }
+
+        #[test]
+        fn a_point_in_the_penumbra_of_a_spot_light_should_be_partially_lit() {
+            let light = spot();
+
+            let steep = light.intensity_at(Point3D::new(6.5, 0.0, 0.0));
+            let shallow = light.intensity_at(Point3D::new(0.0, 0.0, 9.0));
+            assert!(steep > 0.0 && steep < 1.0, "{}", steep);
+            assert!(shallow > 0.0 && shallow < 1.0, "{}", shallow);
+            assert!(steep > shallow);
+        }
+
+        #[test]
+        fn a_point_outside_the_outer_cone_of_a_spot_light_should_not_be_lit() {
+            let light = spot();
+
+            assert_eq!(light.intensity_at(Point3D::new(11.0, 0.0, 0.0)), 0.0);
+            assert_eq!(light.intensity_at(Point3D::new(0.0, 20.0, 0.0)), 0.0);
+        }
+
+        #[test]
+        fn a_spot_light_should_be_sampled_like_a_point_light() {
+            let light = spot();
+            let (mut samples, n_samples) = light.samples();
+
+            assert_eq!(n_samples, 1);
+            assert_eq!(samples.next(), Some(&Point3D::new(0.0, 10.0, 0.0)));
+            assert!(samples.next().is_none());
+        }
+    }
+
+    mod falloff {
+        use super::*;
+
+        #[test]
+        fn a_point_light_without_falloff_should_have_constant_intensity() {
+            let light = Light::point(Colour::WHITE, Point3D::ORIGIN);
+
+            assert_eq!(light.intensity_at(Point3D::new(1.0, 0.0, 0.0)), 1.0);
+            assert_eq!(light.intensity_at(Point3D::new(0.0, 1000.0, 0.0)), 1.0);
+        }
+
+        #[test]
+        fn a_point_light_with_falloff_should_scale_intensity_by_the_falloff_coefficients() {
+            let light = Light::point_with_falloff(Colour::WHITE, Point3D::ORIGIN, 1.0, 0.5, 0.25);
+
+            assert_eq!(light.intensity_at(Point3D::ORIGIN), 1.0);
+            assert_eq!(light.intensity_at(Point3D::new(0.0, 0.0, 2.0)), 1.0 / 3.0);
+            assert_eq!(light.intensity_at(Point3D::new(4.0, 0.0, 0.0)), 1.0 / 7.0);
+        }
     }
 }
@@ -1,4 +1,4 @@
-use super::{Pattern, UvPattern};
+use super::{Pattern, Texture, UvPattern};
 use crate::core::Colour;
 
 #[derive(Clone, Debug, PartialEq)]
@@ -11,7 +11,31 @@ pub struct Material {
     pub reflective: f64,
     pub transparency: f64,
     pub refractive: f64,
+    /// how much refracted rays are scattered, producing a frosted glass effect - 0 is perfectly clear,
+    /// and values around 0.1 - 0.5 are reasonably frosted. Has no effect on opaque materials
+    pub refraction_roughness: f64,
+    /// how much reflected rays are scattered, producing blurry (i.e. glossy) reflections - 0 is a perfect mirror.
+    /// Has no effect on materials which aren't reflective
+    pub roughness: f64,
     pub casts_shadow: bool,
+    /// light given off by the material itself, regardless of the lights in the scene or any shadows.
+    /// Note that this doesn't illuminate other objects
+    pub emission: Colour,
+    /// how much of each colour of light is absorbed per unit of distance travelled through a transparent material
+    /// (i.e. Beer-Lambert absorption), so thicker parts of coloured glass appear darker - black absorbs nothing, producing clear glass.
+    /// Note that light _absorbed_ by a colour leaves the opposite colour, so e.g. red glass absorbs green and blue
+    pub absorption: Colour,
+    /// a tangent-space normal map, used to add surface detail to shapes which define a tangent frame (see `with_normal_map`)
+    pub normal_map: Option<Texture>,
+    /// a greyscale height map, and how strongly its slopes tilt the normal, used to add surface detail to shapes which define
+    /// a tangent frame (see `with_bump_map`)
+    pub bump_map: Option<(Texture, f64)>,
+    /// if false, rays cast from the camera (and refracted rays following on from them) pass straight through the object,
+    /// although it still casts shadows and appears in reflections - e.g. to composite a rendered shadow onto a photograph
+    pub visible_to_camera: bool,
+    /// if false, reflected rays (and refracted rays following on from them) pass straight through the object, although it
+    /// still casts shadows and is visible to the camera
+    pub visible_in_reflections: bool,
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -32,7 +56,84 @@ impl Default for Material {
             reflective: 0.0,
             transparency: 0.0,
             refractive: 1.0,
+            refraction_roughness: 0.0,
+            roughness: 0.0,
             casts_shadow: true,
+            emission: Colour::BLACK,
+            absorption: Colour::BLACK,
+            normal_map: None,
+            bump_map: None,
+            visible_to_camera: true,
+            visible_in_reflections: true,
         }
     }
 }
+
+impl Material {
+    /// Clear glass, which mostly refracts light, but reflects more of it at glancing angles
+    pub fn glass() -> Self {
+        Material {
+            kind: MaterialKind::Solid(Colour::BLACK),
+            ambient: 0.0,
+            diffuse: 0.1,
+            specular: 1.0,
+            shininess: 300.0,
+            reflective: 0.9,
+            transparency: 0.9,
+            refractive: 1.5,
+            ..Default::default()
+        }
+    }
+
+    /// A perfect mirror, which only shows its reflections (and the highlights of lights)
+    pub fn mirror() -> Self {
+        Material {
+            kind: MaterialKind::Solid(Colour::BLACK),
+            ambient: 0.0,
+            diffuse: 0.0,
+            specular: 1.0,
+            shininess: 300.0,
+            reflective: 1.0,
+            ..Default::default()
+        }
+    }
+
+    /// A rough surface with no highlights or reflections, e.g. paper or plaster
+    pub fn matte(colour: Colour) -> Self {
+        Material {
+            kind: MaterialKind::Solid(colour),
+            specular: 0.0,
+            ..Default::default()
+        }
+    }
+
+    /// A polished metal, which is partially reflective, with sharp highlights
+    pub fn metal(colour: Colour) -> Self {
+        Material {
+            kind: MaterialKind::Solid(colour),
+            diffuse: 0.4,
+            specular: 0.9,
+            shininess: 250.0,
+            reflective: 0.6,
+            ..Default::default()
+        }
+    }
+
+    /// Perturbs the surface normal using the image, sampled using the UV coordinates of the shape:
+    /// red runs along increasing `u`, green along increasing `v`, and blue along the unperturbed normal.
+    ///
+    /// Note: currently only spheres define a tangent frame - the normal map is ignored on other shapes
+    pub fn with_normal_map(mut self, image: impl Into<Texture>) -> Self {
+        self.normal_map = Some(image.into());
+        self
+    }
+
+    /// Tilts the surface normal down the slopes of the image, treated as a greyscale height map (where brighter is higher)
+    /// sampled using the UV coordinates of the shape. Higher `strength` produces steeper bumps, and a flat image has no effect.
+    ///
+    /// Note: as with normal maps, this is ignored on shapes which don't define a tangent frame
+    pub fn with_bump_map(mut self, image: impl Into<Texture>, strength: f64) -> Self {
+        self.bump_map = Some((image.into(), strength));
+        self
+    }
+}
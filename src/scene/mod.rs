@@ -2,7 +2,9 @@ mod object;
 pub use object::{CsgOperator, Object};
 
 mod bounding_box;
-use bounding_box::BoundingBox;
+pub(crate) use bounding_box::BoundingBox;
+
+mod bvh;
 
 mod light;
 pub use light::{Light, LightSample};
@@ -11,22 +13,32 @@ mod material;
 pub use material::{Material, MaterialKind};
 
 mod pattern;
-pub use pattern::{Pattern, UvPattern};
+pub use pattern::{Pattern, UvMapping, UvPattern, WrapMode};
+
+mod sky;
+pub use sky::Sky;
+
+mod texture;
+pub use texture::{Texture, TextureCache};
 
 mod world;
-pub use world::{World, WorldSettings};
+pub(crate) use world::Counters;
+pub use world::{AoSettings, FogSettings, World, WorldSettings};
 
 mod intersection;
 pub use intersection::{HitData, Intersection, Intersections, ReflectionData};
 
-pub use shape::{cone::ConeBuilder, cylinder::CylinderBuilder};
-use shape::{cube::Cube, plane::Plane, sphere::Sphere, triangle::Triangle, Shape};
+use shape::{
+    capsule::Capsule, cube::Cube, ellipsoid::Ellipsoid, plane::Plane, quad::Quad, sphere::Sphere,
+    torus::Torus, triangle::Triangle, Shape,
+};
+pub use shape::{cone::ConeBuilder, cylinder::CylinderBuilder, disk::DiskBuilder};
 mod shape {
     use super::*;
-    use crate::core::{Normal3D, Point3D, Ray};
+    use crate::core::{Normal3D, Point3D, Ray, Vector3D};
     use std::fmt::Debug;
 
-    pub trait Shape: Debug + Sync {
+    pub trait Shape: Debug + Send + Sync {
         fn object_bounds(&self) -> BoundingBox;
 
         fn object_normal_at(&self, point: Point3D) -> Normal3D;
@@ -37,13 +49,30 @@ mod shape {
         ) -> Intersections<'parent>;
 
         fn uv_at(&self, point: Point3D) -> (f64, f64);
+
+        /// the direction in which `u` increases at the given point, used to orient normal maps -
+        /// shapes without a tangent frame (or points where it's undefined, e.g. the poles of a sphere) return `None`
+        fn object_tangent_at(&self, _point: Point3D) -> Option<Vector3D> {
+            None
+        }
+
+        /// the UV coordinates used to sample the given texture `channel` - only meaningful for shapes
+        /// which carry their own texture coordinates, so by default, every channel is identical to `uv_at`
+        fn uv_channel_at(&self, point: Point3D, _channel: usize) -> (f64, f64) {
+            self.uv_at(point)
+        }
     }
 
+    pub mod capsule;
     pub mod cone;
     pub mod cube;
     pub mod cylinder;
+    pub mod disk;
+    pub mod ellipsoid;
     pub mod plane;
+    pub mod quad;
     pub mod sphere;
+    pub mod torus;
     pub mod triangle;
 }
 
@@ -52,14 +81,22 @@ mod tests {
     use super::*;
 
     mod bounding_box_tests;
+    mod capsule_tests;
     mod cone_tests;
     mod cube_tests;
     mod cylinder_tests;
+    mod disk_tests;
+    mod ellipsoid_tests;
     mod intersection_tests;
+    mod material_tests;
     mod object_tests;
     mod pattern_tests;
     mod plane_tests;
+    mod quad_tests;
+    mod sky_tests;
     mod sphere_tests;
+    mod texture_tests;
+    mod torus_tests;
     mod triangle_tests;
     mod world_tests;
 }
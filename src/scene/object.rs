@@ -1,15 +1,23 @@
 use super::*;
-use crate::core::{Colour, Normal3D, Point3D, Ray, Transform, Vector3D, VectorMaths};
-use crate::scene::{Material, MaterialKind};
-use std::sync::atomic::{AtomicU32, Ordering};
+use crate::core::{
+    Colour, Normal3D, Point3D, Ray, Transform, TransformPath, Vector3D, VectorMaths,
+};
+use crate::scene::{Material, MaterialKind, Texture, WrapMode};
+use smallvec::SmallVec;
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::sync::Arc;
 
 #[derive(Debug)]
 pub struct Object {
     pub material: Material,
     transform: Transform,
+    /// the path from `transform` to the transform at the end of the camera shutter, if the object moves while the shutter is open
+    motion: Option<TransformPath>,
     kind: ObjectKind,
     pub(in crate::scene) bounds: BoundingBox,
     pub(in crate::scene) id: u32,
+    /// intersections further than this distance along a ray are ignored, hiding any part of the object beyond it
+    clip_distance: Option<f64>,
 }
 
 #[derive(Debug)]
@@ -21,6 +29,8 @@ enum ObjectKind {
         right: Box<Object>,
         operator: CsgOperator,
     },
+    /// geometry shared with other instances, which is moved into place by the instance's own transform
+    Instance(Arc<Object>),
 }
 
 #[derive(Debug, Copy, Clone, PartialEq)]
@@ -52,6 +62,8 @@ impl CsgOperator {
 }
 
 // if you need more than 4 billion objects, you've got bigger problems than integer overflow
+// IDs are only ever compared for equality (e.g. to check which side of a CSG was hit), never ordered, so the order objects are
+// created in - even across threads - doesn't affect rendering
 static NEXT_ID: AtomicU32 = AtomicU32::new(0);
 
 impl Object {
@@ -63,6 +75,11 @@ impl Object {
         Self::from_shape(Box::new(Plane))
     }
 
+    /// a finite `width` x `depth` rectangle lying in the XZ plane - see `Quad`
+    pub fn quad(width: f64, depth: f64) -> Self {
+        Self::from_shape(Box::new(Quad::new(width, depth)))
+    }
+
     pub fn cube() -> Self {
         Self::from_shape(Box::new(Cube))
     }
@@ -75,6 +92,26 @@ impl Object {
         ConeBuilder::new()
     }
 
+    /// a flat disk lying in the XZ plane - see `Disk`
+    pub fn disk(radius: f64) -> DiskBuilder {
+        DiskBuilder::new(radius)
+    }
+
+    /// a sphere stretched by `a`, `b`, and `c` along the X, Y, and Z axes - see `Ellipsoid`
+    pub fn ellipsoid(a: f64, b: f64, c: f64) -> Self {
+        Self::from_shape(Box::new(Ellipsoid::new(a, b, c)))
+    }
+
+    /// a torus lying flat in the XZ plane - see `Torus`
+    pub fn torus(major_radius: f64, minor_radius: f64) -> Self {
+        Self::from_shape(Box::new(Torus::new(major_radius, minor_radius)))
+    }
+
+    /// a cylinder with rounded ends, standing upright on the Y axis - see `Capsule`
+    pub fn capsule(radius: f64, height: f64) -> Self {
+        Self::from_shape(Box::new(Capsule::new(radius, height)))
+    }
+
     pub fn triangle(point1: Point3D, point2: Point3D, point3: Point3D) -> Self {
         Self::from_shape(Box::new(Triangle::new(point1, point2, point3)))
     }
@@ -92,22 +129,64 @@ impl Object {
         )))
     }
 
-    pub fn group(children: Vec<Object>) -> Self {
-        let initial_bounds = children
-            .first()
-            .map_or(BoundingBox::infinite(), |c| c.bounds);
-        let bounds = children
-            .iter()
-            .skip(1)
-            .map(|c| c.bounds)
-            .fold(initial_bounds, |acc, next| acc.expand_to_fit(&next));
+    /// a triangle (smooth, if `normals` are provided) with texture coordinates for one or more UV channels;
+    /// each element of `texture_coordinates` gives the coordinates of `point1`, `point2`, and `point3` for that channel
+    pub fn textured_triangle(
+        point1: Point3D,
+        point2: Point3D,
+        point3: Point3D,
+        normals: Option<[Normal3D; 3]>,
+        texture_coordinates: Vec<[(f64, f64); 3]>,
+    ) -> Self {
+        let triangle = match normals {
+            Some([normal1, normal2, normal3]) => {
+                Triangle::smooth(point1, point2, point3, normal1, normal2, normal3)
+            }
+            None => Triangle::new(point1, point2, point3),
+        };
+
+        Self::from_shape(Box::new(
+            triangle.with_texture_coordinates(texture_coordinates),
+        ))
+    }
+
+    /// a group of triangles, where each point after the first two forms a triangle with the two points before it;
+    /// every other triangle is flipped so that all triangles share the winding (and therefore the normal) of the first triangle
+    pub fn triangle_strip(points: Vec<Point3D>) -> Self {
+        let triangles = points
+            .windows(3)
+            .enumerate()
+            .map(|(index, window)| {
+                if index % 2 == 0 {
+                    Object::triangle(window[0], window[1], window[2])
+                } else {
+                    Object::triangle(window[1], window[0], window[2])
+                }
+            })
+            .collect();
+
+        Self::group(triangles)
+    }
 
+    /// a group of triangles, each formed by the `centre` and two consecutive points on the `rim`
+    pub fn triangle_fan(centre: Point3D, rim: Vec<Point3D>) -> Self {
+        let triangles = rim
+            .windows(2)
+            .map(|edge| Object::triangle(centre, edge[0], edge[1]))
+            .collect();
+
+        Self::group(triangles)
+    }
+
+    pub fn group(children: Vec<Object>) -> Self {
         Object {
             transform: Transform::identity(),
+            motion: None,
             material: Material::default(),
-            bounds,
+            bounds: bounds_of(&children),
             kind: ObjectKind::Group(children),
             id: Self::next_id(),
+            clip_distance: None,
         }
     }
 
@@ -125,9 +204,38 @@ impl Object {
         Self::csg(left, right, CsgOperator::Subtract)
     }
 
+    /// Places a copy of the `shared` object with the given `transform` (applied on top of the shared object's own transforms),
+    /// without cloning its geometry, e.g. to fill a scene with many copies of a large mesh.
+    ///
+    /// Note: the shared object keeps its own material, so the material of the instance is ignored, and groups can't
+    /// be re-organised (e.g. by `bvh`) once they're shared, so should be organised first
+    pub fn instance(shared: Arc<Object>, transform: Transform) -> Self {
+        // an instance of an instance is flattened, so each intersection only needs to remember a single instance
+        let (shared, transform) = match &shared.kind {
+            ObjectKind::Instance(inner) => (Arc::clone(inner), transform * shared.transform),
+            _ => (shared, transform),
+        };
+        assert!(
+            !shared.contains_instance(),
+            "instances cannot be nested within the children of a shared object"
+        );
+
+        Object {
+            transform: Transform::identity(),
+            motion: None,
+            material: Material::default(),
+            bounds: shared.bounds,
+            kind: ObjectKind::Instance(shared),
+            id: Self::next_id(),
+            clip_distance: None,
+        }
+        .transformed(transform)
+    }
+
     fn csg(left: Object, right: Object, operator: CsgOperator) -> Self {
         Object {
             transform: Transform::identity(),
+            motion: None,
             material: Material::default(),
             bounds: left.bounds.expand_to_fit(&right.bounds),
             kind: ObjectKind::Csg {
@@ -136,16 +244,19 @@ impl Object {
                 operator,
             },
             id: Self::next_id(),
+            clip_distance: None,
         }
     }
 
     pub(in crate::scene) fn from_shape(shape: Box<dyn Shape>) -> Self {
         Object {
             transform: Transform::identity(),
+            motion: None,
             material: Material::default(),
             bounds: shape.object_bounds(),
             kind: ObjectKind::Shape(shape),
             id: Self::next_id(),
+            clip_distance: None,
         }
     }
 
@@ -154,17 +265,50 @@ impl Object {
     }
 
     pub fn normal_at(&self, point: Point3D) -> Normal3D {
-        let inverted_transform = self.transform.inverse();
+        self.normal_at_time(point, 0.0)
+    }
+
+    /// as `normal_at`, but for a moving object at the given `time` (between 0 and 1) while the shutter is open
+    pub(in crate::scene) fn normal_at_time(&self, point: Point3D, time: f64) -> Normal3D {
+        let inverted_transform = self.transform_at(time).inverse();
 
         let (x, y, z, _) = inverted_transform * point;
         let object_point = Point3D::new(x, y, z);
 
         let object_normal = match &self.kind {
-            ObjectKind::Shape(shape) => shape.object_normal_at(object_point),
+            ObjectKind::Shape(shape) => {
+                let normal = shape.object_normal_at(object_point);
+                let material = &self.material;
+
+                let tangent = match (&material.normal_map, &material.bump_map) {
+                    (None, None) => None,
+                    _ => shape.object_tangent_at(object_point),
+                };
+
+                if let Some(tangent) = tangent {
+                    let uv = shape.uv_at(object_point);
+                    let normal = match &material.normal_map {
+                        Some(normal_map) => apply_normal_map(normal_map, uv, normal, tangent),
+                        None => normal,
+                    };
+
+                    match &material.bump_map {
+                        Some((bump_map, strength)) => {
+                            apply_bump_map(bump_map, *strength, uv, normal, tangent)
+                        }
+                        None => normal,
+                    }
+                } else {
+                    normal
+                }
+            }
             ObjectKind::Group(_) => unreachable!("should never need to calculate normals on Group object as rays should only intersect Shapes"),
-            ObjectKind::Csg { .. } => unreachable!("Rays cannot intersect CSGs directly")
+            ObjectKind::Csg { .. } => unreachable!("Rays cannot intersect CSGs directly"),
+            ObjectKind::Instance(_) => unreachable!("Rays intersect the shared object, rather than the instance")
         };
 
+        // the inverse transpose keeps the normal on the same side of the surface, even when the transform mirrors the object
+        // (i.e. has a negative determinant) and therefore reverses the winding of triangles, so no special handling is needed
         let (x, y, z, _) = inverted_transform.transpose() * object_normal;
         let world_normal = Vector3D::new(x, y, z);
         world_normal.normalised()
@@ -177,9 +321,28 @@ impl Object {
         eye_vector: Normal3D,
         surface_normal: Normal3D,
         light_source: &LightSample,
+    ) -> Colour {
+        self.lit_colour(
+            self.raw_colour_at_time(point, Some(surface_normal), 0.0),
+            point,
+            direct_light,
+            eye_vector,
+            surface_normal,
+            light_source,
+        )
+    }
+
+    /// as `colour_at`, but with the `material_colour` at the `point` already known, e.g. for a moving object, or an instance
+    pub(in crate::scene) fn lit_colour(
+        &self,
+        material_colour: Colour,
+        point: Point3D,
+        direct_light: Colour,
+        eye_vector: Normal3D,
+        surface_normal: Normal3D,
+        light_source: &LightSample,
     ) -> Colour {
         let material = &self.material;
-        let material_colour = self.raw_colour_at(point);
         let ambient = material_colour * light_source.colour * material.ambient;
 
         // i.e. is in shadow
@@ -215,15 +378,34 @@ impl Object {
     ///
     /// Intended for use by transparency/shadow calculations
     pub fn raw_colour_at(&self, point: Point3D) -> Colour {
-        let object_point = {
-            let inverse = self.transform.inverse();
+        self.raw_colour_at_time(point, None, 0.0)
+    }
 
-            let (x, y, z, _) = inverse * point;
+    /// as `raw_colour_at`, but for a moving object at the given `time` (between 0 and 1) while the shutter is open.
+    ///
+    /// The surface `normal` (in world space) at the `point`, if known, stops anti-aliased patterns from filtering along axes
+    /// which don't change across the surface - see `Pattern::with_anti_aliasing`
+    pub(in crate::scene) fn raw_colour_at_time(
+        &self,
+        point: Point3D,
+        normal: Option<Normal3D>,
+        time: f64,
+    ) -> Colour {
+        let transform = self.transform_at(time);
+        let object_point = {
+            let (x, y, z, _) = transform.inverse() * point;
             Point3D::new(x, y, z)
         };
+        let normal = normal.map(Vector3D::from);
 
         match &self.material.kind {
-            MaterialKind::Pattern(pattern) => pattern.colour_at(object_point),
+            MaterialKind::Pattern(pattern) if pattern.is_world_space() => {
+                pattern.colour_on_surface(point, normal)
+            }
+            MaterialKind::Pattern(pattern) => pattern.colour_on_surface(
+                object_point,
+                normal.map(|normal| transform.inverse_transform_normal(normal)),
+            ),
             MaterialKind::Solid(colour) => *colour,
             MaterialKind::Uv(uv_pattern) => {
                 let uv = match &self.kind {
@@ -233,10 +415,11 @@ impl Object {
                         let inverse = uv_pattern.transform.inverse();
 
                         let (x, y, z, _) = inverse * object_point;
-                        shape.uv_at(Point3D::new(x, y, z))
+                        shape.uv_channel_at(Point3D::new(x, y, z), uv_pattern.channel)
                     }
                     ObjectKind::Group(_) => panic!("cannot UV map a group"),
                     ObjectKind::Csg { .. } => panic!("cannot UV map a CSG"),
+                    ObjectKind::Instance(_) => panic!("cannot UV map an instance"),
                 };
 
                 uv_pattern.colour_at(uv)
@@ -245,35 +428,102 @@ impl Object {
     }
 
     pub fn intersect(&self, with: &Ray) -> Intersections {
+        self.intersect_counted(with, None, None)
+    }
+
+    /// As `intersect`, but adds every bounding box tested to `bbox_tests`, if provided.
+    ///
+    /// If `nearest_beyond` is `Some((last, bias))`, groups check their children nearest-first (by the distance to their
+    /// bounding boxes), and skip any children which are entirely beyond the nearest hit found so far, or entirely behind
+    /// the ray. Only intersections which `Intersections::hit_beyond(last, bias)` could return count as hits, so that a
+    /// skipped hit on the `last` object never hides the hit beyond it.
+    /// Every intersection up to and including the nearest hit is still included, which is all that's needed to shade the hit
+    /// (including tracking refractive indices), but not e.g. to find every object between a point and a light
+    pub(in crate::scene) fn intersect_counted(
+        &self,
+        with: &Ray,
+        nearest_beyond: Option<(Option<u32>, f64)>,
+        bbox_tests: Option<&AtomicU64>,
+    ) -> Intersections<'_> {
+        if let Some(counter) = bbox_tests {
+            counter.fetch_add(1, Ordering::Relaxed);
+        }
+
         if !self.bounds.intersected_by(&with) {
             return Intersections::empty();
         }
 
-        match &self.kind {
+        self.intersect_within_bounds(with, nearest_beyond, bbox_tests)
+    }
+
+    /// As `intersect_counted`, for a ray already known to intersect the bounds of this object
+    fn intersect_within_bounds(
+        &self,
+        with: &Ray,
+        nearest_beyond: Option<(Option<u32>, f64)>,
+        bbox_tests: Option<&AtomicU64>,
+    ) -> Intersections<'_> {
+        let intersections = match &self.kind {
             ObjectKind::Shape(shape) => {
-                let ray_transform = self.transform.inverse();
+                let ray_transform = self.transform_at(with.time).inverse();
 
                 let transformed = with.transformed(&ray_transform);
                 shape.object_intersect(&self, transformed)
             }
-            ObjectKind::Group(children) => children
-                .iter()
-                .map(|child| child.intersect(with))
-                .fold(Intersections::empty(), Intersections::join),
+            ObjectKind::Group(children) => match nearest_beyond {
+                Some((last, bias)) => {
+                    let mut by_distance = children
+                        .iter()
+                        .filter_map(|child| {
+                            if let Some(counter) = bbox_tests {
+                                counter.fetch_add(1, Ordering::Relaxed);
+                            }
+                            Some((child.bounds.intersection_t(with)?, child))
+                        })
+                        .collect::<SmallVec<[_; 4]>>();
+                    by_distance.sort_unstable_by(|(first, _), (second, _)| {
+                        f64::partial_cmp(first, second).expect("a `t` value should never be NaN")
+                    });
+
+                    let mut nearest_hit = f64::INFINITY;
+                    let mut intersections = Intersections::empty();
+                    for (entry_t, child) in by_distance {
+                        if entry_t > nearest_hit {
+                            break;
+                        }
+
+                        // the bounds of each child have already been tested (and counted) while sorting them
+                        let child_intersections =
+                            child.intersect_within_bounds(with, nearest_beyond, bbox_tests);
+                        nearest_hit = child_intersections
+                            .iter()
+                            .filter(|intersection| intersection.is_hit_beyond(last, bias))
+                            .map(|intersection| intersection.t)
+                            .fold(nearest_hit, f64::min);
+                        intersections.append(child_intersections);
+                    }
+
+                    intersections
+                }
+                None => children
+                    .iter()
+                    .map(|child| child.intersect_counted(with, None, bbox_tests))
+                    .fold(Intersections::empty(), Intersections::join),
+            },
             ObjectKind::Csg {
                 left: first,
                 right: second,
                 operator,
             } => {
-                let first_intersections = first.intersect(&with);
-                let second_intersections = second.intersect(&with);
+                let first_intersections = first.intersect_counted(&with, None, bbox_tests);
+                let second_intersections = second.intersect_counted(&with, None, bbox_tests);
 
                 let intersections = first_intersections.join(second_intersections);
 
                 let (filtered, ..) = intersections.into_iter().fold(
                     (Intersections::empty(), false, false),
                     |(mut out, in_first, in_second), intersection| {
-                        let hit_first = first.contains(intersection.with.id);
+                        let hit_first = first.contains(&intersection);
 
                         if operator.is_intersection(hit_first, in_first, in_second) {
                             out.push(intersection)
@@ -289,6 +539,25 @@ impl Object {
 
                 filtered
             }
+            ObjectKind::Instance(shared) => {
+                let ray_transform = self.transform_at(with.time).inverse();
+
+                let transformed = with.transformed(&ray_transform);
+                let mut intersections =
+                    shared.intersect_counted(&transformed, nearest_beyond, bbox_tests);
+                intersections
+                    .0
+                    .iter_mut()
+                    .for_each(|intersection| intersection.instance = Some(self));
+
+                intersections
+            }
+        };
+
+        if let Some(distance) = self.clip_distance {
+            intersections.clipped_beyond(distance)
+        } else {
+            intersections
         }
     }
 
@@ -300,7 +569,7 @@ impl Object {
     /// dependent on which sub-bounding box contains it. Therefore, groups may be larger than `threshold`.
     pub fn optimised(mut self, threshold: usize) -> Self {
         self.kind = match self.kind {
-            shape @ ObjectKind::Shape(_) => shape,
+            kind @ (ObjectKind::Shape(_) | ObjectKind::Instance(_)) => kind,
             ObjectKind::Group(children) => {
                 let children = if children.len() >= threshold && children.len() > 1 {
                     let (left, right) = self.bounds.split();
@@ -353,6 +622,47 @@ impl Object {
         self
     }
 
+    /// Re-organises Group structures into a bounding volume hierarchy, where each group contains either no more than
+    /// `leaf_size` shapes, or exactly two sub-groups, split along the axis in which the children are most spread out.
+    ///
+    /// Unlike `optimised`, every child ends up in a sub-group, so the number of bounding boxes tested by a ray grows with
+    /// the logarithm of the number of children, rather than linearly. Nested groups are rebuilt from the inside out,
+    /// but not flattened, as their transforms and materials have already been applied to their children.
+    pub fn bvh(mut self, leaf_size: usize) -> Self {
+        self.kind = match self.kind {
+            kind @ (ObjectKind::Shape(_) | ObjectKind::Instance(_)) => kind,
+            ObjectKind::Group(children) => {
+                let children = children
+                    .into_iter()
+                    .map(|child| child.bvh(leaf_size))
+                    .collect();
+
+                ObjectKind::Group(bvh::partition(children, leaf_size))
+            }
+            ObjectKind::Csg {
+                left,
+                right,
+                operator,
+            } => ObjectKind::Csg {
+                left: Box::new(left.bvh(leaf_size)),
+                right: Box::new(right.bvh(leaf_size)),
+                operator,
+            },
+        };
+
+        self
+    }
+
+    /// Hides any part of this object (including the children of groups and CSGs) further than `distance` along a ray,
+    /// e.g. for reveal effects.
+    ///
+    /// Note: the distance is measured from the origin of each ray, which is the camera for primary rays, but the surface
+    /// being shaded for reflections, refractions, and shadows
+    pub fn with_clip_distance(mut self, distance: f64) -> Self {
+        self.clip_distance = Some(distance);
+        self
+    }
+
     pub fn with_material(mut self, material: Material) -> Self {
         self.apply_material(material);
         self
@@ -396,20 +706,241 @@ impl Object {
         }
 
         self.transform = transform * self.transform;
+        self.motion = self
+            .motion
+            .map(|path| TransformPath::new(self.transform, transform * path.end()));
         self.bounds = self.bounds.transformed(transform);
     }
 
+    /// Moves this object (or every child of a group or CSG) while the camera shutter is open, blurring it along the path
+    /// it takes. The object starts with its current transform, and ends with `movement` applied on top of it.
+    ///
+    /// Note: the translation, scale, and rotation are interpolated separately (see `TransformPath`), so an object rotating
+    /// on the spot keeps its shape, but an object orbiting a distant point moves in a straight line, rather than an arc
+    pub fn with_motion(mut self, movement: Transform) -> Self {
+        self.apply_motion(movement);
+        self
+    }
+
+    fn apply_motion(&mut self, movement: Transform) {
+        match &mut self.kind {
+            ObjectKind::Shape(shape) => {
+                let path = motion_to(self.transform, self.motion, movement);
+                self.bounds = bounds_along(&shape.object_bounds(), &path);
+                self.motion = Some(path);
+            }
+            ObjectKind::Group(children) => {
+                children
+                    .iter_mut()
+                    .for_each(|child| child.apply_motion(movement));
+                self.bounds = bounds_of(children);
+            }
+            ObjectKind::Csg { left, right, .. } => {
+                left.apply_motion(movement);
+                right.apply_motion(movement);
+                self.bounds = left.bounds.expand_to_fit(&right.bounds);
+            }
+            ObjectKind::Instance(shared) => {
+                let path = motion_to(self.transform, self.motion, movement);
+                self.bounds = bounds_along(&shared.bounds, &path);
+                self.motion = Some(path);
+            }
+        }
+    }
+
+    /// converts a world space `point` into the space of the object shared by this instance, at the given `time`
+    pub(in crate::scene) fn to_shared_space(&self, point: Point3D, time: f64) -> Point3D {
+        let (x, y, z, _) = self.transform_at(time).inverse() * point;
+        Point3D::new(x, y, z)
+    }
+
+    /// converts a `normal` on the object shared by this instance into world space, at the given `time`
+    pub(in crate::scene) fn normal_from_shared_space(
+        &self,
+        normal: Normal3D,
+        time: f64,
+    ) -> Normal3D {
+        let (x, y, z, _) = self.transform_at(time).inverse().transpose() * normal;
+        Vector3D::new(x, y, z).normalised()
+    }
+
+    /// the transform at the given `time`, where 0 is the moment the shutter opens and 1 is the moment it closes
+    fn transform_at(&self, time: f64) -> Transform {
+        match &self.motion {
+            Some(path) => path.at(time),
+            None => self.transform,
+        }
+    }
+
     pub fn id(&self) -> u32 {
         self.id
     }
 
-    fn contains(&self, id: u32) -> bool {
+    /// Detaches the object with the given `id` from this group, or any nested group, shrinking the bounds of the groups
+    /// it was removed from.
+    ///
+    /// Note: the children of CSGs cannot be removed, as this would change the shape of the CSG
+    pub(in crate::scene) fn remove_child(&mut self, id: u32) -> Option<Object> {
+        if let ObjectKind::Group(children) = &mut self.kind {
+            let removed = if let Some(index) = children.iter().position(|child| child.id == id) {
+                Some(children.remove(index))
+            } else {
+                children.iter_mut().find_map(|child| child.remove_child(id))
+            };
+
+            if removed.is_some() {
+                self.bounds = bounds_of(children);
+            }
+
+            removed
+        } else {
+            None
+        }
+    }
+
+    /// The `(min, max)` corners of the world-space box containing the whole object, including all of its children, and the
+    /// full path of its motion (if it's moving). Unbounded objects (e.g. planes) have huge (but finite) corners along any unbounded axes
+    pub fn bounds_world(&self) -> (Point3D, Point3D) {
+        (self.bounds.min(), self.bounds.max())
+    }
+
+    /// the bounds of this object, followed by the bounds of every group, CSG, and shape nested inside it, along with how deeply
+    /// each one is nested (where this object is 0)
+    pub(crate) fn nested_bounds(&self) -> Vec<(usize, BoundingBox)> {
+        let nested = match &self.kind {
+            // the shared object's bounds aren't in world space, so can't be drawn as they are
+            ObjectKind::Shape(_) | ObjectKind::Instance(_) => vec![],
+            ObjectKind::Group(children) => children.iter().collect(),
+            ObjectKind::Csg { left, right, .. } => vec![left.as_ref(), right.as_ref()],
+        };
+
+        let mut bounds = vec![(0, self.bounds)];
+        for child in nested {
+            bounds.extend(
+                child
+                    .nested_bounds()
+                    .into_iter()
+                    .map(|(depth, child_bounds)| (depth + 1, child_bounds)),
+            );
+        }
+
+        bounds
+    }
+
+    /// whether the `intersection` is with this object (or one of its children).
+    ///
+    /// Instances can't be nested, and the instance is only recorded once the shared object has been intersected, so any
+    /// intersection through an instance must belong to that instance, rather than to the shared object, or another instance of it
+    fn contains(&self, intersection: &Intersection) -> bool {
         match &self.kind {
-            ObjectKind::Shape(_) => self.id == id,
-            ObjectKind::Group(children) => children.iter().any(|child| child.contains(id)),
-            ObjectKind::Csg { left, right, .. } => left.contains(id) || right.contains(id),
+            ObjectKind::Shape(_) => {
+                intersection.instance.is_none() && self.id == intersection.with.id
+            }
+            ObjectKind::Group(children) => {
+                children.iter().any(|child| child.contains(intersection))
+            }
+            ObjectKind::Csg { left, right, .. } => {
+                left.contains(intersection) || right.contains(intersection)
+            }
+            ObjectKind::Instance(_) => intersection.instance.map(Object::id) == Some(self.id),
         }
     }
+
+    fn contains_instance(&self) -> bool {
+        match &self.kind {
+            ObjectKind::Shape(_) => false,
+            ObjectKind::Group(children) => children.iter().any(Object::contains_instance),
+            ObjectKind::Csg { left, right, .. } => {
+                left.contains_instance() || right.contains_instance()
+            }
+            ObjectKind::Instance(_) => true,
+        }
+    }
+}
+
+/// the path from the `transform` to the end of the existing `motion` (if any), with `movement` applied on top
+fn motion_to(
+    transform: Transform,
+    motion: Option<TransformPath>,
+    movement: Transform,
+) -> TransformPath {
+    let end = motion.map_or(transform, |path| path.end());
+
+    TransformPath::new(transform, movement * end)
+}
+
+/// The box containing `object_bounds` at every point along the `path`.
+///
+/// Rotations move the corners of the box around arcs, which can bulge beyond the boxes at either end of the path, so the
+/// path is sampled at several points along the way - the arcs between samples only bulge beyond the box by around 0.1% of
+/// the size of the object, for half a turn
+fn bounds_along(object_bounds: &BoundingBox, path: &TransformPath) -> BoundingBox {
+    const STEPS: usize = 32;
+
+    (1..=STEPS).fold(object_bounds.transformed(path.start()), |bounds, step| {
+        let t = step as f64 / STEPS as f64;
+        bounds.expand_to_fit(&object_bounds.transformed(path.at(t)))
+    })
+}
+
+/// the smallest bounding box containing all of the `children` - an empty group is treated as infinitely large
+fn bounds_of(children: &[Object]) -> BoundingBox {
+    let initial_bounds = children
+        .first()
+        .map_or(BoundingBox::infinite(), |c| c.bounds);
+
+    children
+        .iter()
+        .skip(1)
+        .map(|c| c.bounds)
+        .fold(initial_bounds, |acc, next| acc.expand_to_fit(&next))
+}
+
+/// perturbs the object-space `normal` by the tangent-space normal map at the given UV coordinates
+fn apply_normal_map(
+    normal_map: &Texture,
+    (u, v): (f64, f64),
+    normal: Normal3D,
+    tangent: Vector3D,
+) -> Normal3D {
+    let sample = texture::sample_bilinear(&normal_map.image(), u, v, WrapMode::Repeat);
+    // 128 is treated as zero (rather than 127.5), so a flat map of (128, 128, 255) leaves the normal unchanged
+    let decode = |component: f64| (component * 255.0 - 128.0) / 127.0;
+
+    // the tangent may not be perpendicular to the normal (e.g. on a transformed sphere), so remove any overlap
+    let tangent = (tangent - normal * tangent.dot(normal)).normalised();
+    let bitangent = tangent.cross(normal);
+
+    (tangent * decode(sample.red())
+        + bitangent * decode(sample.green())
+        + normal * decode(sample.blue()))
+    .normalised()
+}
+
+/// tilts the object-space `normal` down the slope of the height map at the given UV coordinates
+fn apply_bump_map(
+    bump_map: &Texture,
+    strength: f64,
+    (u, v): (f64, f64),
+    normal: Normal3D,
+    tangent: Vector3D,
+) -> Normal3D {
+    let image = bump_map.image();
+    let height_at = |(u, v): (f64, f64)| {
+        let sample = texture::sample_bilinear(&image, u, v, WrapMode::Repeat);
+        (sample.red() + sample.green() + sample.blue()) / 3.0
+    };
+
+    // the distance between neighbouring pixels, in UV coordinates
+    let du = 1.0 / (image.width().max(2) - 1) as f64;
+    let dv = 1.0 / (image.height().max(2) - 1) as f64;
+    let slope_u = (height_at((u + du, v)) - height_at((u - du, v))) / 2.0;
+    let slope_v = (height_at((u, v + dv)) - height_at((u, v - dv))) / 2.0;
+
+    let tangent = (tangent - normal * tangent.dot(normal)).normalised();
+    let bitangent = tangent.cross(normal);
+
+    (normal - tangent * (slope_u * strength) - bitangent * (slope_v * strength)).normalised()
 }
 
 #[cfg(test)]
@@ -422,6 +953,25 @@ impl Object {
         }
     }
 
+    /// see `intersect_counted`
+    pub fn intersect_nearest(&self, with: &Ray) -> Intersections<'_> {
+        self.intersect_nearest_beyond(with, None, crate::core::EPSILON)
+    }
+
+    /// see `intersect_counted`
+    pub fn intersect_nearest_beyond(
+        &self,
+        with: &Ray,
+        last: Option<u32>,
+        bias: f64,
+    ) -> Intersections<'_> {
+        self.intersect_counted(with, Some((last, bias)), None)
+    }
+
+    pub fn is_shape(&self) -> bool {
+        matches!(self.kind, ObjectKind::Shape(_))
+    }
+
     pub fn shape(&self) -> &Box<dyn Shape> {
         if let ObjectKind::Shape(shape) = &self.kind {
             shape
@@ -1,34 +1,120 @@
 use std::num::NonZeroUsize;
 use std::ops::RangeInclusive;
-use std::sync::Arc;
-
-use image::RgbImage;
 
 use crate::core::Colour;
 use crate::core::F64Ext;
 use crate::core::Point3D;
 use crate::core::Transform;
+use crate::core::Vector3D;
+use crate::core::VectorMaths;
+use crate::scene::shape::{cylinder::Cylinder, Shape};
+use crate::scene::texture::sample_bilinear;
+use crate::scene::{Cube, Plane, Sphere, Texture};
 
 use super::pattern::Kind::{Checkers, Gradient, Ring, Striped};
 
+pub(super) mod noise;
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct Pattern {
     kind: Kind,
     transform: Transform,
+    /// whether the pattern is anchored to the world, rather than moving with the object it's applied to
+    world_space: bool,
+    /// if set, stripes and checkers are averaged over a box this wide (in pattern space) around each point
+    filter_width: Option<f64>,
 }
 
 #[derive(Clone, Debug, PartialEq)]
 enum Kind {
     Striped(Colour, Colour),
-    Gradient { from: Colour, delta: Colour },
+    Gradient {
+        from: Colour,
+        delta: Colour,
+    },
+    RepeatingGradient {
+        from: Colour,
+        delta: Colour,
+    },
     Ring(Colour, Colour),
-    Checkers(Colour, Colour),
+    /// the third value is the size of each cell along the (x, y, z) axes
+    Checkers(Colour, Colour, (f64, f64, f64)),
+    UvMapped {
+        pattern: Box<UvPattern>,
+        mapping: UvMapping,
+    },
+    Noise {
+        base: Box<Pattern>,
+        scale: f64,
+        octaves: u8,
+    },
+    Blend {
+        a: Box<Pattern>,
+        b: Box<Pattern>,
+    },
+    Nested {
+        primary: Box<Pattern>,
+        secondary: Box<Pattern>,
+    },
+}
+
+/// Projects 3D points onto a 2D UV pattern the same way as the equivalent shape unwraps its surface,
+/// allowing a UV pattern to be applied to any object via a `Pattern`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum UvMapping {
+    Planar,
+    Spherical,
+    Cylindrical,
+    Cubic,
+}
+
+impl UvMapping {
+    fn uv_at(&self, point: Point3D) -> (f64, f64) {
+        match self {
+            UvMapping::Planar => Plane.uv_at(point),
+            UvMapping::Spherical => Sphere.uv_at(point),
+            UvMapping::Cylindrical => Cylinder::INFINITE.uv_at(point),
+            UvMapping::Cubic => Cube.uv_at(point),
+        }
+    }
+}
+
+/// How UV coordinates outside of `0..1` are mapped onto an image
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum WrapMode {
+    /// uses the pixels at the edge of the image
+    Clamp,
+    /// tiles the image
+    Repeat,
+    /// tiles the image, flipping every other tile, so the edges of adjacent tiles match
+    Mirror,
+}
+
+impl WrapMode {
+    /// maps `t` into the range `0..=1`
+    pub(super) fn apply(&self, t: f64) -> f64 {
+        match self {
+            WrapMode::Clamp => t.clamp(0.0, 1.0),
+            WrapMode::Repeat => t.rem_euclid(1.0),
+            WrapMode::Mirror => {
+                let t = t.rem_euclid(2.0);
+                if t > 1.0 {
+                    2.0 - t
+                } else {
+                    t
+                }
+            }
+        }
+    }
 }
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct UvPattern {
     kind: UvPatternKind,
     pub transform: Transform,
+    /// which set of texture coordinates to sample, for shapes with more than one (e.g. mesh triangles);
+    /// shapes without texture coordinates map every channel identically
+    pub(super) channel: usize,
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -46,7 +132,7 @@ pub(super) enum UvPatternKind {
         bottom_left: Colour,
         bottom_right: Colour,
     },
-    Image(Arc<RgbImage>),
+    Image(Texture, WrapMode),
     MultiFace(Vec<(RangeInclusive<f64>, RangeInclusive<f64>, UvPattern)>),
 }
 
@@ -55,6 +141,8 @@ impl Pattern {
         Pattern {
             kind: Striped(primary, secondary),
             transform: Transform::identity(),
+            world_space: false,
+            filter_width: None,
         }
     }
 
@@ -65,6 +153,22 @@ impl Pattern {
                 delta: to - from,
             },
             transform: Transform::identity(),
+            world_space: false,
+            filter_width: None,
+        }
+    }
+
+    /// as `gradient`, but reverses direction every unit along x (i.e. runs `from` -> `to` -> `from`), so adjacent
+    /// repeats blend together rather than jumping back to `from`
+    pub fn gradient_repeating(from: Colour, to: Colour) -> Self {
+        Pattern {
+            kind: Kind::RepeatingGradient {
+                from,
+                delta: to - from,
+            },
+            transform: Transform::identity(),
+            world_space: false,
+            filter_width: None,
         }
     }
 
@@ -72,13 +176,84 @@ impl Pattern {
         Pattern {
             kind: Ring(primary, secondary),
             transform: Transform::identity(),
+            world_space: false,
+            filter_width: None,
         }
     }
 
     pub const fn checkers(primary: Colour, secondary: Colour) -> Self {
+        Self::checkers_scaled(primary, secondary, 1.0, 1.0, 1.0)
+    }
+
+    /// a checkers pattern where each cell is `sx` by `sy` by `sz` units, rather than a unit cube
+    pub const fn checkers_scaled(
+        primary: Colour,
+        secondary: Colour,
+        sx: f64,
+        sy: f64,
+        sz: f64,
+    ) -> Self {
+        Pattern {
+            kind: Checkers(primary, secondary, (sx, sy, sz)),
+            transform: Transform::identity(),
+            world_space: false,
+            filter_width: None,
+        }
+    }
+
+    /// a 2D pattern, where each point is mapped to a UV coordinate using the `mapping` rather than the UV mapping of the object
+    pub fn uv_mapped(pattern: UvPattern, mapping: UvMapping) -> Self {
+        Pattern {
+            kind: Kind::UvMapped {
+                pattern: Box::new(pattern),
+                mapping,
+            },
+            transform: Transform::identity(),
+            world_space: false,
+            filter_width: None,
+        }
+    }
+
+    /// Perturbs each point by up to `scale` in each direction before sampling the `base` pattern, using fractal noise
+    /// with the given number of `octaves` - e.g. turning stripes into marble.
+    ///
+    /// More octaves add finer detail, at the cost of taking longer to calculate
+    pub fn noise(base: Pattern, scale: f64, octaves: u8) -> Self {
         Pattern {
-            kind: Checkers(primary, secondary),
+            kind: Kind::Noise {
+                base: Box::new(base),
+                scale,
+                octaves,
+            },
             transform: Transform::identity(),
+            world_space: false,
+            filter_width: None,
+        }
+    }
+
+    /// an even mix of two patterns - each pattern's own transform still applies
+    pub fn blend(a: Pattern, b: Pattern) -> Self {
+        Pattern {
+            kind: Kind::Blend {
+                a: Box::new(a),
+                b: Box::new(b),
+            },
+            transform: Transform::identity(),
+            world_space: false,
+            filter_width: None,
+        }
+    }
+
+    /// a checkers pattern, where each cube is filled with a pattern rather than a single colour
+    pub fn nested(primary: Pattern, secondary: Pattern) -> Self {
+        Pattern {
+            kind: Kind::Nested {
+                primary: Box::new(primary),
+                secondary: Box::new(secondary),
+            },
+            transform: Transform::identity(),
+            world_space: false,
+            filter_width: None,
         }
     }
 
@@ -87,12 +262,113 @@ impl Pattern {
         self
     }
 
+    /// Anchors the pattern to the world, rather than the object it's applied to, such that moving the object moves it across
+    /// the pattern (e.g. a floor which lines up with the rest of the scene, wherever it's placed).
+    ///
+    /// Note: only applies to the top-level pattern of a material - any nested patterns follow their parent
+    pub fn world_space(mut self) -> Self {
+        self.world_space = true;
+        self
+    }
+
+    /// Softens the edges of stripes and checkers, by averaging the pattern over a box `width` units wide (in pattern space)
+    /// around each point, such that points on (or near) a boundary blend the two colours. This reduces shimmering on
+    /// distant patterns far more cheaply than casting more rays.
+    ///
+    /// The `width` should be roughly the size of a pixel, when projected onto the pattern - larger values blur the pattern.
+    /// Other kinds of pattern are unaffected
+    pub fn with_anti_aliasing(mut self, width: f64) -> Self {
+        self.filter_width = Some(width);
+        self
+    }
+
+    pub(in crate::scene) fn is_world_space(&self) -> bool {
+        self.world_space
+    }
+
     pub fn colour_at(&self, object_point: Point3D) -> Colour {
+        self.colour_on_surface(object_point, None)
+    }
+
+    /// as `colour_at`, but for a point on a surface with the (object space) `normal`, if known.
+    ///
+    /// Anti-aliased patterns only filter along the axes which change across the surface, so e.g. a checkered floor lying on
+    /// a boundary between two rows of cells isn't blurred into a single colour. Without a normal, every axis is filtered
+    pub(in crate::scene) fn colour_on_surface(
+        &self,
+        object_point: Point3D,
+        normal: Option<Vector3D>,
+    ) -> Colour {
         use Kind::*;
 
         let inverse = self.transform.inverse();
 
         let (x, y, z, _) = inverse * object_point;
+        let normal = normal.map(|normal| self.transform.inverse_transform_normal(normal));
+
+        if let UvMapped { pattern, mapping } = &self.kind {
+            let (x, y, z, _) = pattern.transform.inverse() * Point3D::new(x, y, z);
+            return pattern.colour_at(mapping.uv_at(Point3D::new(x, y, z)));
+        }
+
+        if let Noise {
+            base,
+            scale,
+            octaves,
+        } = &self.kind
+        {
+            let point = Point3D::new(x, y, z);
+            // offset the samples for each axis, so they aren't all perturbed by the same amount
+            let dx = noise::fractal(point, *octaves);
+            let dy = noise::fractal(point + Vector3D::new(31.4, 15.9, 26.5), *octaves);
+            let dz = noise::fractal(point + Vector3D::new(-35.8, 97.9, -32.3), *octaves);
+
+            return base.colour_on_surface(point + Vector3D::new(dx, dy, dz) * *scale, normal);
+        }
+
+        if let Blend { a, b } = &self.kind {
+            let point = Point3D::new(x, y, z);
+            return a
+                .colour_on_surface(point, normal)
+                .average(b.colour_on_surface(point, normal));
+        }
+
+        let point = Point3D::new(x, y, z);
+        // the size of a cell along each axis
+        let (sx, sy, sz) = match &self.kind {
+            Checkers(_, _, size) => *size,
+            _ => (1.0, 1.0, 1.0),
+        };
+        let (x, y, z) = (x / sx, y / sy, z / sz);
+
+        if let Some(width) = self.filter_width {
+            // the filter covers a square on the surface, which spans less of an axis the closer the normal is to it
+            let (wx, wy, wz) = match normal.map(|normal| normal.normalised()) {
+                Some(normal) => (
+                    width * (1.0 - normal.x().powi(2)).max(0.0).sqrt(),
+                    width * (1.0 - normal.y().powi(2)).max(0.0).sqrt(),
+                    width * (1.0 - normal.z().powi(2)).max(0.0).sqrt(),
+                ),
+                None => (width, width, width),
+            };
+            // the widths are in pattern space, but the coordinates are in cells
+            let (wx, wy, wz) = (wx / sx, wy / sy, wz / sz);
+
+            match &self.kind {
+                Striped(primary, secondary) => {
+                    let weight = (filtered_square_wave(x, wx) + 1.0) / 2.0;
+                    return secondary.lerp(primary, weight);
+                }
+                Checkers(primary, secondary, _) => {
+                    // the box filter is separable, and the checkers are the product of a square wave along each axis
+                    let wave = filtered_square_wave(x, wx)
+                        * filtered_square_wave(y, wy)
+                        * filtered_square_wave(z, wz);
+                    return secondary.lerp(primary, (wave + 1.0) / 2.0);
+                }
+                _ => (),
+            }
+        }
 
         let (x, y, z) = (nudge(x), nudge(y), nudge(z));
 
@@ -100,10 +376,23 @@ impl Pattern {
             Striped(primary, _) if x.floor() % 2.0 == 0.0 => *primary,
             Striped(_, secondary) => *secondary,
             Gradient { from, delta } => from + &(delta * object_point.x().fract()),
+            RepeatingGradient { from, delta } => {
+                // a triangle wave, rising from 0 to 1 over one unit, then falling back to 0 over the next - along the
+                // same axis as a plain gradient
+                let t = 1.0 - (object_point.x().rem_euclid(2.0) - 1.0).abs();
+                from + &(delta * t)
+            }
             Ring(primary, _) if (x.powi(2) + z.powi(2)).sqrt().floor() % 2.0 == 0.0 => *primary,
             Ring(_, secondary) => *secondary,
-            Checkers(primary, _) if (x.floor() + y.floor() + z.floor()) % 2.0 == 0.0 => *primary,
-            Checkers(_, secondary) => *secondary,
+            Checkers(primary, ..) if (x.floor() + y.floor() + z.floor()) % 2.0 == 0.0 => *primary,
+            Checkers(_, secondary, _) => *secondary,
+            Nested { primary, .. } if (x.floor() + y.floor() + z.floor()) % 2.0 == 0.0 => {
+                primary.colour_on_surface(point, normal)
+            }
+            Nested { secondary, .. } => secondary.colour_on_surface(point, normal),
+            UvMapped { .. } => unreachable!("UV mapped patterns are sampled before nudging"),
+            Noise { .. } => unreachable!("noise patterns are sampled before nudging"),
+            Blend { .. } => unreachable!("blended patterns are sampled before nudging"),
         }
     }
 }
@@ -123,6 +412,7 @@ impl UvPattern {
                 height: height.get(),
             },
             transform: Transform::identity(),
+            channel: 0,
         }
     }
 
@@ -144,6 +434,7 @@ impl UvPattern {
                 (1.0..=2.0, 3.0..=4.0, left),
             ]),
             transform: Transform::identity(),
+            channel: 0,
         }
     }
 
@@ -155,6 +446,7 @@ impl UvPattern {
                 (2.0..=3.0, 0.0..=1.0, bottom),
             ]),
             transform: Transform::identity(),
+            channel: 0,
         }
     }
 
@@ -176,13 +468,20 @@ impl UvPattern {
                 bottom_right,
             },
             transform: Transform::identity(),
+            channel: 0,
         }
     }
 
-    pub fn image(img: Arc<RgbImage>) -> Self {
+    /// an image, which is repeated outside of `0..1` - see `image_wrapped`
+    pub fn image(texture: Texture) -> Self {
+        Self::image_wrapped(texture, WrapMode::Repeat)
+    }
+
+    pub fn image_wrapped(texture: Texture, wrap: WrapMode) -> Self {
         UvPattern {
-            kind: UvPatternKind::Image(img),
+            kind: UvPatternKind::Image(texture, wrap),
             transform: Transform::identity(),
+            channel: 0,
         }
     }
 
@@ -190,6 +489,11 @@ impl UvPattern {
         self.transform = transform;
         self
     }
+
+    pub fn with_uv_channel(mut self, channel: usize) -> Self {
+        self.channel = channel;
+        self
+    }
 }
 
 impl UvPattern {
@@ -231,19 +535,7 @@ impl UvPattern {
                 *bottom_right
             }
             UvPatternKind::AlignmentCheck { main, .. } => *main,
-            UvPatternKind::Image(img) => {
-                let v = 1.0 - v;
-
-                let x = u.rem_euclid(1.0) * (img.width() - 1) as f64;
-                let y = v.rem_euclid(1.0) * (img.height() - 1) as f64;
-
-                let pixel = img.get_pixel(x.round() as _, y.round() as _);
-                Colour::new(
-                    pixel.0[0] as f64 / 255.0,
-                    pixel.0[1] as f64 / 255.0,
-                    pixel.0[2] as f64 / 255.0,
-                )
-            }
+            UvPatternKind::Image(texture, wrap) => sample_bilinear(&texture.image(), u, v, *wrap),
             UvPatternKind::MultiFace(faces) => faces
                 .iter()
                 .find_map(|(u_range, v_range, uv)| {
@@ -265,3 +557,23 @@ fn nudge(f: f64) -> f64 {
         f
     }
 }
+
+/// The average, over `width` units centred on `x`, of a square wave which is 1 wherever `x` floors to an even number, and -1
+/// wherever it floors to an odd number.
+///
+/// The integral of the square wave is a triangle wave, so the average is the change in the triangle wave across the `width`.
+/// Negligible widths (e.g. along an axis which doesn't change across the surface) sample the (nudged) square wave directly
+fn filtered_square_wave(x: f64, width: f64) -> f64 {
+    if width < crate::core::EPSILON {
+        return if nudge(x).floor() % 2.0 == 0.0 {
+            1.0
+        } else {
+            -1.0
+        };
+    }
+
+    let integral = |x: f64| 1.0 - (x.rem_euclid(2.0) - 1.0).abs();
+    let half_width = width / 2.0;
+
+    (integral(x + half_width) - integral(x - half_width)) / width
+}
@@ -0,0 +1,96 @@
+//! Deterministic gradient (Perlin) noise, used to perturb patterns.
+//!
+//! Rather than the usual random permutation table, the gradient at each lattice point is chosen by hashing the lattice
+//! coordinates, so the noise is the same on every run, on every thread, without any shared state
+
+use crate::core::Point3D;
+
+/// gradient noise at the given point, between roughly -1 and 1 - always 0 on integer coordinates
+pub(in crate::scene) fn perlin(point: Point3D) -> f64 {
+    let (x, y, z) = (point.x(), point.y(), point.z());
+    let (x0, y0, z0) = (x.floor(), y.floor(), z.floor());
+    let (x, y, z) = (x - x0, y - y0, z - z0);
+    let (i, j, k) = (x0 as i64, y0 as i64, z0 as i64);
+
+    let (u, v, w) = (fade(x), fade(y), fade(z));
+
+    let corner = |di: i64, dj: i64, dk: i64| {
+        gradient(
+            hash(i + di, j + dj, k + dk),
+            x - di as f64,
+            y - dj as f64,
+            z - dk as f64,
+        )
+    };
+
+    lerp(
+        w,
+        lerp(
+            v,
+            lerp(u, corner(0, 0, 0), corner(1, 0, 0)),
+            lerp(u, corner(0, 1, 0), corner(1, 1, 0)),
+        ),
+        lerp(
+            v,
+            lerp(u, corner(0, 0, 1), corner(1, 0, 1)),
+            lerp(u, corner(0, 1, 1), corner(1, 1, 1)),
+        ),
+    )
+}
+
+/// sums `octaves` layers of noise, each with double the frequency and half the amplitude of the last, normalised to roughly -1 to 1
+pub(in crate::scene) fn fractal(point: Point3D, octaves: u8) -> f64 {
+    let (total, max_amplitude, ..) = (0..octaves.max(1)).fold(
+        (0.0, 0.0, 1.0, 1.0),
+        |(total, max_amplitude, amplitude, frequency), _| {
+            let sample = perlin(Point3D::new(
+                point.x() * frequency,
+                point.y() * frequency,
+                point.z() * frequency,
+            ));
+
+            (
+                total + sample * amplitude,
+                max_amplitude + amplitude,
+                amplitude / 2.0,
+                frequency * 2.0,
+            )
+        },
+    );
+
+    total / max_amplitude
+}
+
+fn fade(t: f64) -> f64 {
+    t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
+
+fn lerp(t: f64, from: f64, to: f64) -> f64 {
+    from + t * (to - from)
+}
+
+fn hash(i: i64, j: i64, k: i64) -> u32 {
+    let mut hash = (i as u32).wrapping_mul(0x8da6_b343)
+        ^ (j as u32).wrapping_mul(0xd816_3841)
+        ^ (k as u32).wrapping_mul(0xcb1a_b31f);
+    hash ^= hash >> 13;
+    hash = hash.wrapping_mul(0x5bd1_e995);
+    hash ^ (hash >> 15)
+}
+
+/// dot product of the offset from the lattice point with one of 12 gradients pointing to the edges of a cube (as in Ken Perlin's improved noise)
+fn gradient(hash: u32, x: f64, y: f64, z: f64) -> f64 {
+    let h = hash & 15;
+    let u = if h < 8 { x } else { y };
+    let v = if h < 4 {
+        y
+    } else if h == 12 || h == 14 {
+        x
+    } else {
+        z
+    };
+
+    let u = if h & 1 == 0 { u } else { -u };
+    let v = if h & 2 == 0 { v } else { -v };
+    u + v
+}
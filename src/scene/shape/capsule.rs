@@ -0,0 +1,112 @@
+use crate::core::{Normal3D, Point3D, Ray, Vector3D, VectorMaths};
+use crate::scene::bounding_box::BoundingBox;
+use crate::scene::intersection::{Intersection, Intersections};
+use crate::scene::shape::sphere::{azimuthal_tangent, azimuthal_u};
+use crate::scene::shape::Shape;
+use crate::scene::Object;
+
+/// A cylinder centred on the Y axis, with a hemisphere on each end, such that the centre of the capsule is at the world origin.
+///
+/// The `height` is the length of the cylindrical part (the "barrel"), so the full height of the capsule is `height + 2 * radius`
+#[derive(Debug, PartialEq)]
+pub struct Capsule {
+    radius: f64,
+    height: f64,
+}
+
+impl Capsule {
+    pub fn new(radius: f64, height: f64) -> Self {
+        Capsule { radius, height }
+    }
+
+    /// the centre of the hemisphere capping the top of the barrel - the bottom cap is the mirror image
+    fn top_centre(&self) -> Point3D {
+        Point3D::new(0.0, self.height / 2.0, 0.0)
+    }
+
+    fn bottom_centre(&self) -> Point3D {
+        Point3D::new(0.0, -self.height / 2.0, 0.0)
+    }
+}
+
+impl Shape for Capsule {
+    fn object_bounds(&self) -> BoundingBox {
+        let half_height = self.height / 2.0 + self.radius;
+
+        BoundingBox::new(
+            Point3D::new(-self.radius, -half_height, -self.radius),
+            Point3D::new(self.radius, half_height, self.radius),
+        )
+    }
+
+    fn object_normal_at(&self, point: Point3D) -> Normal3D {
+        let half_height = self.height / 2.0;
+
+        if point.y() > half_height {
+            (point - self.top_centre()).normalised()
+        } else if point.y() < -half_height {
+            (point - self.bottom_centre()).normalised()
+        } else {
+            Vector3D::new(point.x(), 0.0, point.z()).normalised()
+        }
+    }
+
+    fn object_intersect<'parent>(
+        &self,
+        parent: &'parent Object,
+        with: Ray,
+    ) -> Intersections<'parent> {
+        let half_height = self.height / 2.0;
+        let radius_squared = self.radius.powi(2);
+        let mut intersections = Intersections::empty();
+
+        // the barrel is an infinite cylinder, truncated at either end, where the caps take over
+        let a = with.direction.x().powi(2) + with.direction.z().powi(2);
+        if a.abs() > f64::EPSILON {
+            let b =
+                2.0 * (with.origin.x() * with.direction.x() + with.origin.z() * with.direction.z());
+            let c = with.origin.x().powi(2) + with.origin.z().powi(2) - radius_squared;
+
+            if let Some((first, second)) = crate::core::quadratic(a, b, c) {
+                for t in [first, second] {
+                    let y = with.position(t).y();
+                    if y > -half_height && y < half_height {
+                        intersections.push(Intersection::new(t, parent));
+                    }
+                }
+            }
+        }
+
+        // each cap is a sphere, of which only the half beyond the end of the barrel is part of the capsule
+        for (centre, sign) in [(self.top_centre(), 1.0), (self.bottom_centre(), -1.0)] {
+            let centre_to_ray = with.origin - centre;
+            let a = with.direction.dot(with.direction);
+            let b = 2.0 * with.direction.dot(centre_to_ray);
+            let c = centre_to_ray.dot(centre_to_ray) - radius_squared;
+
+            if let Some((first, second)) = crate::core::quadratic(a, b, c) {
+                for t in [first, second] {
+                    if with.position(t).y() * sign >= half_height {
+                        intersections.push(Intersection::new(t, parent));
+                    }
+                }
+            }
+        }
+
+        intersections
+    }
+
+    /// `u` runs around the Y axis, and `v` runs from the bottom of the bottom cap (0) to the top of the top cap (1)
+    fn uv_at(&self, point: Point3D) -> (f64, f64) {
+        let u = azimuthal_u(point.x(), point.z());
+
+        let full_height = self.height + 2.0 * self.radius;
+        let v = (point.y() + full_height / 2.0) / full_height;
+
+        (u, v)
+    }
+
+    fn object_tangent_at(&self, point: Point3D) -> Option<Vector3D> {
+        azimuthal_tangent(point)
+    }
+}
@@ -2,20 +2,21 @@ use super::{Intersection, Intersections};
 use crate::core::F64Ext;
 use crate::core::{Normal3D, Point3D, Ray, Vector3D, VectorMaths};
 use crate::scene::bounding_box::BoundingBox;
+use crate::scene::shape::sphere::azimuthal_u;
 use crate::scene::Object;
 use crate::scene::Shape;
-use std::f64::consts::PI;
 
 /// An infinite double-napped cone (like a sand timer), tapering to a point at the origin,
 /// centred on the y axis, with a radius equal to the absolute y value (i.e. the radius is 1 at y -1)
 ///
 /// May be truncated at either end, to make the shape finite. Truncating at y = 0 produces a single cone.
-/// May be capped, otherwise the end will be open and the inner face will be visible
+/// Either end may be capped, otherwise that end will be open and the inner face will be visible
 #[derive(Debug, PartialEq)]
 pub struct Cone {
     max_y: f64,
     min_y: f64,
-    capped: bool,
+    capped_top: bool,
+    capped_bottom: bool,
 }
 
 impl Shape for Cone {
@@ -29,9 +30,9 @@ impl Shape for Cone {
     }
 
     fn object_normal_at(&self, point: Point3D) -> Normal3D {
-        if self.capped && point.y().is_roughly_gte(self.max_y) {
+        if self.capped_top && point.y().is_roughly_gte(self.max_y) {
             Normal3D::POSITIVE_Y
-        } else if self.capped && point.y().is_roughly_lte(self.min_y) {
+        } else if self.capped_bottom && point.y().is_roughly_lte(self.min_y) {
             Normal3D::NEGATIVE_Y
         } else {
             let y = (point.x().powi(2) + point.z().powi(2)).sqrt();
@@ -59,26 +60,22 @@ impl Shape for Cone {
             distance.sqrt().is_roughly_lte(y.abs())
         };
 
-        let mut cap_intersections = if self.capped {
-            let mut ts = Intersections::empty();
-            // check bottom cap
+        let mut cap_intersections = Intersections::empty();
+        if self.capped_bottom {
             let t = (self.min_y - ray.origin.y()) / ray.direction.y();
 
             if intersects_cap(t) {
-                ts.push(Intersection::new(t, parent));
+                cap_intersections.push(Intersection::new(t, parent));
             }
+        }
 
-            // check top cap
+        if self.capped_top {
             let t = (self.max_y - ray.origin.y()) / ray.direction.y();
 
             if intersects_cap(t) {
-                ts.push(Intersection::new(t, parent));
+                cap_intersections.push(Intersection::new(t, parent));
             }
-
-            ts
-        } else {
-            Intersections::empty()
-        };
+        }
 
         let a = ray.direction.x().powi(2) - ray.direction.y().powi(2) + ray.direction.z().powi(2);
         let b = 2.0 * ray.origin.x() * ray.direction.x() - 2.0 * ray.origin.y() * ray.direction.y()
@@ -121,26 +118,25 @@ impl Shape for Cone {
     ///  - u <- 0..1 maps to the sides of the cone,
     ///  - u <- 1..2 maps to the top cap of the cone
     ///  - u <- 2..3 maps to the bottom cap of the cone
+    ///
+    /// On the sides, `v` repeats every unit of `y` (as with `Cylinder`), rather than being normalised between `min_y` and `max_y`,
+    /// so infinite cones can be mapped, and patterns are the same size along the whole length of the cone
     fn uv_at(&self, point: Point3D) -> (f64, f64) {
-        if self.capped && self.max_y.roughly_equals(point.y()) {
+        if self.capped_top && self.max_y.roughly_equals(point.y()) {
             let u = (point.x() + 1.0) / 2.0;
             let v = (1.0 - point.z()) / 2.0;
 
             return (u + 1.0, v);
         }
 
-        if self.capped && self.min_y.roughly_equals(point.y()) {
+        if self.capped_bottom && self.min_y.roughly_equals(point.y()) {
             let u = (point.x() + 1.0) / 2.0;
             let v = (point.z() + 1.0) / 2.0;
 
             return (u + 2.0, v);
         }
 
-        // azimuthal angle
-        let theta = point.x().atan2(point.z());
-        let raw_u = theta / (2.0 * PI);
-        // corrects backwards azimuthal angle
-        let u = 1.0 - (raw_u + 0.5);
+        let u = azimuthal_u(point.x(), point.z());
 
         let v = point.y().rem_euclid(1.0);
         (u, v)
@@ -150,7 +146,8 @@ impl Shape for Cone {
 pub struct ConeBuilder {
     min_y: f64,
     max_y: f64,
-    capped: bool,
+    capped_top: bool,
+    capped_bottom: bool,
 }
 
 impl ConeBuilder {
@@ -158,7 +155,8 @@ impl ConeBuilder {
         ConeBuilder {
             min_y: -f64::MAX,
             max_y: f64::MAX,
-            capped: false,
+            capped_top: false,
+            capped_bottom: false,
         }
     }
 
@@ -172,8 +170,20 @@ impl ConeBuilder {
         self
     }
 
-    pub fn capped(mut self) -> Self {
-        self.capped = true;
+    /// closes both ends - equivalent to `capped_top` and `capped_bottom`
+    pub fn capped(self) -> Self {
+        self.capped_top().capped_bottom()
+    }
+
+    /// closes the end at `max_y`
+    pub fn capped_top(mut self) -> Self {
+        self.capped_top = true;
+        self
+    }
+
+    /// closes the end at `min_y`
+    pub fn capped_bottom(mut self) -> Self {
+        self.capped_bottom = true;
         self
     }
 
@@ -181,7 +191,8 @@ impl ConeBuilder {
         Object::from_shape(Box::new(Cone {
             min_y: self.min_y,
             max_y: self.max_y,
-            capped: self.capped,
+            capped_top: self.capped_top,
+            capped_bottom: self.capped_bottom,
         }))
     }
 }
@@ -3,19 +3,30 @@ use crate::core::Ray;
 use crate::core::{Normal3D, Point3D, Vector3D, VectorMaths};
 use crate::scene::bounding_box::BoundingBox;
 use crate::scene::intersection::{Intersection, Intersections};
+use crate::scene::shape::sphere::azimuthal_u;
 use crate::scene::Object;
 use crate::scene::Shape;
-use std::f64::consts::PI;
 
 /// An infinite cylinder centred on the y axis, with a constant radius of 1
 ///
 /// May be truncated at either end to make it finite.
-/// May be capped, otherwise the ends will be open, and the inner face will be visible
+/// Either end may be capped, otherwise that end will be open, and the inner face will be visible
 #[derive(Debug, PartialEq)]
 pub struct Cylinder {
     max_y: f64,
     min_y: f64,
-    capped: bool,
+    capped_top: bool,
+    capped_bottom: bool,
+}
+
+impl Cylinder {
+    /// an infinite, open cylinder, i.e. the default built by `CylinderBuilder`
+    pub(in crate::scene) const INFINITE: Cylinder = Cylinder {
+        min_y: -f64::MAX,
+        max_y: f64::MAX,
+        capped_top: false,
+        capped_bottom: false,
+    };
 }
 
 impl Shape for Cylinder {
@@ -27,9 +38,9 @@ impl Shape for Cylinder {
     }
 
     fn object_normal_at(&self, point: Point3D) -> Normal3D {
-        if self.capped && point.y().is_roughly_gte(self.max_y) {
+        if self.capped_top && point.y().is_roughly_gte(self.max_y) {
             Normal3D::POSITIVE_Y
-        } else if self.capped && point.y().is_roughly_lte(self.min_y) {
+        } else if self.capped_bottom && point.y().is_roughly_lte(self.min_y) {
             Normal3D::NEGATIVE_Y
         } else {
             Vector3D::new(point.x(), 0.0, point.z()).normalised()
@@ -48,26 +59,22 @@ impl Shape for Cylinder {
             (x.powi(2) + z.powi(2)).is_roughly_lte(1.0)
         };
 
-        let cap_intersections = if self.capped {
-            let mut ts = Intersections::empty();
-            // check bottom cap
+        let mut cap_intersections = Intersections::empty();
+        if self.capped_bottom {
             let t = (self.min_y - ray.origin.y()) / ray.direction.y();
 
             if intersects_cap(t) {
-                ts.push(Intersection::new(t, parent));
+                cap_intersections.push(Intersection::new(t, parent));
             }
+        }
 
-            // check top cap
+        if self.capped_top {
             let t = (self.max_y - ray.origin.y()) / ray.direction.y();
 
             if intersects_cap(t) {
-                ts.push(Intersection::new(t, parent));
+                cap_intersections.push(Intersection::new(t, parent));
             }
-
-            ts
-        } else {
-            Intersections::empty()
-        };
+        }
 
         let a = ray.direction.x().powi(2) + ray.direction.z().powi(2);
 
@@ -107,25 +114,21 @@ impl Shape for Cylinder {
     ///  - u <- 1..2 maps to the top cap of the cylinder
     ///  - u <- 2..3 maps to the bottom cap of the cylinder
     fn uv_at(&self, point: Point3D) -> (f64, f64) {
-        if self.capped && self.max_y.roughly_equals(point.y()) {
+        if self.capped_top && self.max_y.roughly_equals(point.y()) {
             let u = (point.x() + 1.0) / 2.0;
             let v = (1.0 - point.z()) / 2.0;
 
             return (u + 1.0, v);
         }
 
-        if self.capped && self.min_y.roughly_equals(point.y()) {
+        if self.capped_bottom && self.min_y.roughly_equals(point.y()) {
             let u = (point.x() + 1.0) / 2.0;
             let v = (point.z() + 1.0) / 2.0;
 
             return (u + 2.0, v);
         }
 
-        // azimuthal angle
-        let theta = point.x().atan2(point.z());
-        let raw_u = theta / (2.0 * PI);
-        // corrects backwards azimuthal angle
-        let u = 1.0 - (raw_u + 0.5);
+        let u = azimuthal_u(point.x(), point.z());
 
         let v = point.y().rem_euclid(1.0);
         (u, v)
@@ -135,7 +138,8 @@ impl Shape for Cylinder {
 pub struct CylinderBuilder {
     min_y: f64,
     max_y: f64,
-    capped: bool,
+    capped_top: bool,
+    capped_bottom: bool,
 }
 
 impl CylinderBuilder {
@@ -143,7 +147,8 @@ impl CylinderBuilder {
         CylinderBuilder {
             min_y: -f64::MAX,
             max_y: f64::MAX,
-            capped: false,
+            capped_top: false,
+            capped_bottom: false,
         }
     }
 
@@ -157,8 +162,20 @@ impl CylinderBuilder {
         self
     }
 
-    pub fn capped(mut self) -> Self {
-        self.capped = true;
+    /// closes both ends - equivalent to `capped_top` and `capped_bottom`
+    pub fn capped(self) -> Self {
+        self.capped_top().capped_bottom()
+    }
+
+    /// closes the end at `max_y`
+    pub fn capped_top(mut self) -> Self {
+        self.capped_top = true;
+        self
+    }
+
+    /// closes the end at `min_y`
+    pub fn capped_bottom(mut self) -> Self {
+        self.capped_bottom = true;
         self
     }
 
@@ -166,7 +183,8 @@ impl CylinderBuilder {
         Object::from_shape(Box::new(Cylinder {
             min_y: self.min_y,
             max_y: self.max_y,
-            capped: self.capped,
+            capped_top: self.capped_top,
+            capped_bottom: self.capped_bottom,
         }))
     }
 }
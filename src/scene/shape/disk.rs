@@ -0,0 +1,89 @@
+use crate::core::{F64Ext, Normal3D, Point3D, Ray, VectorMaths};
+use crate::scene::bounding_box::BoundingBox;
+use crate::scene::intersection::{Intersection, Intersections};
+use crate::scene::shape::Shape;
+use crate::scene::Object;
+
+/// A flat disk in the XZ plane, centred on the world origin, facing up the Y axis.
+///
+/// May have a hole in the middle (i.e. be an annulus, or ring) if the `inner_radius` is non-zero
+#[derive(Debug, PartialEq)]
+pub struct Disk {
+    radius: f64,
+    inner_radius: f64,
+}
+
+impl Shape for Disk {
+    fn object_bounds(&self) -> BoundingBox {
+        BoundingBox::new(
+            Point3D::new(-self.radius, 0.0, -self.radius),
+            Point3D::new(self.radius, 0.0, self.radius),
+        )
+    }
+
+    fn object_normal_at(&self, _: Point3D) -> Normal3D {
+        Normal3D::POSITIVE_Y
+    }
+
+    fn object_intersect<'parent>(
+        &self,
+        parent: &'parent Object,
+        with: Ray,
+    ) -> Intersections<'parent> {
+        if with.direction.y().is_roughly_zero() {
+            return Intersections::empty();
+        }
+
+        let t = -with.origin.y() / with.direction.y();
+        let point = with.position(t);
+        let distance_squared = point.x().powi(2) + point.z().powi(2);
+
+        if distance_squared.is_roughly_lte(self.radius.powi(2))
+            && distance_squared.is_roughly_gte(self.inner_radius.powi(2))
+        {
+            Intersections::single(Intersection::new(t, parent))
+        } else {
+            Intersections::empty()
+        }
+    }
+
+    /// maps the square containing the disk to `0..1`, as if viewed from above (i.e. the same as the top cap of a cylinder)
+    fn uv_at(&self, point: Point3D) -> (f64, f64) {
+        let u = (point.x() / self.radius + 1.0) / 2.0;
+        let v = (1.0 - point.z() / self.radius) / 2.0;
+
+        (u, v)
+    }
+}
+
+pub struct DiskBuilder {
+    radius: f64,
+    inner_radius: f64,
+}
+
+impl DiskBuilder {
+    pub(in crate::scene) fn new(radius: f64) -> Self {
+        DiskBuilder {
+            radius,
+            inner_radius: 0.0,
+        }
+    }
+
+    /// cuts a hole of the given radius out of the middle of the disk
+    pub fn inner_radius(mut self, inner_radius: f64) -> Self {
+        self.inner_radius = inner_radius;
+        self
+    }
+
+    pub fn build(self) -> Object {
+        assert!(
+            self.inner_radius < self.radius,
+            "the inner radius of a disk must be smaller than the outer radius"
+        );
+
+        Object::from_shape(Box::new(Disk {
+            radius: self.radius,
+            inner_radius: self.inner_radius,
+        }))
+    }
+}
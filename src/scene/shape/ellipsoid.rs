@@ -0,0 +1,89 @@
+use crate::core::{Normal3D, Point3D, Ray, Vector3D, VectorMaths};
+use crate::scene::bounding_box::BoundingBox;
+use crate::scene::intersection::{Intersection, Intersections};
+use crate::scene::shape::sphere::{azimuthal_tangent, Sphere};
+use crate::scene::shape::Shape;
+use crate::scene::Object;
+
+/// An ellipsoid centred on the world origin, with the semi-axes `a`, `b`, and `c` along the X, Y, and Z axes respectively,
+/// i.e. the surface where `x²/a² + y²/b² + z²/c² = 1`
+#[derive(Debug, PartialEq)]
+pub struct Ellipsoid {
+    a: f64,
+    b: f64,
+    c: f64,
+}
+
+impl Ellipsoid {
+    pub fn new(a: f64, b: f64, c: f64) -> Self {
+        Ellipsoid { a, b, c }
+    }
+
+    /// maps a point onto the unit sphere the ellipsoid is stretched from
+    fn to_unit_sphere(&self, vector: Vector3D) -> Vector3D {
+        Vector3D::new(
+            vector.x() / self.a,
+            vector.y() / self.b,
+            vector.z() / self.c,
+        )
+    }
+}
+
+impl Shape for Ellipsoid {
+    fn object_bounds(&self) -> BoundingBox {
+        BoundingBox::new(
+            Point3D::new(-self.a, -self.b, -self.c),
+            Point3D::new(self.a, self.b, self.c),
+        )
+    }
+
+    fn object_normal_at(&self, point: Point3D) -> Normal3D {
+        // the gradient of the implicit surface
+        Vector3D::new(
+            2.0 * point.x() / self.a.powi(2),
+            2.0 * point.y() / self.b.powi(2),
+            2.0 * point.z() / self.c.powi(2),
+        )
+        .normalised()
+    }
+
+    fn object_intersect<'parent>(
+        &self,
+        parent: &'parent Object,
+        with: Ray,
+    ) -> Intersections<'parent> {
+        // scaling each axis by its semi-axis turns the ellipsoid into a unit sphere, without changing the `t` values
+        let origin = self.to_unit_sphere(with.origin - Point3D::ORIGIN);
+        let direction = self.to_unit_sphere(with.direction);
+
+        let a = direction.dot(direction);
+        let b = 2.0 * direction.dot(origin);
+        let c = origin.dot(origin) - 1.0;
+
+        if let Some((first, second)) = crate::core::quadratic(a, b, c) {
+            Intersections::pair(
+                Intersection::new(first, parent),
+                Intersection::new(second, parent),
+            )
+        } else {
+            Intersections::empty()
+        }
+    }
+
+    /// as a sphere, where `u` runs around the Y axis, and `v` runs from the bottom (0) to the top (1)
+    fn uv_at(&self, point: Point3D) -> (f64, f64) {
+        Sphere.uv_at(Point3D::ORIGIN + self.to_unit_sphere(point - Point3D::ORIGIN))
+    }
+
+    fn object_tangent_at(&self, point: Point3D) -> Option<Vector3D> {
+        // the tangent of the unit sphere, stretched along with the sphere
+        let tangent =
+            azimuthal_tangent(Point3D::ORIGIN + self.to_unit_sphere(point - Point3D::ORIGIN))?;
+
+        Some(Vector3D::new(
+            tangent.x() * self.a,
+            0.0,
+            tangent.z() * self.c,
+        ))
+    }
+}
@@ -0,0 +1,65 @@
+use crate::core::{F64Ext, Normal3D, Point3D, Ray, Vector3D, VectorMaths};
+use crate::scene::bounding_box::BoundingBox;
+use crate::scene::intersection::{Intersection, Intersections};
+use crate::scene::shape::Shape;
+use crate::scene::Object;
+
+/// A finite rectangle in the XZ plane, centred on the world origin, facing up the Y axis - `width` along the X axis, and
+/// `depth` along the Z axis
+#[derive(Debug, PartialEq)]
+pub struct Quad {
+    width: f64,
+    depth: f64,
+}
+
+impl Quad {
+    pub fn new(width: f64, depth: f64) -> Self {
+        Quad { width, depth }
+    }
+}
+
+impl Shape for Quad {
+    fn object_bounds(&self) -> BoundingBox {
+        BoundingBox::new(
+            Point3D::new(-self.width / 2.0, 0.0, -self.depth / 2.0),
+            Point3D::new(self.width / 2.0, 0.0, self.depth / 2.0),
+        )
+    }
+
+    fn object_normal_at(&self, _: Point3D) -> Normal3D {
+        Normal3D::POSITIVE_Y
+    }
+
+    fn object_intersect<'parent>(
+        &self,
+        parent: &'parent Object,
+        with: Ray,
+    ) -> Intersections<'parent> {
+        if with.direction.y().is_roughly_zero() {
+            return Intersections::empty();
+        }
+
+        let t = -with.origin.y() / with.direction.y();
+        let point = with.position(t);
+
+        if point.x().abs().is_roughly_lte(self.width / 2.0)
+            && point.z().abs().is_roughly_lte(self.depth / 2.0)
+        {
+            Intersections::single(Intersection::new(t, parent))
+        } else {
+            Intersections::empty()
+        }
+    }
+
+    /// maps the quad to `0..1`, with `u` increasing along the X axis, and `v` along the Z axis (the same as a plane)
+    fn uv_at(&self, point: Point3D) -> (f64, f64) {
+        let u = point.x() / self.width + 0.5;
+        let v = point.z() / self.depth + 0.5;
+
+        (u, v)
+    }
+
+    fn object_tangent_at(&self, _: Point3D) -> Option<Vector3D> {
+        Some(Vector3D::new(1.0, 0.0, 0.0))
+    }
+}
@@ -1,4 +1,4 @@
-use crate::core::{Normal3D, Point3D, Ray, VectorMaths};
+use crate::core::{F64Ext, Normal3D, Point3D, Ray, Vector3D, VectorMaths};
 use crate::scene::bounding_box::BoundingBox;
 use crate::scene::intersection::{Intersection, Intersections};
 use crate::scene::shape::Shape;
@@ -40,19 +40,41 @@ impl Shape for Sphere {
     fn uv_at(&self, point: Point3D) -> (f64, f64) {
         // See https://en.wikipedia.org/wiki/Spherical_coordinate_system noting this uses _mathematical_ notation
 
-        // azimuthal angle - this is backwards but gets corrected later
-        let theta = point.x().atan2(point.z());
+        let u = azimuthal_u(point.x(), point.z());
         // given the centre is at the world origin, the radius is given by the magnitude of the vector
         // from the world origin to the point
         let r = (point - Point3D::ORIGIN).magnitude();
         // polar angle
         let phi = (point.y() / r).acos();
-        let raw_u = theta / (2.0 * PI);
-        // corrects backwards azimuthal angle
-        let u = 1.0 - (raw_u + 0.5);
         // subtract from 1 to invert `v` such that 1 is the northernmost point
         let v = 1.0 - (phi / PI);
 
         (u, v)
     }
+
+    fn object_tangent_at(&self, point: Point3D) -> Option<Vector3D> {
+        azimuthal_tangent(point)
+    }
+}
+
+/// the `u` coordinate of a point around the Y axis, from 0 to 1 - `u` increases clockwise around the Y axis, when viewed
+/// from above
+pub(in crate::scene) fn azimuthal_u(x: f64, z: f64) -> f64 {
+    // azimuthal angle - this is backwards but gets corrected later
+    let theta = x.atan2(z);
+    let raw_u = theta / (2.0 * PI);
+    // corrects backwards azimuthal angle
+    1.0 - (raw_u + 0.5)
+}
+
+/// the direction in which `azimuthal_u` increases at the `point`, or `None` on the Y axis, where `u` is undefined
+pub(in crate::scene) fn azimuthal_tangent(point: Point3D) -> Option<Vector3D> {
+    // `u` increases clockwise around the Y axis, when viewed from above
+    let tangent = Vector3D::new(-point.z(), 0.0, point.x());
+
+    if tangent.magnitude().is_roughly_zero() {
+        None
+    } else {
+        Some(tangent)
+    }
 }
@@ -0,0 +1,86 @@
+use crate::core::{Normal3D, Point3D, Ray, Vector3D, VectorMaths};
+use crate::scene::bounding_box::BoundingBox;
+use crate::scene::intersection::{Intersection, Intersections};
+use crate::scene::shape::sphere::azimuthal_u;
+use crate::scene::shape::Shape;
+use crate::scene::Object;
+use std::f64::consts::PI;
+
+/// A torus (i.e. a ring, or a donut) centred on the world origin, lying flat in the XZ plane, such that the Y axis passes through the hole.
+///
+/// The `major_radius` is the distance from the origin to the centre of the tube, and the `minor_radius` is the radius of the tube
+#[derive(Debug, PartialEq)]
+pub struct Torus {
+    major_radius: f64,
+    minor_radius: f64,
+}
+
+impl Torus {
+    pub fn new(major_radius: f64, minor_radius: f64) -> Self {
+        Torus {
+            major_radius,
+            minor_radius,
+        }
+    }
+}
+
+impl Shape for Torus {
+    fn object_bounds(&self) -> BoundingBox {
+        let outer = self.major_radius + self.minor_radius;
+
+        BoundingBox::new(
+            Point3D::new(-outer, -self.minor_radius, -outer),
+            Point3D::new(outer, self.minor_radius, outer),
+        )
+    }
+
+    fn object_normal_at(&self, point: Point3D) -> Normal3D {
+        // the normal points away from the closest point on the circle running through the centre of the tube
+        let towards_point = Vector3D::new(point.x(), 0.0, point.z()).normalised();
+        let centre_of_tube = Point3D::ORIGIN + towards_point * self.major_radius;
+
+        (point - centre_of_tube).normalised()
+    }
+
+    fn object_intersect<'parent>(
+        &self,
+        parent: &'parent Object,
+        with: Ray,
+    ) -> Intersections<'parent> {
+        // substitutes the ray into (x^2 + y^2 + z^2 + R^2 - r^2)^2 = 4R^2(x^2 + z^2)
+        let origin = with.origin - Point3D::ORIGIN;
+        let direction = with.direction;
+        let major_squared = self.major_radius.powi(2);
+
+        let direction_squared = direction.dot(direction);
+        let origin_dot_direction = origin.dot(direction);
+        let k = origin.dot(origin) + major_squared - self.minor_radius.powi(2);
+
+        let a = direction_squared.powi(2);
+        let b = 4.0 * direction_squared * origin_dot_direction;
+        let c = 2.0 * direction_squared * k + 4.0 * origin_dot_direction.powi(2)
+            - 4.0 * major_squared * (direction.x().powi(2) + direction.z().powi(2));
+        let d = 4.0 * k * origin_dot_direction
+            - 8.0 * major_squared * (origin.x() * direction.x() + origin.z() * direction.z());
+        let e = k.powi(2) - 4.0 * major_squared * (origin.x().powi(2) + origin.z().powi(2));
+
+        let mut intersections = Intersections::empty();
+        for t in crate::core::quartic(a, b, c, d, e) {
+            intersections.push(Intersection::new(t, parent));
+        }
+
+        intersections
+    }
+
+    /// `u` runs around the Y axis (i.e. around the ring), and `v` runs around the tube, starting from the outer edge, and going over the top
+    fn uv_at(&self, point: Point3D) -> (f64, f64) {
+        let u = azimuthal_u(point.x(), point.z());
+
+        let distance_from_centre_of_tube =
+            (point.x().powi(2) + point.z().powi(2)).sqrt() - self.major_radius;
+        let phi = point.y().atan2(distance_from_centre_of_tube);
+        let v = (phi / (2.0 * PI)).rem_euclid(1.0);
+
+        (u, v)
+    }
+}
@@ -13,6 +13,8 @@ pub struct Triangle {
     edge2: Vector3D,
     denominator: f64,
     kind: NormalKind,
+    /// one set of (u, v) coordinates per texture channel, for each of `p1`, `p2`, and `p3` respectively
+    texture_coordinates: Vec<[(f64, f64); 3]>,
 }
 
 impl Triangle {
@@ -31,6 +33,7 @@ impl Triangle {
             edge2,
             denominator,
             kind: NormalKind::Uniform(normal),
+            texture_coordinates: Vec::new(),
         }
     }
 
@@ -58,8 +61,14 @@ impl Triangle {
                 normal2,
                 normal3,
             },
+            texture_coordinates: Vec::new(),
         }
     }
+
+    pub fn with_texture_coordinates(mut self, texture_coordinates: Vec<[(f64, f64); 3]>) -> Self {
+        self.texture_coordinates = texture_coordinates;
+        self
+    }
 }
 
 #[derive(Debug, PartialEq)]
@@ -144,4 +153,18 @@ impl Shape for Triangle {
         // using `v` and `w` like this (and ignoring `u`) gives the same coordinates as Möller–Trumbore
         (v, w)
     }
+
+    /// interpolates the texture coordinates of the given channel, if the triangle has them;
+    /// otherwise falls back to the Barycentric coordinates
+    fn uv_channel_at(&self, point: Point3D, channel: usize) -> (f64, f64) {
+        let (u, v) = self.uv_at(point);
+
+        if let Some([(u1, v1), (u2, v2), (u3, v3)]) = self.texture_coordinates.get(channel) {
+            let w = 1.0 - u - v;
+
+            (u1 * w + u2 * u + u3 * v, v1 * w + v2 * u + v3 * v)
+        } else {
+            (u, v)
+        }
+    }
 }
@@ -0,0 +1,74 @@
+use std::sync::Arc;
+
+use image::RgbImage;
+use std::f64::consts::PI;
+
+use crate::core::{Colour, Point3D, Vector3D, VectorMaths};
+use crate::scene::pattern::WrapMode;
+use crate::scene::shape::{cube::Cube, Shape};
+use crate::scene::texture::sample_bilinear;
+
+/// the environment surrounding the scene, sampled by rays which don't intersect any objects
+#[derive(Clone, Debug, PartialEq)]
+pub enum Sky {
+    /// six images, one for each face of an infinitely large cube surrounding the scene, named after the axis each face is centred on
+    /// (e.g. `px` is the face seen when looking along the positive X axis)
+    CubeMap {
+        px: Arc<RgbImage>,
+        nx: Arc<RgbImage>,
+        py: Arc<RgbImage>,
+        ny: Arc<RgbImage>,
+        pz: Arc<RgbImage>,
+        nz: Arc<RgbImage>,
+    },
+    /// a single image covering every direction, using a latitude/longitude (i.e. equirectangular) mapping - the top row of
+    /// the image is straight up, the bottom row is straight down, and the centre of the image is seen when looking along
+    /// the positive Z axis
+    Environment(Arc<RgbImage>),
+}
+
+impl Sky {
+    pub fn colour_at(&self, direction: Vector3D) -> Colour {
+        match self {
+            Sky::CubeMap {
+                px,
+                nx,
+                py,
+                ny,
+                pz,
+                nz,
+            } => {
+                let (x, y, z) = (direction.x(), direction.y(), direction.z());
+                let largest = x.abs().max(y.abs().max(z.abs()));
+                // project the direction onto the surface of the unit cube, so it can be mapped the same way as a cube
+                let point = Point3D::new(x / largest, y / largest, z / largest);
+
+                let face = if largest == x {
+                    px
+                } else if largest == -x {
+                    nx
+                } else if largest == y {
+                    py
+                } else if largest == -y {
+                    ny
+                } else if largest == z {
+                    pz
+                } else {
+                    nz
+                };
+
+                let (u, v) = Cube.uv_at(point);
+                sample_bilinear(face, u, v, WrapMode::Repeat)
+            }
+            Sky::Environment(image) => {
+                let direction = direction.normalised();
+                // as seen from inside the sphere, so `u` increases towards +X when looking along +Z (unlike `Sphere`)
+                let u = 0.5 + direction.x().atan2(direction.z()) / (2.0 * PI);
+                let v = 1.0 - direction.y().clamp(-1.0, 1.0).acos() / PI;
+
+                // both coordinates are already in the range 0..=1, and repeating would wrap straight down around to the top row
+                sample_bilinear(image, u, v, WrapMode::Clamp)
+            }
+        }
+    }
+}
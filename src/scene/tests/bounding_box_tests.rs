@@ -377,6 +377,44 @@ mod unit_tests {
                 assert!(!bounds.intersected_by(&ray), "{}", scenario)
             })
         }
+
+        #[test]
+        fn the_intersection_t_of_a_bounding_box_in_front_of_a_ray_should_be_the_entry_point() {
+            let bounds =
+                BoundingBox::new(Point3D::new(-1.0, -1.0, -1.0), Point3D::new(1.0, 1.0, 1.0));
+            let ray = Ray::new(Point3D::new(0.5, 0.0, -5.0), Normal3D::POSITIVE_Z);
+
+            assert_eq!(bounds.intersection_t(&ray), Some(4.0));
+        }
+
+        #[test]
+        fn the_intersection_t_of_a_bounding_box_behind_a_ray_should_be_none() {
+            let bounds =
+                BoundingBox::new(Point3D::new(-1.0, -1.0, -1.0), Point3D::new(1.0, 1.0, 1.0));
+            let ray = Ray::new(Point3D::new(0.5, 0.0, 5.0), Normal3D::POSITIVE_Z);
+
+            assert_eq!(bounds.intersection_t(&ray), None);
+            // the line along the ray still passes through the box
+            assert!(bounds.intersected_by(&ray));
+        }
+
+        #[test]
+        fn the_intersection_t_of_a_bounding_box_containing_the_ray_origin_should_be_negative() {
+            let bounds =
+                BoundingBox::new(Point3D::new(-1.0, -1.0, -1.0), Point3D::new(1.0, 1.0, 1.0));
+            let ray = Ray::new(Point3D::new(0.0, 0.5, 0.0), Normal3D::POSITIVE_Z);
+
+            assert_eq!(bounds.intersection_t(&ray), Some(-1.0));
+        }
+
+        #[test]
+        fn the_intersection_t_of_a_bounding_box_missed_by_a_ray_should_be_none() {
+            let bounds =
+                BoundingBox::new(Point3D::new(-1.0, -1.0, -1.0), Point3D::new(1.0, 1.0, 1.0));
+            let ray = Ray::new(Point3D::new(2.0, 0.0, 2.0), Normal3D::NEGATIVE_Z);
+
+            assert_eq!(bounds.intersection_t(&ray), None);
+        }
     }
 
     mod splitting {
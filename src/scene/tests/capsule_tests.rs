@@ -0,0 +1,112 @@
+use super::*;
+use crate::core::{Normal3D, Point3D, Ray, Vector3D, VectorMaths};
+use approx::*;
+
+fn capsule() -> Object {
+    Object::capsule(1.0, 2.0)
+}
+
+fn intersection_ts(object: &Object, ray: Ray) -> Vec<f64> {
+    object.intersect(&ray).iter().map(|i| i.t).collect()
+}
+
+#[test]
+fn a_ray_along_the_axis_of_a_capsule_should_hit_both_caps() {
+    let ray = Ray::new(Point3D::new(0.0, 5.0, 0.0), Normal3D::NEGATIVE_Y);
+
+    assert_eq!(intersection_ts(&capsule(), ray), vec![3.0, 7.0]);
+}
+
+#[test]
+fn a_ray_hitting_the_top_cap_of_a_capsule_should_intersect_the_hemisphere() {
+    let ray = Ray::new(Point3D::new(0.0, 1.5, -5.0), Normal3D::POSITIVE_Z);
+
+    let ts = intersection_ts(&capsule(), ray);
+    assert_eq!(ts.len(), 2);
+    assert_abs_diff_eq!(ts[0], 5.0 - 0.75_f64.sqrt());
+    assert_abs_diff_eq!(ts[1], 5.0 + 0.75_f64.sqrt());
+}
+
+#[test]
+fn a_ray_hitting_the_barrel_of_a_capsule_should_intersect_the_sides() {
+    let ray = Ray::new(Point3D::new(0.0, 0.5, -5.0), Normal3D::POSITIVE_Z);
+
+    assert_eq!(intersection_ts(&capsule(), ray), vec![4.0, 6.0]);
+}
+
+#[test]
+fn a_ray_entering_the_barrel_and_leaving_through_a_cap_should_intersect_once_on_each() {
+    let ray = Ray::new(
+        Point3D::new(0.0, -0.5, -2.0),
+        Vector3D::new(0.0, 1.0, 1.0).normalised(),
+    );
+
+    let ts = intersection_ts(&capsule(), ray.clone());
+    assert_eq!(ts.len(), 2);
+    assert!(ray.position(ts[0]).y() < 1.0);
+    assert!(ray.position(ts[1]).y() > 1.0);
+}
+
+#[test]
+fn a_ray_grazing_the_seam_between_the_cap_and_the_barrel_should_only_touch_the_capsule() {
+    let ray = Ray::new(Point3D::new(1.0, 1.0, -5.0), Normal3D::POSITIVE_Z);
+
+    let ts = intersection_ts(&capsule(), ray);
+    assert!(!ts.is_empty());
+    ts.iter()
+        .for_each(|t| assert_abs_diff_eq!(*t, 5.0, epsilon = 1e-6));
+}
+
+#[test]
+fn a_ray_passing_beside_a_capsule_should_miss() {
+    vec![
+        Ray::new(Point3D::new(1.01, 0.0, -5.0), Normal3D::POSITIVE_Z),
+        Ray::new(Point3D::new(0.0, 2.01, -5.0), Normal3D::POSITIVE_Z),
+        // would hit the infinite cylinder the barrel is part of, but passes above the top cap
+        Ray::new(Point3D::new(0.9, 2.5, -5.0), Normal3D::POSITIVE_Z),
+    ]
+    .into_iter()
+    .for_each(|ray| assert!(capsule().intersect(&ray).is_empty(), "{:?}", ray));
+}
+
+#[test]
+fn the_normal_on_the_barrel_of_a_capsule_should_point_away_from_the_axis() {
+    let capsule = capsule();
+
+    assert_eq!(
+        capsule.normal_at(Point3D::new(0.0, 0.5, -1.0)),
+        Normal3D::NEGATIVE_Z
+    );
+    assert_eq!(
+        capsule.normal_at(Point3D::new(1.0, -0.9, 0.0)),
+        Normal3D::POSITIVE_X
+    );
+}
+
+#[test]
+fn the_normal_on_a_cap_of_a_capsule_should_point_away_from_the_centre_of_the_cap() {
+    let capsule = capsule();
+
+    assert_eq!(
+        capsule.normal_at(Point3D::new(0.0, 2.0, 0.0)),
+        Normal3D::POSITIVE_Y
+    );
+    assert_eq!(
+        capsule.normal_at(Point3D::new(0.0, -2.0, 0.0)),
+        Normal3D::NEGATIVE_Y
+    );
+
+    let diagonal = 1.0 / 2.0_f64.sqrt();
+    assert_abs_diff_eq!(
+        capsule.normal_at(Point3D::new(diagonal, 1.0 + diagonal, 0.0)),
+        Vector3D::new(1.0, 1.0, 0.0).normalised()
+    );
+}
+
+#[test]
+fn the_bounds_of_a_capsule_should_include_both_caps() {
+    assert_eq!(
+        Object::capsule(0.5, 3.0).bounds,
+        BoundingBox::new(Point3D::new(-0.5, -2.0, -0.5), Point3D::new(0.5, 2.0, 0.5))
+    );
+}
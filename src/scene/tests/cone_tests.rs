@@ -97,6 +97,18 @@ fn the_size_of_the_top_cap_of_a_capped_cone_should_scale_with_the_max_y_value()
     assert_eq!(intersections.len(), 2);
 }
 
+#[test]
+fn a_ray_entering_the_open_top_of_a_cone_capped_at_the_bottom_should_only_be_stopped_by_the_bottom()
+{
+    let capped = Object::cone().min_y(0.5).max_y(1.0).capped().build();
+    let cup = Object::cone().min_y(0.5).max_y(1.0).capped_bottom().build();
+    let ray = Ray::new(Point3D::new(0.0, 2.0, 0.0), Normal3D::NEGATIVE_Y);
+
+    let ts = |cone: &Object| cone.intersect(&ray).iter().map(|i| i.t).collect::<Vec<_>>();
+    assert_eq!(ts(&capped), vec![1.0, 1.5]);
+    assert_eq!(ts(&cup), vec![1.5]);
+}
+
 #[rustfmt::skip]
 #[test]
 fn should_be_able_to_calculate_the_normal_of_any_point_on_a_double_napped_cone() {
@@ -161,3 +173,77 @@ fn uv_mapping_the_caps_of_a_capped_cone_should_project_points_onto_a_circle_on_a
             assert_eq!(cone.shape().uv_at(point), (u, v));
         })
 }
+
+#[test]
+fn uv_checkers_on_the_side_of_a_cone_should_not_stretch_towards_the_apex() {
+    use crate::core::Colour;
+    use std::num::NonZeroUsize;
+
+    let cone = Object::cone()
+        .min_y(-2.0)
+        .max_y(0.0)
+        .build()
+        .with_material(Material {
+            kind: MaterialKind::Uv(UvPattern::checkers(
+                Colour::WHITE,
+                Colour::BLACK,
+                NonZeroUsize::new(8).unwrap(),
+                NonZeroUsize::new(4).unwrap(),
+            )),
+            ..Default::default()
+        });
+
+    // walk down the slanted surface from just below the apex to the base, in equal steps
+    let colours = (1..200)
+        .map(|i| -(i as f64) * 0.01)
+        .map(|y| cone.raw_colour_at(Point3D::new(0.0, y, y)))
+        .collect::<Vec<_>>();
+
+    let boundaries = colours
+        .windows(2)
+        .enumerate()
+        .filter(|(_, pair)| pair[0] != pair[1])
+        .map(|(i, _)| i)
+        .collect::<Vec<_>>();
+
+    // `v` repeats every unit of `y`, so each row of checkers covers the same length of the slanted surface
+    let cell_lengths = boundaries
+        .windows(2)
+        .map(|pair| pair[1] - pair[0])
+        .collect::<Vec<_>>();
+    assert!(cell_lengths.len() >= 5, "{:?}", boundaries);
+    assert!(
+        cell_lengths.iter().all(|length| *length == 25),
+        "{:?}",
+        cell_lengths
+    );
+}
+
+#[test]
+fn checker_columns_either_side_of_the_uv_seam_of_a_cone_should_alternate() {
+    use crate::core::Colour;
+    use std::num::NonZeroUsize;
+
+    let cone = Object::cone()
+        .min_y(0.0)
+        .max_y(1.0)
+        .build()
+        .with_material(Material {
+            kind: MaterialKind::Uv(UvPattern::checkers(
+                Colour::WHITE,
+                Colour::BLACK,
+                NonZeroUsize::new(8).unwrap(),
+                NonZeroUsize::new(1).unwrap(),
+            )),
+            ..Default::default()
+        });
+
+    // the seam runs down the -Z side of the cone; at y = 0.5, the radius is 0.5
+    let before = Point3D::new(-0.001, 0.5, -0.5);
+    let after = Point3D::new(0.001, 0.5, -0.5);
+
+    assert!(cone.shape().uv_at(before).0 > 0.99);
+    assert!(cone.shape().uv_at(after).0 < 0.01);
+    assert_eq!(cone.raw_colour_at(before), Colour::BLACK);
+    assert_eq!(cone.raw_colour_at(after), Colour::WHITE);
+}
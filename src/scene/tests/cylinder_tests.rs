@@ -1,6 +1,6 @@
 use super::*;
 use crate::core::{Normal3D, Point3D, Ray, Vector3D, VectorMaths};
-use std::f64::consts::FRAC_1_SQRT_2;
+use std::f64::consts::{FRAC_1_SQRT_2, PI};
 
 #[test]
 fn a_ray_that_misses_an_infinite_cylinder_should_not_intersect() {
@@ -178,6 +178,54 @@ fn the_normal_vector_on_a_cap_should_either_be_pos_y_axis_or_neg_y_axis() {
     })
 }
 
+#[test]
+fn a_ray_entering_the_open_top_of_a_cylinder_capped_at_the_bottom_should_only_be_stopped_by_the_bottom(
+) {
+    let cup = Object::cylinder()
+        .min_y(1.0)
+        .max_y(2.0)
+        .capped_bottom()
+        .build();
+
+    let ray = Ray::new(Point3D::new(0.0, 3.0, 0.0), Normal3D::NEGATIVE_Y);
+    let intersections = cup.intersect(&ray);
+    assert_eq!(intersections.len(), 1);
+    assert_eq!(intersections.get(0).unwrap().t, 2.0);
+    assert_eq!(
+        cup.normal_at(Point3D::new(0.0, 1.0, 0.0)),
+        Normal3D::NEGATIVE_Y
+    );
+}
+
+#[test]
+fn a_ray_entering_the_closed_top_of_a_cylinder_capped_at_the_top_should_pass_through_the_open_bottom(
+) {
+    let cylinder = Object::cylinder()
+        .min_y(1.0)
+        .max_y(2.0)
+        .capped_top()
+        .build();
+
+    let ray = Ray::new(Point3D::new(0.0, 3.0, 0.0), Normal3D::NEGATIVE_Y);
+    let intersections = cylinder.intersect(&ray);
+    assert_eq!(intersections.len(), 1);
+    assert_eq!(intersections.get(0).unwrap().t, 1.0);
+}
+
+#[test]
+fn the_open_end_of_a_cylinder_capped_at_one_end_should_have_the_normal_of_the_wall() {
+    let cup = Object::cylinder()
+        .min_y(1.0)
+        .max_y(2.0)
+        .capped_bottom()
+        .build();
+
+    assert_eq!(
+        cup.normal_at(Point3D::new(1.0, 2.0, 0.0)),
+        Normal3D::POSITIVE_X
+    );
+}
+
 #[rustfmt::skip]
 #[test]
 fn uv_mapping_a_unit_cylinder_should_project_points_on_the_sides_onto_a_plane() {
@@ -225,3 +273,79 @@ fn uv_mapping_the_caps_of_a_capped_cylinder_should_project_points_onto_a_circle_
             assert_eq!(cylinder.shape().uv_at(point), (u, v));
         })
 }
+
+mod uv_seam {
+    use super::*;
+    use crate::core::Colour;
+    use std::num::NonZeroUsize;
+
+    fn checkered_cylinder(columns: usize) -> Object {
+        Object::cylinder()
+            .min_y(0.0)
+            .max_y(1.0)
+            .build()
+            .with_material(Material {
+                kind: MaterialKind::Uv(UvPattern::checkers(
+                    Colour::WHITE,
+                    Colour::BLACK,
+                    NonZeroUsize::new(columns).unwrap(),
+                    NonZeroUsize::new(1).unwrap(),
+                )),
+                ..Default::default()
+            })
+    }
+
+    /// a point on the side of the cylinder, where an angle of 0 is the seam (i.e. the point on the -Z axis)
+    fn around(angle: f64) -> Point3D {
+        Point3D::new(angle.sin(), 0.5, -angle.cos())
+    }
+
+    #[test]
+    fn the_u_coordinate_should_wrap_from_1_to_0_across_the_seam() {
+        let cylinder = checkered_cylinder(8);
+
+        let (before, _) = cylinder.shape().uv_at(around(-0.001));
+        let (after, _) = cylinder.shape().uv_at(around(0.001));
+        assert!(before > 0.999 && before < 1.0, "{}", before);
+        assert!(after > 0.0 && after < 0.001, "{}", after);
+    }
+
+    #[test]
+    fn checker_columns_either_side_of_the_seam_should_alternate() {
+        let cylinder = checkered_cylinder(8);
+
+        // the last column (7) and the first column (0) are adjacent, and have different parities
+        assert_eq!(cylinder.raw_colour_at(around(-0.001)), Colour::BLACK);
+        assert_eq!(cylinder.raw_colour_at(around(0.001)), Colour::WHITE);
+        // exactly on the seam, both signs of zero should fall in the first column
+        assert_eq!(
+            cylinder.raw_colour_at(Point3D::new(0.0, 0.5, -1.0)),
+            Colour::WHITE
+        );
+        assert_eq!(
+            cylinder.raw_colour_at(Point3D::new(-0.0, 0.5, -1.0)),
+            Colour::WHITE
+        );
+    }
+
+    #[test]
+    fn checker_columns_should_tile_the_full_circumference_evenly() {
+        let cylinder = checkered_cylinder(8);
+        let samples = 800;
+
+        let colours = (0..samples)
+            .map(|i| (i as f64 + 0.5) / samples as f64 * 2.0 * PI)
+            .map(|angle| cylinder.raw_colour_at(around(angle)))
+            .collect::<Vec<_>>();
+
+        // includes the pair across the seam, from the last sample back to the first
+        let boundaries = (0..samples)
+            .filter(|&i| colours[i] != colours[(i + 1) % samples])
+            .collect::<Vec<_>>();
+
+        assert_eq!(boundaries.len(), 8, "{:?}", boundaries);
+        boundaries.windows(2).for_each(|pair| {
+            assert_eq!(pair[1] - pair[0], samples / 8, "{:?}", boundaries);
+        });
+    }
+}
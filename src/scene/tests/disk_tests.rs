@@ -0,0 +1,90 @@
+use super::*;
+use crate::core::{Normal3D, Point3D, Ray, Transform, Vector3D, VectorMaths};
+use approx::*;
+use std::f64::consts::PI;
+
+fn intersection_ts(object: &Object, ray: Ray) -> Vec<f64> {
+    object.intersect(&ray).iter().map(|i| i.t).collect()
+}
+
+#[test]
+fn a_ray_hitting_a_disk_within_its_radius_should_intersect_once() {
+    let disk = Object::disk(2.0).build();
+
+    vec![
+        (Point3D::new(0.0, 1.0, 0.0), Normal3D::NEGATIVE_Y, 1.0),
+        (Point3D::new(1.5, -3.0, -1.0), Normal3D::POSITIVE_Y, 3.0),
+        (Point3D::new(2.0, 5.0, 0.0), Normal3D::NEGATIVE_Y, 5.0),
+    ]
+    .into_iter()
+    .for_each(|(origin, direction, t)| {
+        assert_eq!(intersection_ts(&disk, Ray::new(origin, direction)), vec![t]);
+    });
+}
+
+#[test]
+fn a_ray_passing_outside_the_radius_of_a_disk_should_not_intersect() {
+    let disk = Object::disk(2.0).build();
+
+    vec![
+        Ray::new(Point3D::new(2.5, 1.0, 0.0), Normal3D::NEGATIVE_Y),
+        Ray::new(Point3D::new(1.5, 1.0, 1.5), Normal3D::NEGATIVE_Y),
+        Ray::new(
+            Point3D::new(0.0, 1.0, 0.0),
+            Vector3D::new(1.0, -0.1, 0.0).normalised(),
+        ),
+    ]
+    .into_iter()
+    .for_each(|ray| assert!(disk.intersect(&ray).is_empty(), "{:?}", ray));
+}
+
+#[test]
+fn a_ray_parallel_to_a_disk_should_not_intersect() {
+    let disk = Object::disk(2.0).build();
+    let ray = Ray::new(Point3D::new(-5.0, 0.0, 0.0), Normal3D::POSITIVE_X);
+
+    assert!(disk.intersect(&ray).is_empty());
+}
+
+#[test]
+fn a_ray_passing_through_the_hole_of_an_annulus_should_not_intersect() {
+    let ring = Object::disk(2.0).inner_radius(1.0).build();
+
+    let through_hole = Ray::new(Point3D::new(0.5, 1.0, 0.5), Normal3D::NEGATIVE_Y);
+    assert!(ring.intersect(&through_hole).is_empty());
+
+    let through_ring = Ray::new(Point3D::new(0.0, 1.0, 1.5), Normal3D::NEGATIVE_Y);
+    assert_eq!(intersection_ts(&ring, through_ring), vec![1.0]);
+}
+
+#[test]
+fn the_normal_of_a_disk_should_always_point_up() {
+    let disk = Object::disk(1.0).build();
+
+    assert_eq!(
+        disk.normal_at(Point3D::new(0.5, 0.0, -0.5)),
+        Normal3D::POSITIVE_Y
+    );
+
+    let wall = Object::disk(1.0)
+        .build()
+        .transformed(Transform::identity().rotate_x(PI / 2.0));
+    assert_abs_diff_eq!(
+        wall.normal_at(Point3D::new(0.5, 0.0, 0.0)),
+        Normal3D::POSITIVE_Z
+    );
+}
+
+#[test]
+fn the_bounds_of_a_disk_should_be_flat_and_contain_the_radius() {
+    let disk = Object::disk(3.0).inner_radius(1.0).build();
+
+    assert_eq!(disk.bounds.min(), Point3D::new(-3.0, 0.0, -3.0));
+    assert_eq!(disk.bounds.max(), Point3D::new(3.0, 0.0, 3.0));
+}
+
+#[test]
+#[should_panic(expected = "inner radius")]
+fn a_disk_with_an_inner_radius_larger_than_its_radius_should_not_be_constructable() {
+    Object::disk(1.0).inner_radius(2.0).build();
+}
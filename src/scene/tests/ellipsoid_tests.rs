@@ -0,0 +1,106 @@
+use super::*;
+use crate::core::{Normal3D, Point3D, Ray, Transform, Vector3D, VectorMaths};
+use approx::*;
+
+fn ellipsoid() -> Object {
+    Object::ellipsoid(3.0, 2.0, 0.5)
+}
+
+fn intersection_ts(object: &Object, ray: Ray) -> Vec<f64> {
+    object.intersect(&ray).iter().map(|i| i.t).collect()
+}
+
+#[test]
+fn a_ray_along_each_semi_axis_should_hit_the_ellipsoid_at_the_end_of_the_axis() {
+    vec![
+        (
+            Ray::new(Point3D::new(-10.0, 0.0, 0.0), Normal3D::POSITIVE_X),
+            vec![7.0, 13.0],
+            "x",
+        ),
+        (
+            Ray::new(Point3D::new(0.0, 10.0, 0.0), Normal3D::NEGATIVE_Y),
+            vec![8.0, 12.0],
+            "y",
+        ),
+        (
+            Ray::new(Point3D::new(0.0, 0.0, -10.0), Normal3D::POSITIVE_Z),
+            vec![9.5, 10.5],
+            "z",
+        ),
+    ]
+    .into_iter()
+    .for_each(|(ray, expected, axis)| {
+        let ts = intersection_ts(&ellipsoid(), ray);
+        assert_eq!(ts.len(), 2, "{}", axis);
+        assert_abs_diff_eq!(ts[0], expected[0], epsilon = 1e-9);
+        assert_abs_diff_eq!(ts[1], expected[1], epsilon = 1e-9);
+    });
+}
+
+#[test]
+fn a_ray_passing_beside_the_ellipsoid_should_miss() {
+    vec![
+        Ray::new(Point3D::new(0.0, 2.01, -10.0), Normal3D::POSITIVE_Z),
+        Ray::new(Point3D::new(3.01, 0.0, -10.0), Normal3D::POSITIVE_Z),
+        Ray::new(Point3D::new(-10.0, 0.0, 0.51), Normal3D::POSITIVE_X),
+    ]
+    .into_iter()
+    .for_each(|ray| assert!(ellipsoid().intersect(&ray).is_empty(), "{:?}", ray));
+}
+
+#[test]
+fn the_normal_at_the_end_of_each_semi_axis_should_point_along_the_axis() {
+    let ellipsoid = ellipsoid();
+
+    vec![
+        (Point3D::new(3.0, 0.0, 0.0), Normal3D::POSITIVE_X),
+        (Point3D::new(-3.0, 0.0, 0.0), Normal3D::NEGATIVE_X),
+        (Point3D::new(0.0, 2.0, 0.0), Normal3D::POSITIVE_Y),
+        (Point3D::new(0.0, -2.0, 0.0), Normal3D::NEGATIVE_Y),
+        (Point3D::new(0.0, 0.0, 0.5), Normal3D::POSITIVE_Z),
+        (Point3D::new(0.0, 0.0, -0.5), Normal3D::NEGATIVE_Z),
+    ]
+    .into_iter()
+    .for_each(|(point, expected)| assert_eq!(ellipsoid.normal_at(point), expected, "{:?}", point));
+}
+
+#[test]
+fn the_normal_should_be_the_gradient_of_the_surface() {
+    let ellipsoid = Object::ellipsoid(2.0, 1.0, 1.0);
+    // x²/4 + y² = 1
+    let point = Point3D::new(2.0_f64.sqrt(), 0.5_f64.sqrt(), 0.0);
+
+    assert_abs_diff_eq!(
+        ellipsoid.normal_at(point),
+        Vector3D::new(2.0_f64.sqrt() / 2.0, 2.0 * 0.5_f64.sqrt(), 0.0).normalised()
+    );
+}
+
+#[test]
+fn the_normal_should_match_a_non_uniformly_scaled_sphere() {
+    let ellipsoid = ellipsoid();
+    let sphere =
+        Object::sphere().transformed(Transform::identity().scale_x(3.0).scale_y(2.0).scale_z(0.5));
+    let ray = Ray::new(
+        Point3D::new(1.0, 0.5, -5.0),
+        Vector3D::new(-0.1, 0.05, 1.0).normalised(),
+    );
+
+    let hit = ellipsoid.intersect(&ray).hit(None).unwrap();
+    let point = ray.position(hit.t);
+    assert_abs_diff_eq!(
+        sphere.intersect(&ray).hit(None).unwrap().t,
+        hit.t,
+        epsilon = 1e-9
+    );
+    assert_abs_diff_eq!(ellipsoid.normal_at(point), sphere.normal_at(point));
+}
+
+#[test]
+fn the_bounds_of_an_ellipsoid_should_be_its_semi_axes() {
+    assert_eq!(
+        ellipsoid().bounds,
+        BoundingBox::new(Point3D::new(-3.0, -2.0, -0.5), Point3D::new(3.0, 2.0, 0.5))
+    );
+}
@@ -315,3 +315,117 @@ fn an_intersection_with_the_same_object_at_a_non_zero_t_should_be_a_hit() {
     assert!(hit.is_some());
     assert_eq!(hit.unwrap().t, 2.0);
 }
+
+#[test]
+fn joining_more_intersections_than_fit_inline_should_keep_every_intersection_in_order() {
+    let sphere = Object::sphere();
+    let ts = |intersections: &Intersections| intersections.iter().map(|i| i.t).collect::<Vec<_>>();
+
+    let mut intersections = Intersections::pair(
+        Intersection::new(1.0, &sphere),
+        Intersection::new(5.0, &sphere),
+    )
+    .join(Intersections::pair(
+        Intersection::new(-2.0, &sphere),
+        Intersection::new(3.0, &sphere),
+    ));
+    assert_eq!(ts(&intersections), vec![-2.0, 1.0, 3.0, 5.0]);
+
+    intersections.append(Intersections::pair(
+        Intersection::new(4.0, &sphere),
+        Intersection::new(0.5, &sphere),
+    ));
+    intersections.push(Intersection::new(2.0, &sphere));
+    assert_eq!(ts(&intersections), vec![-2.0, 0.5, 1.0, 2.0, 3.0, 4.0, 5.0]);
+    assert_eq!(intersections.hit(None).unwrap().t, 0.5);
+}
+
+#[test]
+fn a_hit_on_the_last_object_within_the_bias_should_be_ignored() {
+    let first = Object::sphere();
+    let second = Object::sphere();
+    let intersections = Intersections::of(vec![
+        Intersection::new(0.001, &first),
+        Intersection::new(0.002, &second),
+    ]);
+
+    assert_eq!(intersections.hit(Some(first.id())).unwrap().t, 0.001);
+    assert_eq!(
+        intersections.hit_beyond(Some(first.id()), 0.01).unwrap().t,
+        0.002
+    );
+    assert_eq!(intersections.hit_beyond(None, 0.01).unwrap().t, 0.001);
+}
+
+#[test]
+fn iterating_over_intersections_by_reference_should_not_consume_them() {
+    let sphere = Object::sphere();
+    let intersections = Intersections::of(vec![
+        Intersection::new(2.0, &sphere),
+        Intersection::new(-1.0, &sphere),
+        Intersection::new(1.0, &sphere),
+    ]);
+
+    let first_pass = intersections.iter().map(|i| i.t).collect::<Vec<_>>();
+    let second_pass = intersections.iter().map(|i| i.t).collect::<Vec<_>>();
+
+    assert_eq!(first_pass, vec![-1.0, 1.0, 2.0]);
+    assert_eq!(first_pass, second_pass);
+    assert_eq!(intersections.len(), 3);
+}
+
+#[test]
+fn all_hits_should_include_every_intersection_in_front_of_the_ray_in_order() {
+    let glass = Material {
+        transparency: 1.0,
+        refractive: 1.5,
+        ..Default::default()
+    };
+    let front = Object::sphere().with_material(glass.clone());
+    let overlapping = Object::sphere()
+        .with_material(glass.clone())
+        .transformed(Transform::identity().translate_z(1.0));
+    let behind = Object::sphere()
+        .with_material(glass)
+        .transformed(Transform::identity().translate_z(-10.0));
+
+    let ray = Ray::new(Point3D::new(0.0, 0.0, -5.0), Normal3D::POSITIVE_Z);
+    let intersections = front
+        .intersect(&ray)
+        .join(overlapping.intersect(&ray))
+        .join(behind.intersect(&ray));
+    assert_eq!(intersections.len(), 6);
+
+    let hits = intersections
+        .all_hits()
+        .map(|hit| (hit.t, hit.with.id()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        hits,
+        vec![
+            (4.0, front.id()),
+            (5.0, overlapping.id()),
+            (6.0, front.id()),
+            (7.0, overlapping.id()),
+        ]
+    );
+}
+
+#[test]
+fn the_reflectance_of_synthetic_hit_data_should_depend_only_on_the_given_refractive_indices() {
+    let sphere = Object::sphere();
+    let hit_data = HitData::synthetic(
+        &sphere,
+        Point3D::new(0.0, 0.0, -1.0),
+        Normal3D::NEGATIVE_Z,
+        Normal3D::NEGATIVE_Z,
+        (1.0, 1.5),
+    );
+
+    let reflection = hit_data.reflection();
+    assert!(!reflection.is_total());
+    assert_abs_diff_eq!(
+        reflection.reflectance(hit_data.entered_refractive, hit_data.exited_refractive),
+        0.04
+    );
+}
@@ -0,0 +1,37 @@
+use super::*;
+use crate::core::Colour;
+use approx::*;
+
+#[test]
+fn glass_should_be_transparent_with_the_refractive_index_of_glass() {
+    let glass = Material::glass();
+
+    assert!(glass.transparency > 0.0);
+    assert_abs_diff_eq!(glass.refractive, 1.5);
+}
+
+#[test]
+fn a_mirror_should_be_fully_reflective_and_opaque() {
+    let mirror = Material::mirror();
+
+    assert_eq!(mirror.reflective, 1.0);
+    assert_eq!(mirror.transparency, 0.0);
+}
+
+#[test]
+fn a_matte_material_should_have_the_given_colour_with_no_highlights_or_reflections() {
+    let matte = Material::matte(Colour::RED);
+
+    assert_eq!(matte.kind, MaterialKind::Solid(Colour::RED));
+    assert_eq!(matte.specular, 0.0);
+    assert_eq!(matte.reflective, 0.0);
+}
+
+#[test]
+fn a_metal_should_have_the_given_colour_and_be_partially_reflective() {
+    let metal = Material::metal(Colour::BLUE);
+
+    assert_eq!(metal.kind, MaterialKind::Solid(Colour::BLUE));
+    assert!(metal.reflective > 0.0 && metal.reflective < 1.0);
+    assert_eq!(metal.transparency, 0.0);
+}
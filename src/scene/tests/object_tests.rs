@@ -202,7 +202,7 @@ mod shape_tests {
 
 mod group_tests {
     use super::*;
-    use crate::core::{Normal3D, Point3D, Ray, Transform, Vector3D, VectorMaths};
+    use crate::core::{Normal3D, Point3D, Ray, Transform, Vector3D, VectorMaths, EPSILON};
     use approx::*;
     use std::f64::consts::PI;
 
@@ -237,6 +237,107 @@ mod group_tests {
         assert_eq!(intersections.get(3).unwrap().with.id(), first_id);
     }
 
+    #[test]
+    fn intersecting_the_nearest_children_of_a_group_should_skip_children_beyond_the_nearest_hit() {
+        let near = Object::sphere();
+        let near_id = near.id();
+
+        let group = Object::group(vec![
+            Object::sphere().transformed(Transform::identity().translate_z(6.0)),
+            near,
+            Object::sphere().transformed(Transform::identity().translate_z(3.0)),
+        ])
+        .bvh(1);
+        let ray = Ray::new(Point3D::new(0.0, 0.0, -5.0), Normal3D::POSITIVE_Z);
+
+        let intersections = group.intersect_nearest(&ray);
+        assert_eq!(intersections.len(), 2);
+        assert!(intersections.iter().all(|i| i.with.id() == near_id));
+
+        assert_eq!(group.intersect(&ray).len(), 6);
+    }
+
+    #[test]
+    fn intersecting_the_nearest_children_of_a_group_should_skip_children_behind_the_ray() {
+        let group = Object::group(vec![
+            Object::sphere().transformed(Transform::identity().translate_z(-10.0)),
+            Object::sphere(),
+        ]);
+        let ray = Ray::new(Point3D::new(0.0, 0.0, -5.0), Normal3D::POSITIVE_Z);
+
+        let ts = group
+            .intersect_nearest(&ray)
+            .iter()
+            .map(|i| i.t)
+            .collect::<Vec<_>>();
+        assert_eq!(ts, vec![4.0, 6.0]);
+    }
+
+    #[test]
+    fn intersecting_the_nearest_children_of_a_group_should_include_every_intersection_before_the_hit(
+    ) {
+        // the ray starts inside the outer sphere, which should still be included so that the refractive indices are correct
+        let group = Object::group(vec![
+            Object::sphere().transformed(Transform::identity().scale_all(10.0)),
+            Object::sphere(),
+        ]);
+        let ray = Ray::new(Point3D::new(0.0, 0.0, -5.0), Normal3D::POSITIVE_Z);
+
+        let ts = group
+            .intersect_nearest(&ray)
+            .iter()
+            .map(|i| i.t)
+            .collect::<Vec<_>>();
+        assert_eq!(ts.len(), 4);
+        assert_abs_diff_eq!(ts[0], -5.0, epsilon = 1e-9);
+        assert_abs_diff_eq!(ts[1], 4.0);
+        assert_abs_diff_eq!(ts[2], 6.0);
+        assert_abs_diff_eq!(ts[3], 15.0, epsilon = 1e-9);
+    }
+
+    #[test]
+    fn intersecting_the_nearest_children_of_a_group_should_not_skip_children_beyond_an_ignored_hit_on_the_last_object(
+    ) {
+        let last = Object::sphere();
+        let last_id = last.id();
+
+        let group = Object::group(vec![
+            last,
+            Object::sphere().transformed(Transform::identity().translate_z(5.0)),
+        ]);
+        // the ray starts on the surface of the last object hit, so the hit at zero is ignored
+        let ray = Ray::new(Point3D::new(0.0, 0.0, 1.0), Normal3D::POSITIVE_Z);
+
+        let hit = group
+            .intersect_nearest_beyond(&ray, Some(last_id), EPSILON)
+            .hit(Some(last_id))
+            .unwrap();
+        assert_ne!(hit.with.id(), last_id);
+        assert_abs_diff_eq!(hit.t, 3.0);
+    }
+
+    #[test]
+    fn intersecting_the_nearest_children_of_a_group_should_not_skip_children_beyond_a_hit_on_the_last_object_within_the_bias(
+    ) {
+        let last = Object::sphere();
+        let last_id = last.id();
+
+        let group = Object::group(vec![
+            last,
+            Object::sphere().transformed(Transform::identity().translate_z(5.0)),
+        ]);
+        // the ray starts just inside the last object hit, so it hits that object again beyond `EPSILON`, but within the bias
+        let ray = Ray::new(Point3D::new(0.0, 0.0, 1.0 - 5e-4), Normal3D::POSITIVE_Z);
+        let bias = 1e-3;
+
+        let hit = group
+            .intersect_nearest_beyond(&ray, Some(last_id), bias)
+            .hit_beyond(Some(last_id), bias)
+            .unwrap();
+        assert_ne!(hit.with.id(), last_id);
+        assert_abs_diff_eq!(hit.t, 3.0005, epsilon = 1e-9);
+    }
+
     #[test]
     fn a_ray_should_intersect_the_children_of_a_transformed_group() {
         let group = Object::group(vec![
@@ -278,6 +379,137 @@ mod group_tests {
     }
 }
 
+mod clipping {
+    use super::*;
+    use crate::core::{Normal3D, Point3D, Ray, Transform};
+
+    #[test]
+    fn an_object_beyond_its_clip_distance_should_not_be_intersected() {
+        let sphere = Object::sphere()
+            .transformed(Transform::identity().translate_z(10.0))
+            .with_clip_distance(5.0);
+        let ray = Ray::new(Point3D::ORIGIN, Normal3D::POSITIVE_Z);
+
+        assert!(sphere.intersect(&ray).is_empty());
+    }
+
+    #[test]
+    fn an_object_partially_beyond_its_clip_distance_should_only_be_intersected_before_the_clip_distance(
+    ) {
+        let sphere = Object::sphere().with_clip_distance(5.0);
+        let ray = Ray::new(Point3D::new(0.0, 0.0, -5.0), Normal3D::POSITIVE_Z);
+
+        let intersections = sphere.intersect(&ray);
+        assert_eq!(intersections.len(), 1);
+        assert_eq!(intersections.get(0).unwrap().t, 4.0);
+    }
+
+    #[test]
+    fn clipping_a_group_should_clip_its_children() {
+        let group = Object::group(vec![
+            Object::sphere(),
+            Object::sphere().transformed(Transform::identity().translate_z(10.0)),
+        ])
+        .with_clip_distance(8.0);
+        let ray = Ray::new(Point3D::new(0.0, 0.0, -5.0), Normal3D::POSITIVE_Z);
+
+        let intersections = group.intersect(&ray);
+        assert_eq!(intersections.len(), 2);
+        assert_eq!(intersections.get(0).unwrap().t, 4.0);
+        assert_eq!(intersections.get(1).unwrap().t, 6.0);
+    }
+}
+
+mod triangle_strips_and_fans {
+    use super::*;
+    use crate::core::{Normal3D, Point3D, Ray};
+
+    #[test]
+    fn a_triangle_strip_should_contain_a_triangle_for_each_point_after_the_first_two() {
+        let strip = Object::triangle_strip(vec![
+            Point3D::new(0.0, 0.0, 0.0),
+            Point3D::new(0.0, 1.0, 0.0),
+            Point3D::new(1.0, 0.0, 0.0),
+            Point3D::new(1.0, 1.0, 0.0),
+            Point3D::new(2.0, 0.0, 0.0),
+        ]);
+
+        assert_eq!(strip.children().len(), 3);
+    }
+
+    #[test]
+    fn every_triangle_in_a_strip_should_have_the_same_winding() {
+        let strip = Object::triangle_strip(vec![
+            Point3D::new(0.0, 0.0, 0.0),
+            Point3D::new(0.0, 1.0, 0.0),
+            Point3D::new(1.0, 0.0, 0.0),
+            Point3D::new(1.0, 1.0, 0.0),
+            Point3D::new(2.0, 0.0, 0.0),
+        ]);
+
+        strip.children().iter().for_each(|triangle| {
+            assert_eq!(triangle.normal_at(Point3D::ORIGIN), Normal3D::POSITIVE_Z);
+        });
+    }
+
+    #[test]
+    fn a_ray_should_intersect_a_triangle_strip_between_any_three_consecutive_points() {
+        let strip = Object::triangle_strip(vec![
+            Point3D::new(0.0, 0.0, 0.0),
+            Point3D::new(0.0, 1.0, 0.0),
+            Point3D::new(1.0, 0.0, 0.0),
+            Point3D::new(1.0, 1.0, 0.0),
+            Point3D::new(2.0, 0.0, 0.0),
+        ]);
+
+        vec![(0.25, 0.25), (0.75, 0.75), (1.25, 0.25)]
+            .into_iter()
+            .for_each(|(x, y)| {
+                let ray = Ray::new(Point3D::new(x, y, -1.0), Normal3D::POSITIVE_Z);
+                assert_eq!(strip.intersect(&ray).len(), 1, "{:?}", (x, y));
+            });
+    }
+
+    #[test]
+    fn a_triangle_strip_with_fewer_than_three_points_should_be_empty() {
+        let strip = Object::triangle_strip(vec![Point3D::ORIGIN, Point3D::new(1.0, 0.0, 0.0)]);
+
+        assert!(strip.children().is_empty());
+    }
+
+    #[test]
+    fn a_triangle_fan_should_contain_a_triangle_for_each_pair_of_consecutive_rim_points() {
+        let fan = Object::triangle_fan(
+            Point3D::ORIGIN,
+            vec![
+                Point3D::new(1.0, 0.0, 0.0),
+                Point3D::new(0.0, 1.0, 0.0),
+                Point3D::new(-1.0, 0.0, 0.0),
+                Point3D::new(0.0, -1.0, 0.0),
+            ],
+        );
+
+        assert_eq!(fan.children().len(), 3);
+    }
+
+    #[test]
+    fn every_triangle_in_a_fan_should_have_the_same_winding() {
+        let fan = Object::triangle_fan(
+            Point3D::ORIGIN,
+            vec![
+                Point3D::new(1.0, 0.0, 0.0),
+                Point3D::new(0.0, 1.0, 0.0),
+                Point3D::new(-1.0, 0.0, 0.0),
+                Point3D::new(0.0, -1.0, 0.0),
+            ],
+        );
+
+        fan.children().iter().for_each(|triangle| {
+            assert_eq!(triangle.normal_at(Point3D::ORIGIN), Normal3D::NEGATIVE_Z);
+        });
+    }
+}
+
 mod constructive_solid_geometry {
     use super::*;
     use crate::core::{Normal3D, Point3D, Ray, Transform};
@@ -399,6 +631,73 @@ mod constructive_solid_geometry {
         assert_eq!(intersections.len(), 2);
         assert_eq!(intersections.get(0).unwrap().with.id, first_id);
     }
+
+    #[test]
+    fn a_csg_should_render_identically_regardless_of_the_order_its_children_were_created_in() {
+        use crate::core::Colour;
+        use crate::renderer::{self, Camera, Samples};
+        use std::f64::consts::FRAC_PI_3;
+
+        fn render(csg: Object) -> crate::renderer::Canvas {
+            let mut world = World::empty();
+            world.lights = vec![Light::point(
+                Colour::WHITE,
+                Point3D::new(-10.0, 10.0, -10.0),
+            )];
+            world.add(csg);
+
+            let camera = Camera::new(
+                nonzero_ext::nonzero!(20u16),
+                nonzero_ext::nonzero!(20u16),
+                FRAC_PI_3,
+                Transform::view_transform(
+                    Point3D::new(1.0, 2.0, -5.0),
+                    Point3D::ORIGIN,
+                    Normal3D::POSITIVE_Y,
+                ),
+            );
+
+            renderer::render(&world, &camera, &Samples::single(), false)
+        }
+
+        fn cube() -> Object {
+            Object::cube().with_material(Material {
+                kind: MaterialKind::Solid(Colour::new(1.0, 0.2, 0.2)),
+                ..Default::default()
+            })
+        }
+
+        fn sphere() -> Object {
+            Object::sphere()
+                .with_material(Material {
+                    kind: MaterialKind::Solid(Colour::new(0.2, 0.2, 1.0)),
+                    ..Default::default()
+                })
+                .transformed(Transform::identity().scale_all(1.3))
+        }
+
+        let cube_first = {
+            let cube = cube();
+            let sphere = sphere();
+            Object::csg_difference(cube, sphere)
+        };
+        let sphere_first = {
+            let sphere = sphere();
+            let cube = cube();
+            Object::csg_difference(cube, sphere)
+        };
+        let (left, right) = cube_first.csg_children();
+        assert!(left.id < right.id);
+        let (left, right) = sphere_first.csg_children();
+        assert!(left.id > right.id);
+
+        let expected = render(cube_first);
+        let actual = render(sphere_first);
+
+        (0..20).for_each(|y| {
+            (0..20).for_each(|x| assert_eq!(actual.get(x, y), expected.get(x, y), "{:?}", (x, y)))
+        });
+    }
 }
 
 mod bounding_boxes {
@@ -528,6 +827,30 @@ mod bounding_boxes {
         assert_eq!(csg.bounds.min(), Point3D::new(-1.0, -1.0, -1.0));
         assert_eq!(csg.bounds.max(), Point3D::new(3.0, 4.0, 5.0));
     }
+
+    #[test]
+    fn the_world_bounds_of_a_translated_sphere_should_be_centred_on_the_translation() {
+        let sphere = Object::sphere().transformed(Transform::identity().translate_x(5.0));
+
+        assert_eq!(
+            sphere.bounds_world(),
+            (Point3D::new(4.0, -1.0, -1.0), Point3D::new(6.0, 1.0, 1.0))
+        );
+    }
+
+    #[test]
+    fn the_world_bounds_of_a_group_should_contain_every_transformed_child() {
+        let group = Object::group(vec![
+            Object::sphere().transformed(Transform::identity().translate_y(-3.0)),
+            Object::cube().transformed(Transform::identity().scale_z(2.0)),
+        ])
+        .transformed(Transform::identity().translate_x(1.0));
+
+        assert_eq!(
+            group.bounds_world(),
+            (Point3D::new(0.0, -4.0, -2.0), Point3D::new(2.0, 1.0, 2.0))
+        );
+    }
 }
 
 mod optimising_groups {
@@ -705,3 +1028,416 @@ mod optimising_groups {
         assert_eq!(optimised.children()[1].id, second_id);
     }
 }
+
+mod bounding_volume_hierarchy {
+    use super::*;
+    use crate::core::{Normal3D, Point3D, Ray, Transform, VectorMaths};
+    use crate::wavefront_parser::WavefrontParser;
+    use std::path::Path;
+
+    fn spheres_along_x(count: usize) -> Vec<Object> {
+        (0..count)
+            .map(|x| {
+                Object::sphere().transformed(Transform::identity().translate_x(x as f64 * 3.0))
+            })
+            .collect()
+    }
+
+    /// the number of shapes in each leaf group, depth-first
+    fn leaf_sizes(object: &Object) -> Vec<usize> {
+        if object.children().iter().all(|child| child.is_shape()) {
+            vec![object.children().len()]
+        } else {
+            object
+                .children()
+                .iter()
+                .filter(|child| !child.is_shape())
+                .flat_map(leaf_sizes)
+                .collect()
+        }
+    }
+
+    #[test]
+    fn building_a_bvh_should_split_a_group_in_half_along_the_longest_axis() {
+        let spheres = spheres_along_x(8);
+        let ids = spheres.iter().map(|s| s.id).collect::<Vec<_>>();
+
+        let bvh = Object::group(spheres).bvh(2);
+
+        assert_eq!(bvh.children().len(), 2);
+        let (left, right) = (&bvh.children()[0], &bvh.children()[1]);
+
+        let ids_in = |group: &Object| {
+            let mut ids = group
+                .children()
+                .iter()
+                .flat_map(|child| child.children().iter().map(|c| c.id))
+                .collect::<Vec<_>>();
+            ids.sort_unstable();
+            ids
+        };
+        assert_eq!(ids_in(left), ids[..4].to_vec());
+        assert_eq!(ids_in(right), ids[4..].to_vec());
+
+        assert_eq!(left.bounds.min(), Point3D::new(-1.0, -1.0, -1.0));
+        assert_eq!(left.bounds.max(), Point3D::new(10.0, 1.0, 1.0));
+    }
+
+    #[test]
+    fn every_leaf_of_a_bvh_should_contain_no_more_than_the_leaf_size() {
+        let bvh = Object::group(spheres_along_x(37)).bvh(4);
+
+        let sizes = leaf_sizes(&bvh);
+        assert!(sizes.iter().all(|&size| size <= 4), "{:?}", sizes);
+        assert_eq!(sizes.iter().sum::<usize>(), 37);
+    }
+
+    #[test]
+    fn building_a_bvh_should_leave_unbounded_shapes_at_the_top_level() {
+        let plane = Object::plane();
+        let plane_id = plane.id;
+
+        let mut children = spheres_along_x(4);
+        children.push(plane);
+        let bvh = Object::group(children).bvh(2);
+
+        assert_eq!(bvh.children().len(), 3);
+        assert_eq!(bvh.children()[0].id, plane_id);
+        assert!(!bvh.children()[1].bounds.is_unbounded());
+        assert!(!bvh.children()[2].bounds.is_unbounded());
+    }
+
+    #[test]
+    fn building_a_bvh_should_rebuild_the_children_of_a_csg() {
+        let csg = Object::csg_difference(
+            Object::group(spheres_along_x(4)),
+            Object::group(spheres_along_x(4)),
+        );
+
+        let bvh = csg.bvh(2);
+        let (left, right) = bvh.csg_children();
+        assert_eq!(leaf_sizes(left), vec![2, 2]);
+        assert_eq!(leaf_sizes(right), vec![2, 2]);
+    }
+
+    #[test]
+    fn a_bvh_should_produce_the_same_hit_points_as_the_optimised_group() {
+        let parser = WavefrontParser::new(Path::new(env!("CARGO_MANIFEST_DIR")).join("meshes"));
+        let optimised = parser.load("suzanne low poly.obj").unwrap().optimised(4);
+        let bvh = parser.load("suzanne low poly.obj").unwrap().bvh(4);
+
+        let hit_points = |object: &Object, ray: &Ray| {
+            let mut ts = object
+                .intersect(ray)
+                .iter()
+                .map(|i| i.t)
+                .collect::<Vec<_>>();
+            ts.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            ts.into_iter().map(|t| ray.position(t)).collect::<Vec<_>>()
+        };
+
+        let mut total_hits = 0;
+        for x in -20..=20 {
+            for y in -20..=20 {
+                let target = Point3D::new(x as f64 / 10.0, y as f64 / 10.0, 0.0);
+                let origin = Point3D::new(0.3, -0.2, -5.0);
+                let ray = Ray::new(origin, (target - origin).normalised());
+
+                let expected = hit_points(&optimised, &ray);
+                assert_eq!(hit_points(&bvh, &ray), expected, "{:?}", ray);
+                total_hits += expected.len();
+            }
+        }
+
+        // make sure the rays actually hit the mesh
+        assert!(total_hits > 500, "{}", total_hits);
+    }
+
+    #[test]
+    fn a_single_shape_should_be_unaffected_by_building_a_bvh() {
+        let sphere = Object::sphere();
+        let id = sphere.id;
+
+        let bvh = sphere.bvh(4);
+        assert_eq!(bvh.id, id);
+        let ray = Ray::new(Point3D::new(0.0, 0.0, -5.0), Normal3D::POSITIVE_Z);
+        assert_eq!(bvh.intersect(&ray).len(), 2);
+    }
+}
+
+mod motion_blur {
+    use super::*;
+    use crate::core::{Normal3D, Point3D, Ray, Transform};
+    use approx::*;
+    use std::f64::consts::PI;
+
+    fn hit_point(object: &Object, time: f64) -> Option<Point3D> {
+        let ray = Ray::new(Point3D::new(0.5, 0.0, -5.0), Normal3D::POSITIVE_Z).with_time(time);
+
+        object
+            .intersect(&ray)
+            .hit(None)
+            .map(|hit| ray.position(hit.t))
+    }
+
+    #[test]
+    fn a_moving_sphere_should_be_hit_at_different_points_at_different_times() {
+        let sphere = Object::sphere().with_motion(Transform::identity().translate_x(2.0));
+
+        let start = hit_point(&sphere, 0.0).unwrap();
+        let middle = hit_point(&sphere, 0.25).unwrap();
+        assert_ne!(start, middle);
+        assert_abs_diff_eq!(start, Point3D::new(0.5, 0.0, -(0.75_f64.sqrt())));
+        assert_eq!(middle, Point3D::new(0.5, 0.0, -1.0));
+        // by the end, the sphere has moved out of the path of the ray
+        assert_eq!(hit_point(&sphere, 1.0), None);
+    }
+
+    #[test]
+    fn an_object_without_motion_should_be_hit_at_the_same_point_at_any_time() {
+        let sphere = Object::sphere();
+
+        assert_eq!(hit_point(&sphere, 0.0), hit_point(&sphere, 1.0));
+    }
+
+    #[test]
+    fn the_motion_should_be_applied_on_top_of_the_existing_transform() {
+        let sphere = Object::sphere()
+            .transformed(Transform::identity().translate_x(-1.5))
+            .with_motion(Transform::identity().translate_x(2.0));
+
+        assert_eq!(hit_point(&sphere, 0.0), None);
+        assert_abs_diff_eq!(
+            hit_point(&sphere, 0.75).unwrap(),
+            Point3D::new(0.5, 0.0, -(0.75_f64.sqrt()))
+        );
+    }
+
+    #[test]
+    fn the_bounds_of_a_moving_object_should_cover_its_entire_path() {
+        let sphere = Object::sphere().with_motion(Transform::identity().translate_x(2.0));
+
+        assert_eq!(
+            sphere.bounds,
+            BoundingBox::new(Point3D::new(-1.0, -1.0, -1.0), Point3D::new(3.0, 1.0, 1.0))
+        );
+    }
+
+    #[test]
+    fn every_child_of_a_moving_group_should_move() {
+        let group = Object::group(vec![
+            Object::sphere(),
+            Object::sphere().transformed(Transform::identity().translate_y(3.0)),
+        ])
+        .with_motion(Transform::identity().translate_x(2.0));
+
+        assert_eq!(
+            group.bounds,
+            BoundingBox::new(Point3D::new(-1.0, -1.0, -1.0), Point3D::new(3.0, 4.0, 1.0))
+        );
+        assert_eq!(hit_point(&group, 1.0), None);
+    }
+
+    #[test]
+    fn transforming_a_moving_object_should_also_transform_the_end_of_its_motion() {
+        let sphere = Object::sphere()
+            .with_motion(Transform::identity().translate_x(2.0))
+            .transformed(Transform::identity().translate_x(-2.0));
+
+        assert_abs_diff_eq!(
+            hit_point(&sphere, 1.0).unwrap(),
+            Point3D::new(0.5, 0.0, -(0.75_f64.sqrt()))
+        );
+    }
+
+    #[test]
+    fn a_sphere_rotating_half_a_turn_should_be_intersectable_half_way_through() {
+        let sphere = Object::sphere().with_motion(Transform::identity().rotate_y(PI));
+
+        assert_abs_diff_eq!(
+            hit_point(&sphere, 0.5).unwrap(),
+            Point3D::new(0.5, 0.0, -(0.75_f64.sqrt()))
+        );
+    }
+
+    #[test]
+    fn a_stretched_sphere_rotating_half_a_turn_should_keep_its_shape_half_way_through() {
+        let sphere = Object::sphere()
+            .transformed(Transform::identity().scale_x(2.0))
+            .with_motion(Transform::identity().rotate_y(PI));
+
+        // a quarter turn points the long axis along Z
+        assert_abs_diff_eq!(
+            hit_point(&sphere, 0.5).unwrap(),
+            Point3D::new(0.5, 0.0, -(3.0_f64.sqrt())),
+            epsilon = 1e-9
+        );
+        assert!(sphere.bounds.min().z() <= -2.0 + 1e-2);
+    }
+}
+
+mod instances {
+    use super::*;
+    use crate::core::{Colour, Normal3D, Point3D, Ray, Transform, Vector3D, VectorMaths};
+    use crate::scene::{HitData, Material, MaterialKind, Pattern};
+    use approx::*;
+    use std::sync::Arc;
+
+    #[test]
+    fn instances_of_a_shared_sphere_at_different_positions_should_each_be_intersected_without_cloning_the_sphere(
+    ) {
+        let shared = Arc::new(Object::sphere());
+        let left = Object::instance(Arc::clone(&shared), Transform::identity().translate_x(-3.0));
+        let right = Object::instance(Arc::clone(&shared), Transform::identity().translate_x(3.0));
+        assert_eq!(Arc::strong_count(&shared), 3);
+
+        let left_ray = Ray::new(Point3D::new(-3.0, 0.0, -5.0), Normal3D::POSITIVE_Z);
+        let right_ray = Ray::new(Point3D::new(3.0, 0.0, -5.0), Normal3D::POSITIVE_Z);
+
+        for (instance, ray, missed_by) in [
+            (&left, &left_ray, &right_ray),
+            (&right, &right_ray, &left_ray),
+        ] {
+            let intersections = instance.intersect(ray);
+            assert_eq!(intersections.len(), 2);
+            assert_eq!(intersections.get(0).unwrap().t, 4.0);
+            assert_eq!(intersections.get(1).unwrap().t, 6.0);
+            assert!(std::ptr::eq(
+                intersections.get(0).unwrap().with,
+                shared.as_ref()
+            ));
+
+            assert!(instance.intersect(missed_by).is_empty());
+        }
+    }
+
+    #[test]
+    fn the_normal_of_an_instance_should_be_transformed_by_the_instance_transform() {
+        let shared = Arc::new(Object::sphere());
+        let instance = Object::instance(
+            shared,
+            Transform::identity().scale_all(2.0).translate_y(5.0),
+        );
+        let ray = Ray::new(Point3D::new(-5.0, 5.0, 0.0), Normal3D::POSITIVE_X);
+
+        let intersections = instance.intersect(&ray);
+        let hit = intersections.hit(None).unwrap();
+        assert_eq!(hit.t, 3.0);
+
+        let hit_data = HitData::from(&ray, hit, intersections);
+        assert_abs_diff_eq!(hit_data.point, Point3D::new(-2.0, 5.0, 0.0));
+        assert_abs_diff_eq!(hit_data.normal, Normal3D::NEGATIVE_X);
+    }
+
+    #[test]
+    fn the_pattern_of_an_instance_should_move_with_the_instance() {
+        let shared = Arc::new(Object::sphere().with_material(Material {
+            kind: MaterialKind::Pattern(Pattern::striped(Colour::WHITE, Colour::BLACK)),
+            ..Default::default()
+        }));
+        let instance = Object::instance(shared, Transform::identity().translate_x(10.5));
+        let ray = Ray::new(Point3D::new(10.5, 0.0, -5.0), Normal3D::POSITIVE_Z);
+
+        let intersections = instance.intersect(&ray);
+        let hit = intersections.hit(None).unwrap();
+        let hit_data = HitData::from(&ray, hit, intersections);
+
+        // the hit is at x = 0 on the shared sphere, which is white, but would be black at x = 10.5
+        assert_eq!(hit_data.raw_colour(), Colour::WHITE);
+    }
+
+    #[test]
+    fn an_instance_of_an_instance_should_combine_both_transforms() {
+        let shared = Arc::new(Object::sphere());
+        let inner = Arc::new(Object::instance(
+            Arc::clone(&shared),
+            Transform::identity().translate_x(2.0),
+        ));
+        let outer = Object::instance(inner, Transform::identity().translate_y(3.0));
+        let ray = Ray::new(Point3D::new(2.0, 3.0, -5.0), Normal3D::POSITIVE_Z);
+
+        let intersections = outer.intersect(&ray);
+        assert_eq!(intersections.len(), 2);
+        assert_eq!(intersections.get(0).unwrap().t, 4.0);
+        assert_eq!(
+            outer.bounds,
+            BoundingBox::new(Point3D::new(1.0, 2.0, -1.0), Point3D::new(3.0, 4.0, 1.0))
+        );
+
+        let normal = intersections
+            .get(0)
+            .unwrap()
+            .normal_at(Point3D::new(2.0, 3.0, -1.0), 0.0);
+        assert_abs_diff_eq!(normal, Vector3D::new(0.0, 0.0, -1.0).normalised());
+    }
+
+    #[test]
+    fn overlapping_instances_of_a_glass_object_should_be_treated_as_separate_objects_when_refracting(
+    ) {
+        let shared = Arc::new(Object::sphere().with_material(Material {
+            transparency: 1.0,
+            refractive: 1.5,
+            ..Default::default()
+        }));
+        let group = Object::group(vec![
+            Object::instance(Arc::clone(&shared), Transform::identity()),
+            Object::instance(shared, Transform::identity().translate_z(1.0)),
+        ]);
+        let ray = Ray::new(Point3D::new(0.0, 0.0, -5.0), Normal3D::POSITIVE_Z);
+
+        let intersections = group.intersect(&ray);
+        assert_eq!(intersections.len(), 4);
+
+        // entering the second instance, while still inside the first
+        let hit_data = HitData::from(
+            &ray,
+            intersections.get(1).unwrap().clone(),
+            intersections.clone(),
+        );
+        assert_eq!(hit_data.entered_refractive, 1.5);
+        assert_eq!(hit_data.exited_refractive, 1.5);
+
+        // leaving the first instance, while still inside the second
+        let hit_data = HitData::from(
+            &ray,
+            intersections.get(2).unwrap().clone(),
+            intersections.clone(),
+        );
+        assert_eq!(hit_data.entered_refractive, 1.5);
+        assert_eq!(hit_data.exited_refractive, 1.5);
+
+        // leaving the second instance
+        let hit_data = HitData::from(&ray, intersections.get(3).unwrap().clone(), intersections);
+        assert_eq!(hit_data.entered_refractive, 1.5);
+        assert_eq!(hit_data.exited_refractive, 1.0);
+    }
+
+    #[test]
+    fn a_csg_of_two_instances_of_the_same_object_should_treat_each_instance_as_a_separate_operand()
+    {
+        let shared = Arc::new(Object::sphere());
+        let left = Object::instance(Arc::clone(&shared), Transform::identity());
+        let right = Object::instance(shared, Transform::identity().translate_z(0.5));
+
+        let left_id = left.id();
+        let right_id = right.id();
+
+        let csg = Object::csg_intersection(left, right);
+        let ray = Ray::new(Point3D::new(0.0, 0.0, -5.0), Normal3D::POSITIVE_Z);
+
+        let intersections = csg.intersect(&ray);
+        assert_eq!(intersections.len(), 2);
+
+        assert_eq!(intersections.get(0).unwrap().t, 4.5);
+        assert_eq!(
+            intersections.get(0).unwrap().instance.unwrap().id(),
+            right_id
+        );
+
+        assert_eq!(intersections.get(1).unwrap().t, 6.0);
+        assert_eq!(
+            intersections.get(1).unwrap().instance.unwrap().id(),
+            left_id
+        );
+    }
+}
@@ -2,7 +2,8 @@ use super::*;
 
 mod unit_tests {
     use super::*;
-    use crate::core::{Colour, Point3D, Transform};
+    use crate::core::{Colour, Point3D, Transform, Vector3D};
+    use approx::*;
     use std::f64::consts::PI;
 
     #[test]
@@ -127,6 +128,47 @@ mod unit_tests {
         );
     }
 
+    #[test]
+    fn a_repeating_gradient_pattern_should_reverse_every_unit_rather_than_jumping_back() {
+        let plain = Pattern::gradient(Colour::WHITE, Colour::BLACK);
+        let repeating = Pattern::gradient_repeating(Colour::WHITE, Colour::BLACK);
+        let at = |pattern: &Pattern, x: f64| pattern.colour_at(Point3D::new(x, 0.0, 0.0));
+
+        assert_eq!(at(&repeating, 0.0), Colour::WHITE);
+        assert_eq!(at(&repeating, 1.0), Colour::BLACK);
+        assert_eq!(at(&repeating, 2.0), Colour::WHITE);
+        assert_eq!(at(&repeating, 0.5), at(&repeating, 1.5));
+        assert_eq!(at(&repeating, 0.25), Colour::greyscale(0.75));
+        assert_eq!(at(&repeating, 1.25), Colour::greyscale(0.25));
+
+        // either side of the seam at x = 1
+        assert_eq!(at(&repeating, 0.875), at(&repeating, 1.125));
+        assert_ne!(at(&plain, 0.875), at(&plain, 1.125));
+    }
+
+    #[test]
+    fn a_repeating_gradient_pattern_should_mirror_around_the_origin() {
+        let pattern = Pattern::gradient_repeating(Colour::WHITE, Colour::BLACK);
+
+        assert_eq!(
+            pattern.colour_at(Point3D::new(-0.25, 0.0, 0.0)),
+            pattern.colour_at(Point3D::new(0.25, 0.0, 0.0))
+        );
+    }
+
+    #[test]
+    fn a_repeating_gradient_pattern_should_match_a_plain_gradient_with_the_same_transform() {
+        let transform = Transform::identity().scale_x(2.0).translate_x(0.5);
+        let plain = Pattern::gradient(Colour::WHITE, Colour::BLACK).with_transform(transform);
+        let repeating =
+            Pattern::gradient_repeating(Colour::WHITE, Colour::BLACK).with_transform(transform);
+        let at = |pattern: &Pattern, x: f64| pattern.colour_at(Point3D::new(x, 0.0, 0.0));
+
+        for x in [0.0, 0.25, 0.5, 0.75] {
+            assert_eq!(at(&repeating, x), at(&plain, x));
+        }
+    }
+
     #[test]
     fn a_ring_pattern_alternates_primary_and_secondary_colour_rings() {
         let pattern = Pattern::ring(Colour::WHITE, Colour::BLACK);
@@ -243,6 +285,130 @@ mod unit_tests {
         );
     }
 
+    #[test]
+    fn a_scaled_checkers_pattern_should_alternate_every_cell_size_along_each_axis() {
+        let pattern = Pattern::checkers_scaled(Colour::WHITE, Colour::BLACK, 2.0, 2.0, 2.0);
+
+        for axis in [
+            Vector3D::new(1.0, 0.0, 0.0),
+            Vector3D::new(0.0, 1.0, 0.0),
+            Vector3D::new(0.0, 0.0, 1.0),
+        ] {
+            let at = |distance: f64| pattern.colour_at(Point3D::ORIGIN + axis * distance);
+
+            assert_eq!(at(0.0), Colour::WHITE, "{:?}", axis);
+            assert_eq!(at(1.99), Colour::WHITE, "{:?}", axis);
+            assert_eq!(at(2.0), Colour::BLACK, "{:?}", axis);
+            assert_eq!(at(3.99), Colour::BLACK, "{:?}", axis);
+            assert_eq!(at(4.0), Colour::WHITE, "{:?}", axis);
+            assert_eq!(at(-0.01), Colour::BLACK, "{:?}", axis);
+        }
+    }
+
+    #[test]
+    fn an_anti_aliased_checkers_pattern_should_average_the_colours_on_a_boundary() {
+        let pattern = Pattern::checkers(Colour::WHITE, Colour::BLACK).with_anti_aliasing(0.1);
+        let grey = Colour::greyscale(0.5);
+
+        assert_abs_diff_eq!(pattern.colour_at(Point3D::new(1.0, 0.5, 0.5)), grey);
+        assert_abs_diff_eq!(pattern.colour_at(Point3D::new(0.5, 0.5, 2.0)), grey);
+        assert_abs_diff_eq!(
+            pattern.colour_at(Point3D::new(0.5, -0.5, 0.5)),
+            Colour::BLACK
+        );
+        assert_abs_diff_eq!(
+            pattern.colour_at(Point3D::new(0.5, 0.5, 0.5)),
+            Colour::WHITE
+        );
+        // part of the way into the filter width, the colours are blended in proportion
+        assert_abs_diff_eq!(
+            pattern.colour_at(Point3D::new(0.975, 0.5, 0.5)),
+            Colour::greyscale(0.75)
+        );
+    }
+
+    #[test]
+    fn an_anti_aliased_checkers_pattern_on_a_plane_lying_on_a_boundary_should_only_blend_across_the_plane(
+    ) {
+        use crate::core::{Normal3D, Ray};
+        use crate::scene::{HitData, Material, MaterialKind, Object};
+
+        let plane = Object::plane().with_material(Material {
+            kind: MaterialKind::Pattern(
+                Pattern::checkers(Colour::WHITE, Colour::BLACK).with_anti_aliasing(0.1),
+            ),
+            ..Default::default()
+        });
+        let colour_at = |x: f64, z: f64| {
+            let ray = Ray::new(Point3D::new(x, 1.0, z), Normal3D::NEGATIVE_Y);
+            let intersections = plane.intersect(&ray);
+            let hit = intersections.hit(None).unwrap();
+
+            HitData::from(&ray, hit, intersections).raw_colour()
+        };
+
+        assert_abs_diff_eq!(colour_at(0.5, 0.5), Colour::WHITE);
+        assert_abs_diff_eq!(colour_at(1.5, 0.5), Colour::BLACK);
+        assert_abs_diff_eq!(colour_at(1.0, 0.5), Colour::greyscale(0.5));
+        assert_abs_diff_eq!(colour_at(0.975, 0.5), Colour::greyscale(0.75));
+    }
+
+    #[test]
+    fn an_anti_aliased_stripe_pattern_should_average_the_colours_on_a_boundary() {
+        let pattern = Pattern::striped(Colour::WHITE, Colour::BLACK).with_anti_aliasing(0.1);
+
+        assert_abs_diff_eq!(
+            pattern.colour_at(Point3D::new(-1.0, 0.0, 0.0)),
+            Colour::greyscale(0.5)
+        );
+        assert_abs_diff_eq!(
+            pattern.colour_at(Point3D::new(1.5, 0.0, 0.0)),
+            Colour::BLACK
+        );
+    }
+
+    #[test]
+    fn a_checkers_pattern_should_scale_each_axis_independently() {
+        let pattern = Pattern::checkers_scaled(Colour::WHITE, Colour::BLACK, 1.0, 0.5, 4.0);
+
+        assert_eq!(
+            pattern.colour_at(Point3D::new(1.0, 0.0, 0.0)),
+            Colour::BLACK
+        );
+        assert_eq!(
+            pattern.colour_at(Point3D::new(0.0, 0.5, 0.0)),
+            Colour::BLACK
+        );
+        assert_eq!(
+            pattern.colour_at(Point3D::new(0.0, 0.0, 3.0)),
+            Colour::WHITE
+        );
+        assert_eq!(
+            pattern.colour_at(Point3D::new(0.0, 0.0, 4.0)),
+            Colour::BLACK
+        );
+    }
+
+    #[test]
+    fn an_anti_aliased_scaled_checkers_pattern_should_filter_over_the_width_in_pattern_space() {
+        let pattern = Pattern::checkers_scaled(Colour::WHITE, Colour::BLACK, 4.0, 4.0, 4.0)
+            .with_anti_aliasing(0.4);
+
+        // half a filter width from the boundary, so the filter only covers one cell
+        assert_abs_diff_eq!(
+            pattern.colour_at(Point3D::new(3.8, 2.0, 2.0)),
+            Colour::WHITE
+        );
+        assert_abs_diff_eq!(
+            pattern.colour_at(Point3D::new(3.9, 2.0, 2.0)),
+            Colour::greyscale(0.75)
+        );
+        assert_abs_diff_eq!(
+            pattern.colour_at(Point3D::new(4.0, 2.0, 2.0)),
+            Colour::greyscale(0.5)
+        );
+    }
+
     #[test]
     fn a_checker_uv_pattern_alternates_between_the_two_colours() {
         let pattern = UvPattern::checkers(
@@ -397,6 +563,336 @@ mod unit_tests {
                 assert_eq!(uv.colour_at((u, v)), expected, "{} side", side)
             })
     }
+
+    #[test]
+    fn a_uv_mapped_pattern_should_apply_an_image_to_a_sphere_the_same_as_a_uv_material() {
+        use image::{Rgb, RgbImage};
+        use std::sync::Arc;
+
+        let image = Arc::new(RgbImage::from_fn(4, 2, |x, y| {
+            Rgb([x as u8 * 60, y as u8 * 200, 100])
+        }));
+
+        let uv_material = Object::sphere().with_material(Material {
+            kind: MaterialKind::Uv(UvPattern::image(image.clone().into())),
+            ..Default::default()
+        });
+        let pattern_material = Object::sphere().with_material(Material {
+            kind: MaterialKind::Pattern(Pattern::uv_mapped(
+                UvPattern::image(image.into()),
+                UvMapping::Spherical,
+            )),
+            ..Default::default()
+        });
+
+        vec![
+            Point3D::new(0.0, 0.0, -1.0),
+            Point3D::new(1.0, 0.0, 0.0),
+            Point3D::new(-1.0, 0.0, 0.0),
+            Point3D::new(0.0, 0.0, 1.0),
+            Point3D::new(0.6, 0.8, 0.0),
+            Point3D::new(0.0, -0.6, -0.8),
+        ]
+        .into_iter()
+        .for_each(|point| {
+            assert_eq!(
+                pattern_material.raw_colour_at(point),
+                uv_material.raw_colour_at(point),
+                "{:?}",
+                point
+            );
+        });
+    }
+
+    #[test]
+    fn a_planar_uv_mapped_pattern_should_ignore_the_uv_mapping_of_the_object() {
+        let pattern = Pattern::uv_mapped(
+            UvPattern::checkers(
+                Colour::BLACK,
+                Colour::WHITE,
+                nonzero_ext::nonzero!(2usize),
+                nonzero_ext::nonzero!(2usize),
+            ),
+            UvMapping::Planar,
+        );
+        let sphere = Object::sphere().with_material(Material {
+            kind: MaterialKind::Pattern(pattern),
+            ..Default::default()
+        });
+
+        vec![
+            (Point3D::new(0.25, 0.9, 0.25), Colour::BLACK),
+            (Point3D::new(0.75, 0.5, 0.25), Colour::WHITE),
+            (Point3D::new(0.25, -0.5, 0.75), Colour::WHITE),
+            (Point3D::new(-0.25, 0.0, -0.25), Colour::BLACK),
+        ]
+        .into_iter()
+        .for_each(|(point, expected)| {
+            assert_eq!(sphere.raw_colour_at(point), expected, "{:?}", point);
+        });
+    }
+
+    #[test]
+    fn transforming_a_uv_mapped_pattern_should_transform_the_mapped_points() {
+        let pattern = Pattern::uv_mapped(
+            UvPattern::checkers(
+                Colour::BLACK,
+                Colour::WHITE,
+                nonzero_ext::nonzero!(2usize),
+                nonzero_ext::nonzero!(2usize),
+            ),
+            UvMapping::Planar,
+        )
+        .with_transform(Transform::identity().translate_x(0.5));
+
+        assert_eq!(
+            pattern.colour_at(Point3D::new(0.25, 0.0, 0.25)),
+            Colour::WHITE
+        );
+        assert_eq!(
+            pattern.colour_at(Point3D::new(0.75, 0.0, 0.25)),
+            Colour::BLACK
+        );
+    }
+
+    #[test]
+    fn a_world_space_pattern_should_not_move_with_the_object() {
+        let object = |x: f64, pattern: Pattern| {
+            Object::plane()
+                .transformed(Transform::identity().translate_x(x))
+                .with_material(Material {
+                    kind: MaterialKind::Pattern(pattern),
+                    ..Default::default()
+                })
+        };
+        let stripes = Pattern::striped(Colour::WHITE, Colour::BLACK);
+        let point = Point3D::new(0.5, 0.0, 0.0);
+
+        vec![0.0, 1.0, 2.5, -3.0].into_iter().for_each(|x| {
+            let colour = object(x, stripes.clone().world_space()).raw_colour_at(point);
+            assert_eq!(colour, Colour::WHITE, "{}", x);
+        });
+
+        // an object-space pattern moves with the object
+        assert_eq!(
+            object(1.0, stripes.clone()).raw_colour_at(point),
+            Colour::BLACK
+        );
+    }
+
+    mod wrap_modes {
+        use super::*;
+        use image::{Rgb, RgbImage};
+        use std::sync::Arc;
+
+        /// a 5x1 image, where the pixel at `x` has a red value of `x * 50`, such that `u` maps to a red value of `u * 200`
+        fn sample_red_at(wrap: WrapMode, u: f64) -> u8 {
+            let image = RgbImage::from_fn(5, 1, |x, _| Rgb([x as u8 * 50, 0, 0]));
+            let pattern = UvPattern::image_wrapped(Arc::new(image).into(), wrap);
+
+            (pattern.colour_at((u, 0.5)).red() * 255.0).round() as u8
+        }
+
+        #[test]
+        fn a_clamped_image_should_use_the_edge_pixels_outside_of_the_image() {
+            assert_eq!(sample_red_at(WrapMode::Clamp, 1.5), 200);
+            assert_eq!(sample_red_at(WrapMode::Clamp, 1.25), 200);
+            assert_eq!(sample_red_at(WrapMode::Clamp, -0.25), 0);
+        }
+
+        #[test]
+        fn a_repeated_image_should_tile_outside_of_the_image() {
+            assert_eq!(sample_red_at(WrapMode::Repeat, 1.5), 100);
+            assert_eq!(sample_red_at(WrapMode::Repeat, 1.25), 50);
+            assert_eq!(sample_red_at(WrapMode::Repeat, -0.25), 150);
+        }
+
+        #[test]
+        fn a_mirrored_image_should_flip_every_other_tile_outside_of_the_image() {
+            assert_eq!(sample_red_at(WrapMode::Mirror, 1.5), 100);
+            assert_eq!(sample_red_at(WrapMode::Mirror, 1.25), 150);
+            assert_eq!(sample_red_at(WrapMode::Mirror, -0.25), 50);
+        }
+
+        #[test]
+        fn every_wrap_mode_should_sample_the_image_the_same_way_within_the_image() {
+            vec![WrapMode::Clamp, WrapMode::Repeat, WrapMode::Mirror]
+                .into_iter()
+                .for_each(|wrap| {
+                    assert_eq!(sample_red_at(wrap, 0.0), 0);
+                    assert_eq!(sample_red_at(wrap, 0.5), 100);
+                    assert_eq!(sample_red_at(wrap, 0.75), 150);
+                });
+        }
+    }
+
+    mod blend {
+        use super::*;
+
+        #[test]
+        fn blending_two_solid_colours_should_produce_the_midpoint() {
+            let red = Pattern::striped(Colour::RED, Colour::RED);
+            let blue = Pattern::striped(Colour::BLUE, Colour::BLUE);
+            let pattern = Pattern::blend(red, blue);
+
+            vec![
+                Point3D::ORIGIN,
+                Point3D::new(0.5, 1.0, -2.0),
+                Point3D::new(-3.25, 0.0, 7.5),
+            ]
+            .into_iter()
+            .for_each(|point| {
+                assert_eq!(pattern.colour_at(point), Colour::new(0.5, 0.0, 0.5));
+            });
+        }
+
+        #[test]
+        fn blending_two_stripe_patterns_offset_by_half_a_period_should_produce_a_uniform_colour() {
+            let stripes = Pattern::striped(Colour::WHITE, Colour::BLACK);
+            let offset = Pattern::striped(Colour::WHITE, Colour::BLACK)
+                .with_transform(Transform::identity().translate_x(1.0));
+            let pattern = Pattern::blend(stripes, offset);
+
+            (0..40)
+                .map(|i| Point3D::new(i as f64 * 0.25 - 5.0, 0.0, 0.0))
+                .for_each(|point| {
+                    assert_eq!(
+                        pattern.colour_at(point),
+                        Colour::greyscale(0.5),
+                        "{:?}",
+                        point
+                    );
+                });
+        }
+
+        #[test]
+        fn blending_should_apply_the_transform_of_each_sub_pattern() {
+            let small = Pattern::striped(Colour::WHITE, Colour::BLACK)
+                .with_transform(Transform::identity().scale_x(0.5));
+            let large = Pattern::striped(Colour::WHITE, Colour::BLACK);
+            let pattern = Pattern::blend(small, large);
+
+            assert_eq!(
+                pattern.colour_at(Point3D::new(0.25, 0.0, 0.0)),
+                Colour::WHITE
+            );
+            assert_eq!(
+                pattern.colour_at(Point3D::new(0.75, 0.0, 0.0)),
+                Colour::greyscale(0.5)
+            );
+            assert_eq!(
+                pattern.colour_at(Point3D::new(1.75, 0.0, 0.0)),
+                Colour::BLACK
+            );
+        }
+    }
+
+    mod nested {
+        use super::*;
+
+        #[test]
+        fn a_nested_pattern_should_sample_a_different_pattern_in_adjacent_checker_cells() {
+            let red_to_blue = Pattern::gradient(Colour::RED, Colour::BLUE);
+            let green_to_white = Pattern::gradient(Colour::GREEN, Colour::WHITE);
+            let pattern = Pattern::nested(red_to_blue.clone(), green_to_white.clone());
+
+            let first_cell = Point3D::new(0.5, 0.5, 0.5);
+            let next_cell = Point3D::new(1.5, 0.5, 0.5);
+            let cell_above = Point3D::new(0.5, 1.5, 0.5);
+
+            assert_eq!(
+                pattern.colour_at(first_cell),
+                red_to_blue.colour_at(first_cell)
+            );
+            assert_eq!(
+                pattern.colour_at(next_cell),
+                green_to_white.colour_at(next_cell)
+            );
+            assert_eq!(
+                pattern.colour_at(cell_above),
+                green_to_white.colour_at(cell_above)
+            );
+            assert_ne!(pattern.colour_at(first_cell), pattern.colour_at(next_cell));
+        }
+
+        #[test]
+        fn a_nested_pattern_should_apply_the_transform_of_each_sub_pattern() {
+            let stripes = Pattern::striped(Colour::WHITE, Colour::BLACK)
+                .with_transform(Transform::identity().scale_x(0.25));
+            let pattern = Pattern::nested(stripes, Pattern::striped(Colour::RED, Colour::RED));
+
+            assert_eq!(
+                pattern.colour_at(Point3D::new(0.1, 0.0, 0.0)),
+                Colour::WHITE
+            );
+            assert_eq!(
+                pattern.colour_at(Point3D::new(0.3, 0.0, 0.0)),
+                Colour::BLACK
+            );
+            assert_eq!(pattern.colour_at(Point3D::new(1.1, 0.0, 0.0)), Colour::RED);
+        }
+    }
+
+    mod noise {
+        use super::*;
+        use crate::scene::pattern::noise;
+
+        fn marble() -> Pattern {
+            Pattern::noise(Pattern::striped(Colour::WHITE, Colour::BLACK), 0.5, 4)
+        }
+
+        #[test]
+        fn noise_should_be_deterministic() {
+            // the exact values don't matter, but they must never change between runs
+            assert_eq!(
+                noise::perlin(Point3D::new(0.5, 1.25, -2.75)),
+                -0.19832086563110352
+            );
+            assert_eq!(
+                noise::fractal(Point3D::new(0.5, 1.25, -2.75), 4),
+                0.02756220499674479
+            );
+            assert_eq!(
+                marble().colour_at(Point3D::new(0.9, 0.2, 0.3)),
+                marble().colour_at(Point3D::new(0.9, 0.2, 0.3))
+            );
+        }
+
+        #[test]
+        fn noise_should_vary_smoothly_between_nearby_points() {
+            (0..1000)
+                .map(|i| Point3D::new(i as f64 * 0.01, i as f64 * 0.007, i as f64 * -0.003))
+                .for_each(|point| {
+                    let nearby = Point3D::new(point.x() + 0.001, point.y(), point.z() - 0.001);
+                    let difference = noise::fractal(point, 4) - noise::fractal(nearby, 4);
+
+                    assert!(difference.abs() < 0.02, "{:?}", point);
+                });
+        }
+
+        #[test]
+        fn noise_should_perturb_the_stripes_of_a_striped_pattern() {
+            let stripes = Pattern::striped(Colour::WHITE, Colour::BLACK);
+            let marble = marble();
+
+            let points = (0..2000)
+                .map(|i| Point3D::new(i as f64 * 0.002, 0.3, 0.6))
+                .collect::<Vec<_>>();
+
+            let perturbed = points
+                .iter()
+                .filter(|point| stripes.colour_at(**point) != marble.colour_at(**point))
+                .count();
+            assert!(perturbed > 0);
+
+            // the stripes are distorted, but remain continuous, rather than becoming speckled
+            let changes = points
+                .windows(2)
+                .filter(|pair| marble.colour_at(pair[0]) != marble.colour_at(pair[1]))
+                .count();
+            assert!(changes < 10, "{}", changes);
+        }
+    }
 }
 
 mod property_tests {
@@ -0,0 +1,83 @@
+use super::*;
+use crate::core::{Normal3D, Point3D, Ray, Transform};
+use approx::*;
+use std::f64::consts::PI;
+
+fn intersection_ts(object: &Object, ray: Ray) -> Vec<f64> {
+    object.intersect(&ray).iter().map(|i| i.t).collect()
+}
+
+#[test]
+fn a_ray_hitting_a_quad_within_its_extent_should_intersect_once() {
+    let quad = Object::quad(4.0, 2.0);
+
+    vec![
+        (Point3D::new(0.0, 1.0, 0.0), Normal3D::NEGATIVE_Y, 1.0),
+        (Point3D::new(1.5, -3.0, -0.5), Normal3D::POSITIVE_Y, 3.0),
+        (Point3D::new(2.0, 5.0, 1.0), Normal3D::NEGATIVE_Y, 5.0),
+    ]
+    .into_iter()
+    .for_each(|(origin, direction, t)| {
+        assert_eq!(intersection_ts(&quad, Ray::new(origin, direction)), vec![t]);
+    });
+}
+
+#[test]
+fn a_ray_passing_just_outside_the_extent_of_a_quad_should_not_intersect() {
+    let quad = Object::quad(4.0, 2.0);
+
+    vec![
+        Point3D::new(2.01, 1.0, 0.0),
+        Point3D::new(-2.01, 1.0, 0.0),
+        Point3D::new(0.0, 1.0, 1.01),
+        Point3D::new(0.0, 1.0, -1.01),
+    ]
+    .into_iter()
+    .map(|origin| Ray::new(origin, Normal3D::NEGATIVE_Y))
+    .for_each(|ray| assert!(quad.intersect(&ray).is_empty(), "{:?}", ray));
+}
+
+#[test]
+fn a_ray_parallel_to_a_quad_should_not_intersect() {
+    let quad = Object::quad(4.0, 2.0);
+    let ray = Ray::new(Point3D::new(-5.0, 0.0, 0.0), Normal3D::POSITIVE_X);
+
+    assert!(quad.intersect(&ray).is_empty());
+}
+
+#[test]
+fn a_quad_should_have_finite_bounds() {
+    let quad = Object::quad(4.0, 2.0);
+
+    assert!(!quad.bounds.is_unbounded());
+    assert_eq!(
+        quad.bounds_world(),
+        (Point3D::new(-2.0, 0.0, -1.0), Point3D::new(2.0, 0.0, 1.0))
+    );
+}
+
+#[test]
+fn the_normal_of_a_transformed_quad_should_follow_the_transform() {
+    let quad = Object::quad(4.0, 2.0).transformed(Transform::identity().rotate_x(PI / 2.0));
+
+    assert_abs_diff_eq!(
+        quad.normal_at(Point3D::new(1.0, 0.5, 0.0)),
+        Normal3D::POSITIVE_Z
+    );
+}
+
+#[test]
+fn the_uv_coordinates_of_a_quad_should_run_from_0_to_1_across_the_quad() {
+    let quad = Quad::new(4.0, 2.0);
+
+    vec![
+        (Point3D::new(-2.0, 0.0, -1.0), (0.0, 0.0)),
+        (Point3D::new(2.0, 0.0, -1.0), (1.0, 0.0)),
+        (Point3D::new(-2.0, 0.0, 1.0), (0.0, 1.0)),
+        (Point3D::new(2.0, 0.0, 1.0), (1.0, 1.0)),
+        (Point3D::new(0.0, 0.0, 0.0), (0.5, 0.5)),
+        (Point3D::new(1.0, 0.0, 0.5), (0.75, 0.75)),
+    ]
+    .into_iter()
+    .for_each(|(point, uv)| assert_eq!(quad.uv_at(point), uv, "{:?}", point));
+}
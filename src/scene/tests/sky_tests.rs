@@ -0,0 +1,174 @@
+use super::*;
+use crate::core::{Colour, Normal3D, Point3D, Ray, Vector3D};
+use image::{Rgb, RgbImage};
+use std::sync::Arc;
+
+const RED: Colour = Colour::new(1.0, 0.0, 0.0);
+const CYAN: Colour = Colour::new(0.0, 1.0, 1.0);
+const GREEN: Colour = Colour::new(0.0, 1.0, 0.0);
+const MAGENTA: Colour = Colour::new(1.0, 0.0, 1.0);
+const BLUE: Colour = Colour::new(0.0, 0.0, 1.0);
+const YELLOW: Colour = Colour::new(1.0, 1.0, 0.0);
+
+fn solid(r: u8, g: u8, b: u8) -> Arc<RgbImage> {
+    Arc::new(RgbImage::from_pixel(1, 1, Rgb([r, g, b])))
+}
+
+fn colourful_cube_map() -> Sky {
+    Sky::CubeMap {
+        px: solid(255, 0, 0),
+        nx: solid(0, 255, 255),
+        py: solid(0, 255, 0),
+        ny: solid(255, 0, 255),
+        pz: solid(0, 0, 255),
+        nz: solid(255, 255, 0),
+    }
+}
+
+#[test]
+fn a_cube_map_should_sample_the_face_on_the_major_axis_of_the_direction() {
+    let sky = colourful_cube_map();
+
+    vec![
+        (Vector3D::new(1.0, 0.0, 0.0), RED),
+        (Vector3D::new(-1.0, 0.0, 0.0), CYAN),
+        (Vector3D::new(0.0, 1.0, 0.0), GREEN),
+        (Vector3D::new(0.0, -1.0, 0.0), MAGENTA),
+        (Vector3D::new(0.0, 0.0, 1.0), BLUE),
+        (Vector3D::new(0.0, 0.0, -1.0), YELLOW),
+        (Vector3D::new(1.0, 0.99, 0.0), RED),
+        (Vector3D::new(0.99, 1.0, 0.0), GREEN),
+        (Vector3D::new(-0.5, -0.6, 0.7), BLUE),
+        (Vector3D::new(-0.5, -0.7, 0.6), MAGENTA),
+    ]
+    .into_iter()
+    .for_each(|(direction, colour)| {
+        assert_eq!(sky.colour_at(direction), colour, "{:?}", direction)
+    });
+}
+
+#[test]
+fn a_cube_map_face_should_be_oriented_as_seen_from_inside_the_cube() {
+    // when looking along +Z with +Y up, -X is on the left, so the top left of the front image should be up and to the left
+    let mut front = RgbImage::from_pixel(2, 2, Rgb([0, 0, 0]));
+    front.put_pixel(0, 0, Rgb([255, 255, 255]));
+
+    let sky = Sky::CubeMap {
+        px: solid(255, 0, 0),
+        nx: solid(0, 255, 255),
+        py: solid(0, 255, 0),
+        ny: solid(255, 0, 255),
+        pz: Arc::new(front),
+        nz: solid(255, 255, 0),
+    };
+
+    // the centres of the pixels lie on the corners of the face, and the pixels are blended by how close each one is,
+    // so the white pixel contributes the most to the top left quarter, and the least to the bottom right
+    vec![
+        (Vector3D::new(-0.5, 0.5, 1.0), 0.5625),
+        (Vector3D::new(0.5, 0.5, 1.0), 0.1875),
+        (Vector3D::new(-0.5, -0.5, 1.0), 0.1875),
+        (Vector3D::new(0.5, -0.5, 1.0), 0.0625),
+    ]
+    .into_iter()
+    .for_each(|(direction, brightness)| {
+        assert_eq!(
+            sky.colour_at(direction),
+            Colour::greyscale(brightness),
+            "{:?}",
+            direction
+        )
+    });
+}
+
+#[test]
+fn a_ray_missing_every_object_should_use_the_sky_instead_of_the_sky_colour() {
+    let mut world = World::empty();
+    world.settings.sky_colour = Colour::WHITE;
+    world.settings.sky = Some(colourful_cube_map());
+
+    let ray = Ray::new(Point3D::ORIGIN, Normal3D::NEGATIVE_Y);
+    assert_eq!(world.colour_at(ray), MAGENTA);
+}
+
+#[test]
+fn a_chrome_sphere_should_reflect_the_cube_map_across_face_boundaries() {
+    let mut world = World::empty();
+    world.settings.sky = Some(colourful_cube_map());
+    world.add(Object::sphere().with_material(Material {
+        kind: MaterialKind::Solid(Colour::BLACK),
+        ambient: 0.0,
+        diffuse: 0.0,
+        specular: 0.0,
+        reflective: 1.0,
+        ..Default::default()
+    }));
+
+    // a ray hitting the sphere `x` units from the centre is reflected `2 * asin(x)` radians from the eye,
+    // so the reflection crosses from the back face to the right face at x = sin(22.5°) ~= 0.383
+    vec![
+        (0.0, 0.0, YELLOW),
+        (0.35, 0.0, YELLOW),
+        (0.42, 0.0, RED),
+        (-0.42, 0.0, CYAN),
+        (0.0, 0.42, GREEN),
+        (0.0, -0.42, MAGENTA),
+    ]
+    .into_iter()
+    .for_each(|(x, y, colour)| {
+        let ray = Ray::new(Point3D::new(x, y, -5.0), Normal3D::POSITIVE_Z);
+        assert_eq!(world.colour_at(ray), colour, "{:?}", (x, y));
+    });
+}
+
+/// a 5x3 latitude/longitude image: the top row is red, the bottom row is blue, and the middle row (the horizon) is
+/// white straight ahead (+Z), green to the right (+X), yellow to the left (-X), and black behind (-Z)
+fn environment() -> Sky {
+    let mut image = RgbImage::from_pixel(5, 3, Rgb([0, 0, 0]));
+    (0..5).for_each(|x| {
+        image.put_pixel(x, 0, Rgb([255, 0, 0]));
+        image.put_pixel(x, 2, Rgb([0, 0, 255]));
+    });
+    image.put_pixel(1, 1, Rgb([255, 255, 0]));
+    image.put_pixel(2, 1, Rgb([255, 255, 255]));
+    image.put_pixel(3, 1, Rgb([0, 255, 0]));
+
+    Sky::Environment(Arc::new(image))
+}
+
+#[test]
+fn a_ray_pointing_straight_up_should_sample_the_top_row_of_an_environment_image() {
+    let mut world = World::empty();
+    world.settings.sky_colour = Colour::WHITE;
+    world.settings.sky = Some(environment());
+
+    let ray = Ray::new(Point3D::ORIGIN, Normal3D::POSITIVE_Y);
+    assert_eq!(world.colour_at(ray), RED);
+}
+
+#[test]
+fn an_environment_image_should_map_latitude_and_longitude_as_seen_from_inside_the_sphere() {
+    let sky = environment();
+
+    vec![
+        (Vector3D::new(0.0, 1.0, 0.0), RED),
+        (Vector3D::new(0.0, -1.0, 0.0), BLUE),
+        (Vector3D::new(0.0, 0.0, 1.0), Colour::WHITE),
+        (Vector3D::new(1.0, 0.0, 0.0), GREEN),
+        (Vector3D::new(-1.0, 0.0, 0.0), YELLOW),
+        (Vector3D::new(0.0, 0.0, -1.0), Colour::BLACK),
+    ]
+    .into_iter()
+    .for_each(|(direction, colour)| {
+        assert_eq!(sky.colour_at(direction), colour, "{:?}", direction)
+    });
+
+    // slightly above the horizon, straight ahead, is mostly the white pixel, blended with a little of the red top row
+    let above_horizon = sky.colour_at(Vector3D::new(0.0, 0.2, 5.0));
+    assert_eq!(above_horizon.red(), 1.0);
+    assert!(
+        above_horizon.green() < 1.0 && above_horizon.green() > 0.95,
+        "{:?}",
+        above_horizon
+    );
+}
@@ -78,6 +78,29 @@ fn should_be_able_to_calculate_a_surface_normal_on_a_transformed_sphere() {
     );
 }
 
+#[test]
+fn a_mirrored_sphere_should_still_have_outward_facing_normals() {
+    let sphere = Object::sphere().transformed(
+        Transform::identity()
+            .scale_x(-1.0)
+            .scale_z(2.0)
+            .translate_x(1.0),
+    );
+
+    assert_abs_diff_eq!(
+        sphere.normal_at(Point3D::new(2.0, 0.0, 0.0)),
+        Normal3D::POSITIVE_X
+    );
+    assert_abs_diff_eq!(
+        sphere.normal_at(Point3D::new(0.0, 0.0, 0.0)),
+        Normal3D::NEGATIVE_X
+    );
+    assert_abs_diff_eq!(
+        sphere.normal_at(Point3D::new(1.0, 0.0, -2.0)),
+        Normal3D::NEGATIVE_Z
+    );
+}
+
 #[test]
 fn a_ray_passing_through_the_world_origin_should_intersect_a_unit_sphere_at_two_points() {
     let ray = Ray::new(Point3D::new(0.0, 0.0, -5.0), Normal3D::POSITIVE_Z);
@@ -228,3 +251,144 @@ fn uv_mapping_a_sphere_should_project_points_onto_a_plane() {
     .into_iter()
     .for_each(|(point, uv)| assert_eq!(Sphere.uv_at(point), uv))
 }
+
+mod normal_maps {
+    use super::*;
+    use image::{Rgb, RgbImage};
+    use std::f64::consts::{FRAC_1_SQRT_2, PI};
+    use std::sync::Arc;
+
+    fn normal_mapped(object: Object, colour: [u8; 3]) -> Object {
+        let normal_map = Arc::new(RgbImage::from_pixel(1, 1, Rgb(colour)));
+
+        object.with_material(Material::default().with_normal_map(normal_map))
+    }
+
+    #[test]
+    fn a_flat_normal_map_should_not_change_the_normal_of_a_sphere() {
+        let sphere = normal_mapped(Object::sphere(), [128, 128, 255]);
+
+        vec![
+            Point3D::new(1.0, 0.0, 0.0),
+            Point3D::new(0.0, 0.0, -1.0),
+            Point3D::new(0.0, 1.0, 0.0),
+            Point3D::new(FRAC_1_SQRT_2, FRAC_1_SQRT_2, 0.0),
+        ]
+        .into_iter()
+        .for_each(|point| {
+            assert_abs_diff_eq!(sphere.normal_at(point), Object::sphere().normal_at(point))
+        });
+    }
+
+    #[test]
+    fn a_red_normal_map_should_tilt_the_normal_of_a_sphere_towards_increasing_u() {
+        let sphere = normal_mapped(Object::sphere(), [255, 128, 255]);
+
+        assert_abs_diff_eq!(
+            sphere.normal_at(Point3D::new(0.0, 0.0, -1.0)),
+            Vector3D::new(FRAC_1_SQRT_2, 0.0, -FRAC_1_SQRT_2).normalised()
+        );
+    }
+
+    #[test]
+    fn a_green_normal_map_should_tilt_the_normal_of_a_sphere_towards_increasing_v() {
+        let sphere = normal_mapped(Object::sphere(), [128, 255, 255]);
+
+        assert_abs_diff_eq!(
+            sphere.normal_at(Point3D::new(0.0, 0.0, -1.0)),
+            Vector3D::new(0.0, FRAC_1_SQRT_2, -FRAC_1_SQRT_2).normalised()
+        );
+    }
+
+    #[test]
+    fn a_normal_map_should_be_transformed_with_the_sphere() {
+        let sphere = normal_mapped(
+            Object::sphere().transformed(Transform::identity().rotate_y(PI / 2.0)),
+            [255, 128, 255],
+        );
+
+        // the point at u = 0 has been rotated from -Z to -X, and the direction of increasing u from +X to -Z
+        assert_abs_diff_eq!(
+            sphere.normal_at(Point3D::new(-1.0, 0.0, 0.0)),
+            Vector3D::new(-FRAC_1_SQRT_2, 0.0, -FRAC_1_SQRT_2).normalised()
+        );
+    }
+
+    #[test]
+    fn a_normal_map_should_be_ignored_by_shapes_without_a_tangent_frame() {
+        let plane = normal_mapped(Object::plane(), [255, 128, 255]);
+
+        assert_eq!(
+            plane.normal_at(Point3D::new(0.5, 0.0, 0.5)),
+            Normal3D::POSITIVE_Y
+        );
+    }
+}
+
+mod bump_maps {
+    use super::*;
+    use image::{Rgb, RgbImage};
+    use std::sync::Arc;
+
+    fn bump_mapped(image: RgbImage) -> Object {
+        Object::sphere().with_material(Material::default().with_bump_map(Arc::new(image), 10.0))
+    }
+
+    /// the points on the equator at u = 0.25, 0.5, and 0.75 - away from the seam at u = 0, where the height map wraps around
+    fn equator() -> Vec<Point3D> {
+        vec![
+            Point3D::new(1.0, 0.0, 0.0),
+            Point3D::new(0.0, 0.0, 1.0),
+            Point3D::new(-1.0, 0.0, 0.0),
+        ]
+    }
+
+    #[test]
+    fn a_flat_height_map_should_not_change_the_normal_of_a_sphere() {
+        let sphere = bump_mapped(RgbImage::from_pixel(16, 16, Rgb([100, 100, 100])));
+
+        equator()
+            .into_iter()
+            .chain(Some(Point3D::new(0.0, 1.0, 0.0)))
+            .for_each(|point| {
+                assert_abs_diff_eq!(sphere.normal_at(point), Object::sphere().normal_at(point))
+            });
+    }
+
+    #[test]
+    fn a_height_map_rising_along_u_should_tilt_the_normal_of_a_sphere_towards_decreasing_u() {
+        let gradient = RgbImage::from_fn(256, 4, |x, _| Rgb([x as u8; 3]));
+        let sphere = bump_mapped(gradient);
+
+        let tilts = equator()
+            .into_iter()
+            .map(|point| {
+                let normal = sphere.normal_at(point);
+                // `u` increases clockwise around the Y axis, when viewed from above
+                let increasing_u = Vector3D::new(-point.z(), 0.0, point.x());
+
+                assert_abs_diff_eq!(normal.y(), 0.0);
+                normal.dot(increasing_u)
+            })
+            .collect::<Vec<_>>();
+
+        assert!(tilts[0] < 0.0, "{:?}", tilts);
+        tilts
+            .iter()
+            .for_each(|tilt| assert_abs_diff_eq!(*tilt, tilts[0], epsilon = 1e-9));
+    }
+
+    #[test]
+    fn a_stronger_bump_map_should_tilt_the_normal_further() {
+        let gradient = Arc::new(RgbImage::from_fn(256, 4, |x, _| Rgb([x as u8; 3])));
+        let point = Point3D::new(1.0, 0.0, 0.0);
+        let tilt = |strength: f64| {
+            let sphere = Object::sphere()
+                .with_material(Material::default().with_bump_map(gradient.clone(), strength));
+
+            sphere.normal_at(point).dot(Vector3D::new(0.0, 0.0, 1.0))
+        };
+
+        assert!(tilt(20.0) < tilt(10.0));
+    }
+}
@@ -0,0 +1,209 @@
+use super::*;
+use crate::core::Colour;
+use crate::util::TestDirectory;
+use image::{Rgb, RgbImage};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// writes a 2x2 image of a single colour to the `dir`, 12 bytes when decoded
+fn write_texture(dir: &TestDirectory, name: &str, colour: [u8; 3]) -> PathBuf {
+    dir.write_image(name, &RgbImage::from_pixel(2, 2, Rgb(colour)))
+}
+
+#[test]
+fn a_resident_texture_should_always_be_in_memory() {
+    let texture = Texture::from(Arc::new(RgbImage::from_pixel(1, 1, Rgb([255, 0, 0]))));
+    assert!(texture.is_resident());
+
+    let pattern = UvPattern::image(texture);
+    assert_eq!(pattern.colour_at((0.5, 0.5)), Colour::new(1.0, 0.0, 0.0));
+}
+
+#[test]
+fn a_cached_texture_should_not_be_kept_in_memory_until_it_is_sampled() {
+    let dir = TestDirectory::new();
+    let cache = TextureCache::new(1024);
+    let texture = cache
+        .load(write_texture(&dir, "lazy.png", [0, 255, 0]))
+        .unwrap();
+    assert!(!texture.is_resident());
+    assert_eq!(cache.resident_bytes(), 0);
+
+    let pattern = UvPattern::image(texture.clone());
+    assert_eq!(pattern.colour_at((0.5, 0.5)), Colour::new(0.0, 1.0, 0.0));
+    assert!(texture.is_resident());
+    assert_eq!(cache.resident_bytes(), 12);
+}
+
+#[test]
+fn loading_a_missing_texture_into_a_cache_should_fail() {
+    let cache = TextureCache::new(1024);
+    assert!(cache
+        .load(std::env::temp_dir().join("does_not_exist.png"))
+        .is_err());
+}
+
+#[test]
+fn loading_a_corrupt_texture_into_a_cache_should_fail() {
+    let dir = TestDirectory::new();
+    let path = write_texture(&dir, "corrupt.png", [255, 0, 0]);
+    // keeps the header (so the dimensions can still be read), but loses the image data
+    let bytes = std::fs::read(&path).unwrap();
+    std::fs::write(&path, &bytes[..bytes.len() / 2]).unwrap();
+
+    let cache = TextureCache::new(1024);
+    assert!(cache.load(path).is_err());
+}
+
+#[test]
+fn sampling_more_textures_than_the_budget_allows_should_evict_the_least_recently_used() {
+    let dir = TestDirectory::new();
+    // room for two 2x2 textures
+    let cache = TextureCache::new(24);
+    let red = cache
+        .load(write_texture(&dir, "red.png", [255, 0, 0]))
+        .unwrap();
+    let green = cache
+        .load(write_texture(&dir, "green.png", [0, 255, 0]))
+        .unwrap();
+    let blue = cache
+        .load(write_texture(&dir, "blue.png", [0, 0, 255]))
+        .unwrap();
+
+    let red_pattern = UvPattern::image(red.clone());
+    let green_pattern = UvPattern::image(green.clone());
+    let blue_pattern = UvPattern::image(blue.clone());
+
+    red_pattern.colour_at((0.0, 0.0));
+    green_pattern.colour_at((0.0, 0.0));
+    red_pattern.colour_at((0.0, 0.0));
+    assert_eq!(cache.resident_bytes(), 24);
+
+    assert_eq!(
+        blue_pattern.colour_at((0.0, 0.0)),
+        Colour::new(0.0, 0.0, 1.0)
+    );
+    assert!(red.is_resident());
+    assert!(!green.is_resident());
+    assert!(blue.is_resident());
+    assert_eq!(cache.resident_bytes(), 24);
+
+    // evicted textures are decoded again when needed
+    assert_eq!(
+        green_pattern.colour_at((0.0, 0.0)),
+        Colour::new(0.0, 1.0, 0.0)
+    );
+    assert!(!red.is_resident());
+    assert!(green.is_resident());
+    assert!(blue.is_resident());
+}
+
+#[test]
+fn a_texture_larger_than_the_budget_should_still_be_usable() {
+    let dir = TestDirectory::new();
+    let cache = TextureCache::new(4);
+    let texture = cache
+        .load(write_texture(&dir, "too_large.png", [255, 255, 255]))
+        .unwrap();
+
+    let pattern = UvPattern::image(texture.clone());
+    assert_eq!(pattern.colour_at((0.5, 0.5)), Colour::WHITE);
+    assert!(texture.is_resident());
+}
+
+mod bilinear_sampling {
+    use super::*;
+    use crate::scene::texture::sample_bilinear;
+    use approx::*;
+
+    /// a 3x3 image, where the red value increases by 100 per column, and the green value by 100 per row from the bottom up,
+    /// such that the colour at `(u, v)` is `(u * 200, v * 200, 0)`
+    fn gradient() -> RgbImage {
+        RgbImage::from_fn(3, 3, |x, y| Rgb([x as u8 * 100, (2 - y as u8) * 100, 0]))
+    }
+
+    fn rgb(red: f64, green: f64) -> Colour {
+        Colour::new(red / 255.0, green / 255.0, 0.0)
+    }
+
+    const WRAP_MODES: [WrapMode; 3] = [WrapMode::Clamp, WrapMode::Repeat, WrapMode::Mirror];
+
+    #[test]
+    fn sampling_exactly_on_a_pixel_should_produce_the_colour_of_that_pixel() {
+        let image = gradient();
+
+        WRAP_MODES.iter().for_each(|&wrap| {
+            assert_abs_diff_eq!(sample_bilinear(&image, 0.5, 0.5, wrap), rgb(100.0, 100.0));
+        });
+
+        // the centres of the edge pixels lie exactly on the edges of the UV square, whichever way the image is wrapped
+        assert_abs_diff_eq!(
+            sample_bilinear(&image, 0.0, 0.0, WrapMode::Clamp),
+            rgb(0.0, 0.0)
+        );
+        assert_abs_diff_eq!(
+            sample_bilinear(&image, 1.0, 0.5, WrapMode::Clamp),
+            rgb(200.0, 100.0)
+        );
+        assert_abs_diff_eq!(
+            sample_bilinear(&image, 1.0, 1.0, WrapMode::Mirror),
+            rgb(200.0, 200.0)
+        );
+    }
+
+    #[test]
+    fn sampling_half_way_between_pixels_should_average_them() {
+        let image = gradient();
+
+        WRAP_MODES.iter().for_each(|&wrap| {
+            assert_abs_diff_eq!(sample_bilinear(&image, 0.25, 0.5, wrap), rgb(50.0, 100.0));
+            assert_abs_diff_eq!(sample_bilinear(&image, 0.75, 0.25, wrap), rgb(150.0, 50.0));
+            assert_abs_diff_eq!(
+                sample_bilinear(&image, 0.625, 0.875, wrap),
+                rgb(125.0, 175.0)
+            );
+        });
+    }
+
+    #[test]
+    fn sampling_outside_of_the_image_with_clamping_should_use_the_edge_pixels() {
+        let image = gradient();
+
+        assert_abs_diff_eq!(
+            sample_bilinear(&image, 1.5, 0.5, WrapMode::Clamp),
+            rgb(200.0, 100.0)
+        );
+        assert_abs_diff_eq!(
+            sample_bilinear(&image, -0.25, -1.0, WrapMode::Clamp),
+            rgb(0.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn sampling_outside_of_the_image_with_repeating_should_tile_the_image() {
+        let image = gradient();
+
+        assert_abs_diff_eq!(
+            sample_bilinear(&image, 1.25, 0.5, WrapMode::Repeat),
+            rgb(50.0, 100.0)
+        );
+        assert_abs_diff_eq!(
+            sample_bilinear(&image, -0.25, 1.75, WrapMode::Repeat),
+            rgb(150.0, 150.0)
+        );
+    }
+
+    #[test]
+    fn sampling_outside_of_the_image_with_mirroring_should_flip_every_other_tile() {
+        let image = gradient();
+
+        assert_abs_diff_eq!(
+            sample_bilinear(&image, 1.25, 0.5, WrapMode::Mirror),
+            rgb(150.0, 100.0)
+        );
+        assert_abs_diff_eq!(
+            sample_bilinear(&image, -0.25, 1.75, WrapMode::Mirror),
+            rgb(50.0, 50.0)
+        );
+    }
+}
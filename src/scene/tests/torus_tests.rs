@@ -0,0 +1,135 @@
+use super::*;
+use crate::core::{Normal3D, Point3D, Ray, Vector3D, VectorMaths};
+use approx::*;
+
+fn ring() -> Object {
+    Object::torus(1.0, 0.25)
+}
+
+fn intersection_ts(object: &Object, ray: Ray) -> Vec<f64> {
+    object.intersect(&ray).iter().map(|i| i.t).collect()
+}
+
+#[test]
+fn a_ray_passing_through_the_hole_of_a_torus_should_not_intersect() {
+    let torus = ring();
+
+    vec![
+        Ray::new(Point3D::new(0.0, 5.0, 0.0), Normal3D::NEGATIVE_Y),
+        Ray::new(Point3D::new(0.5, -5.0, 0.5), Normal3D::POSITIVE_Y),
+        Ray::new(
+            Point3D::new(0.0, 5.0, -0.5),
+            Vector3D::new(0.0, -10.0, 1.0).normalised(),
+        ),
+    ]
+    .into_iter()
+    .for_each(|ray| assert!(torus.intersect(&ray).is_empty(), "{:?}", ray));
+}
+
+#[test]
+fn a_ray_missing_a_torus_entirely_should_not_intersect() {
+    let torus = ring();
+
+    vec![
+        Ray::new(Point3D::new(0.0, 0.5, -5.0), Normal3D::POSITIVE_Z),
+        Ray::new(Point3D::new(1.5, 0.0, -5.0), Normal3D::POSITIVE_Z),
+        Ray::new(Point3D::new(0.0, 5.0, 0.0), Normal3D::POSITIVE_Y),
+    ]
+    .into_iter()
+    .for_each(|ray| assert!(torus.intersect(&ray).is_empty(), "{:?}", ray));
+}
+
+#[test]
+fn a_ray_passing_straight_through_both_sides_of_a_torus_should_intersect_four_times() {
+    let torus = ring();
+    let ray = Ray::new(Point3D::new(-5.0, 0.0, 0.0), Normal3D::POSITIVE_X);
+
+    let ts = intersection_ts(&torus, ray);
+    assert_eq!(ts.len(), 4);
+    assert_abs_diff_eq!(ts[0], 3.75, epsilon = 1e-9);
+    assert_abs_diff_eq!(ts[1], 4.25, epsilon = 1e-9);
+    assert_abs_diff_eq!(ts[2], 5.75, epsilon = 1e-9);
+    assert_abs_diff_eq!(ts[3], 6.25, epsilon = 1e-9);
+}
+
+#[test]
+fn a_ray_passing_through_the_tube_of_a_torus_should_intersect_twice() {
+    let torus = ring();
+    let ray = Ray::new(Point3D::new(1.0, 5.0, 0.0), Normal3D::NEGATIVE_Y);
+
+    let ts = intersection_ts(&torus, ray);
+    assert_eq!(ts.len(), 2);
+    assert_abs_diff_eq!(ts[0], 4.75, epsilon = 1e-9);
+    assert_abs_diff_eq!(ts[1], 5.25, epsilon = 1e-9);
+}
+
+#[test]
+fn a_ray_grazing_the_inner_radius_of_a_torus_should_touch_it_twice_at_the_same_point() {
+    let torus = ring();
+    // travels through the tube, touching the inner surface of the tube at (0.75, 0, 0)
+    let ray = Ray::new(Point3D::new(0.75, 0.0, -5.0), Normal3D::POSITIVE_Z);
+
+    let ts = intersection_ts(&torus, ray);
+    assert_eq!(ts.len(), 4);
+    assert_abs_diff_eq!(ts[0], 4.0, epsilon = 1e-9);
+    assert_abs_diff_eq!(ts[1], 5.0, epsilon = 1e-6);
+    assert_abs_diff_eq!(ts[2], 5.0, epsilon = 1e-6);
+    assert_abs_diff_eq!(ts[3], 6.0, epsilon = 1e-9);
+}
+
+#[test]
+fn a_ray_grazing_the_outer_radius_of_a_torus_should_touch_it_twice_at_the_same_point() {
+    let torus = ring();
+    let ray = Ray::new(Point3D::new(1.25, 0.0, -5.0), Normal3D::POSITIVE_Z);
+
+    let ts = intersection_ts(&torus, ray);
+    assert_eq!(ts.len(), 2);
+    assert_abs_diff_eq!(ts[0], 5.0, epsilon = 1e-6);
+    assert_abs_diff_eq!(ts[1], 5.0, epsilon = 1e-6);
+}
+
+#[test]
+fn the_normal_of_a_torus_should_point_away_from_the_centre_of_the_tube() {
+    let torus = ring();
+
+    vec![
+        (Point3D::new(1.25, 0.0, 0.0), Normal3D::POSITIVE_X),
+        (Point3D::new(0.75, 0.0, 0.0), Normal3D::NEGATIVE_X),
+        (Point3D::new(0.0, 0.0, -1.25), Normal3D::NEGATIVE_Z),
+        (Point3D::new(1.0, 0.25, 0.0), Normal3D::POSITIVE_Y),
+        (Point3D::new(0.0, -0.25, 1.0), Normal3D::NEGATIVE_Y),
+    ]
+    .into_iter()
+    .for_each(|(point, expected)| {
+        assert_abs_diff_eq!(torus.normal_at(point), expected);
+    });
+}
+
+#[test]
+fn the_bounds_of_a_torus_should_contain_the_ring_and_the_tube() {
+    let torus = Object::torus(2.0, 0.5);
+
+    assert_eq!(torus.bounds.min(), Point3D::new(-2.5, -0.5, -2.5));
+    assert_eq!(torus.bounds.max(), Point3D::new(2.5, 0.5, 2.5));
+}
+
+#[test]
+fn a_torus_should_map_u_around_the_ring_and_v_around_the_tube() {
+    let torus = Torus::new(1.0, 0.25);
+
+    vec![
+        (Point3D::new(0.0, 0.0, -1.25), (0.0, 0.0)),
+        (Point3D::new(1.25, 0.0, 0.0), (0.25, 0.0)),
+        (Point3D::new(0.0, 0.0, 1.25), (0.5, 0.0)),
+        (Point3D::new(-1.25, 0.0, 0.0), (0.75, 0.0)),
+        (Point3D::new(0.0, 0.25, -1.0), (0.0, 0.25)),
+        (Point3D::new(0.0, 0.0, -0.75), (0.0, 0.5)),
+        (Point3D::new(0.0, -0.25, -1.0), (0.0, 0.75)),
+    ]
+    .into_iter()
+    .for_each(|(point, (u, v))| {
+        let (actual_u, actual_v) = torus.uv_at(point);
+        assert_abs_diff_eq!(actual_u.rem_euclid(1.0), u);
+        assert_abs_diff_eq!(actual_v, v);
+    });
+}
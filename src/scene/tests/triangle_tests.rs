@@ -202,9 +202,105 @@ mod triangle_tests {
     }
 }
 
+mod textured_triangles {
+    use super::*;
+    use crate::core::Point3D;
+
+    #[test]
+    fn a_textured_triangle_should_interpolate_the_texture_coordinates_of_the_requested_channel() {
+        let object = Object::textured_triangle(
+            Point3D::ORIGIN,
+            Point3D::new(1.0, 0.0, 0.0),
+            Point3D::new(0.0, 0.0, 1.0),
+            None,
+            vec![
+                [(0.0, 0.0), (1.0, 0.0), (0.0, 1.0)],
+                [(0.5, 0.5), (1.0, 0.5), (0.5, 1.0)],
+            ],
+        );
+        let triangle = object.shape();
+
+        assert_eq!(
+            triangle.uv_channel_at(Point3D::new(0.5, 0.0, 0.5), 0),
+            (0.5, 0.5)
+        );
+        assert_eq!(
+            triangle.uv_channel_at(Point3D::new(0.5, 0.0, 0.5), 1),
+            (0.75, 0.75)
+        );
+        assert_eq!(triangle.uv_channel_at(Point3D::ORIGIN, 1), (0.5, 0.5));
+    }
+
+    #[test]
+    fn the_centroid_of_a_textured_triangle_should_map_to_the_average_of_its_texture_coordinates() {
+        let object = Object::textured_triangle(
+            Point3D::new(-1.0, 2.0, 0.5),
+            Point3D::new(3.0, 0.0, 1.0),
+            Point3D::new(0.0, -1.0, 4.0),
+            None,
+            vec![[(0.1, 0.9), (0.7, 0.3), (0.4, 0.0)]],
+        );
+        let triangle = object.shape();
+
+        let centroid = Point3D::new(2.0 / 3.0, 1.0 / 3.0, 5.5 / 3.0);
+        let (u, v) = triangle.uv_channel_at(centroid, 0);
+        approx::assert_abs_diff_eq!(u, 0.4);
+        approx::assert_abs_diff_eq!(v, 0.4);
+    }
+
+    #[test]
+    fn sampling_a_channel_without_texture_coordinates_should_fall_back_to_the_barycentric_uv() {
+        let object = Object::textured_triangle(
+            Point3D::ORIGIN,
+            Point3D::new(1.0, 0.0, 0.0),
+            Point3D::new(0.0, 0.0, 1.0),
+            None,
+            vec![[(0.5, 0.5), (1.0, 0.5), (0.5, 1.0)]],
+        );
+        let triangle = object.shape();
+
+        let point = Point3D::new(0.25, 0.0, 0.5);
+        assert_eq!(triangle.uv_channel_at(point, 1), triangle.uv_at(point));
+    }
+}
+
 mod smooth_triangles {
     use super::*;
-    use crate::core::{Normal3D, Point3D, Ray, Vector3D, VectorMaths};
+    use crate::core::{Colour, Normal3D, Point3D, Ray, Transform, Vector3D, VectorMaths};
+
+    #[test]
+    fn a_smooth_triangle_should_be_intersected_at_exactly_the_same_points_as_a_flat_triangle() {
+        let (p1, p2, p3) = (
+            Point3D::new(0.0, 1.0, 0.0),
+            Point3D::new(-1.0, 0.0, 0.5),
+            Point3D::new(1.0, -0.2, -0.3),
+        );
+        let flat = Object::triangle(p1, p2, p3);
+        let smooth = Object::smooth_triangle(
+            p1,
+            p2,
+            p3,
+            Normal3D::POSITIVE_Y,
+            Normal3D::NEGATIVE_X,
+            Normal3D::POSITIVE_X,
+        );
+
+        for x in -10..=10 {
+            for y in -10..=10 {
+                let origin = Point3D::new(x as f64 / 8.0, y as f64 / 8.0, -2.0);
+                let ray = Ray::new(origin, Vector3D::new(0.1, -0.05, 1.0).normalised());
+
+                let ts = |object: &Object| {
+                    object
+                        .intersect(&ray)
+                        .iter()
+                        .map(|i| i.t)
+                        .collect::<Vec<_>>()
+                };
+                assert_eq!(ts(&flat), ts(&smooth), "{:?}", ray);
+            }
+        }
+    }
 
     #[test]
     fn the_normal_of_a_smooth_triangle_should_be_based_off_the_uv_of_the_intersection() {
@@ -226,4 +322,65 @@ mod smooth_triangles {
             Vector3D::new(-0.554700196225229, 0.8320502943378437, 0.0).normalised()
         );
     }
+
+    #[test]
+    fn mirroring_a_smooth_triangle_should_mirror_its_normals() {
+        let triangle = Object::smooth_triangle(
+            Point3D::new(0.0, 1.0, 0.0),
+            Point3D::new(-1.0, 0.0, 0.0),
+            Point3D::new(1.0, 0.0, 0.0),
+            Normal3D::POSITIVE_Y,
+            Normal3D::NEGATIVE_X,
+            Normal3D::POSITIVE_X,
+        )
+        .transformed(Transform::identity().scale_x(-1.0));
+        let ray = Ray::new(Point3D::new(0.2, 0.3, -2.0), Normal3D::POSITIVE_Z);
+        let intersections = triangle.intersect(&ray);
+        let point = ray.position(intersections.get(0).unwrap().t);
+
+        assert_eq!(
+            triangle.normal_at(point),
+            Vector3D::new(0.554700196225229, 0.8320502943378437, 0.0).normalised()
+        );
+    }
+
+    #[test]
+    fn a_mirrored_mesh_should_be_shaded_the_same_as_the_original() {
+        fn mesh() -> Object {
+            Object::group(vec![
+                Object::smooth_triangle(
+                    Point3D::new(0.0, 1.0, 0.0),
+                    Point3D::new(-1.0, 0.0, 0.0),
+                    Point3D::new(1.0, 0.0, 0.0),
+                    Normal3D::NEGATIVE_Z,
+                    Vector3D::new(-1.0, 0.0, -1.0).normalised(),
+                    Vector3D::new(1.0, 0.0, -1.0).normalised(),
+                ),
+                Object::triangle(
+                    Point3D::new(1.0, 0.0, 0.0),
+                    Point3D::new(-1.0, 0.0, 0.0),
+                    Point3D::new(0.0, -2.0, 0.0),
+                ),
+            ])
+        }
+
+        let mut original = World::empty();
+        original.lights = vec![Light::point(Colour::WHITE, Point3D::new(-2.0, 2.0, -5.0))];
+        original.add(mesh());
+
+        let mut mirrored = World::empty();
+        mirrored.lights = vec![Light::point(Colour::WHITE, Point3D::new(2.0, 2.0, -5.0))];
+        mirrored.add(mesh().transformed(Transform::identity().scale_x(-1.0)));
+
+        vec![(0.2, 0.3), (-0.4, 0.1), (0.1, -0.5), (-0.3, -1.0)]
+            .into_iter()
+            .for_each(|(x, y)| {
+                let ray = Ray::new(Point3D::new(x, y, -5.0), Normal3D::POSITIVE_Z);
+                let mirrored_ray = Ray::new(Point3D::new(-x, y, -5.0), Normal3D::POSITIVE_Z);
+
+                let expected = original.colour_at(ray);
+                assert!(expected.red() > 0.1, "{:?}", (x, y));
+                assert_eq!(mirrored.colour_at(mirrored_ray), expected, "{:?}", (x, y));
+            });
+    }
 }
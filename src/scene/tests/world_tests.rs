@@ -2,7 +2,8 @@ use super::*;
 
 mod intersections {
     use super::*;
-    use crate::core::{Colour, Normal3D, Point3D, Ray, Transform};
+    use crate::core::{Colour, Normal3D, Point3D, Ray, Transform, Vector3D, VectorMaths};
+    use approx::*;
     use std::f64::consts::PI;
 
     #[test]
@@ -20,6 +21,62 @@ mod intersections {
         assert_eq!(intersections.get(3).unwrap().t, 6.0);
     }
 
+    #[test]
+    fn the_default_world_should_contain_a_solid_coloured_outer_sphere_and_a_default_inner_sphere() {
+        let world = World::default();
+        let objects = world.objects().collect::<Vec<_>>();
+
+        assert_eq!(objects.len(), 2);
+        assert_eq!(
+            objects[0].material.kind,
+            MaterialKind::Solid(Colour::new(0.8, 1.0, 0.6))
+        );
+        assert_eq!(objects[0].material.diffuse, 0.7);
+        assert_eq!(objects[0].material.specular, 0.2);
+        assert_eq!(objects[1].material, Material::default());
+        assert_eq!(objects[1].transform(), Transform::identity().scale_all(0.5));
+    }
+
+    #[test]
+    fn casting_a_ray_at_the_default_world_should_hit_the_outer_sphere() {
+        let world = World::default();
+        let ray = Ray::new(Point3D::new(0.0, 0.0, -5.0), Normal3D::POSITIVE_Z);
+
+        let (object, t, point) = world.cast(ray).unwrap();
+        assert!(std::ptr::eq(object, &world.objects[0]));
+        assert_eq!(t, 4.0);
+        assert_eq!(point, Point3D::new(0.0, 0.0, -1.0));
+    }
+
+    #[test]
+    fn casting_a_ray_which_hits_nothing_should_return_none() {
+        let world = World::default();
+        let ray = Ray::new(Point3D::new(0.0, 0.0, -5.0), Normal3D::POSITIVE_Y);
+
+        assert!(world.cast(ray).is_none());
+    }
+
+    #[test]
+    fn casting_a_ray_should_find_transformed_objects_inside_groups() {
+        let mut world = World::empty();
+        world.add(
+            Object::group(vec![
+                Object::sphere().transformed(Transform::identity().translate_x(5.0)),
+                Object::cube().transformed(Transform::identity().translate_x(-5.0)),
+            ])
+            .transformed(Transform::identity().translate_z(10.0)),
+        );
+        let ray = Ray::new(Point3D::new(-5.0, 0.0, 0.0), Normal3D::POSITIVE_Z);
+
+        let (object, t, point) = world.cast(ray).unwrap();
+        assert_eq!(
+            object.normal_at(Point3D::new(-5.0, 0.0, 9.0)),
+            Normal3D::NEGATIVE_Z
+        );
+        assert_eq!(t, 9.0);
+        assert_eq!(point, Point3D::new(-5.0, 0.0, 9.0));
+    }
+
     #[test]
     fn the_colour_should_be_black_when_a_ray_hits_nothing() {
         let world = World::default();
@@ -28,6 +85,31 @@ mod intersections {
         assert_eq!(world.colour_at(ray), Colour::BLACK);
     }
 
+    #[test]
+    fn a_ray_hitting_nothing_should_blend_the_sky_gradient_from_the_horizon_to_the_zenith() {
+        let horizon = Colour::WHITE;
+        let zenith = Colour::new(0.5, 0.7, 1.0);
+
+        let mut world = World::empty();
+        world.settings.sky_colour = Colour::BLACK;
+        world.settings.sky_gradient = Some((horizon, zenith));
+
+        let up = Ray::new(Point3D::ORIGIN, Normal3D::POSITIVE_Y);
+        assert_eq!(world.colour_at(up), zenith);
+
+        let down = Ray::new(Point3D::ORIGIN, Normal3D::NEGATIVE_Y);
+        assert_eq!(world.colour_at(down), horizon);
+
+        let level = Ray::new(Point3D::ORIGIN, Normal3D::POSITIVE_Z);
+        assert_eq!(world.colour_at(level), horizon);
+
+        let halfway = Ray::new(
+            Point3D::ORIGIN,
+            Vector3D::new(0.0, 0.5, 0.75_f64.sqrt()).normalised(),
+        );
+        assert_abs_diff_eq!(world.colour_at(halfway), Colour::new(0.75, 0.85, 1.0));
+    }
+
     #[test]
     fn a_hit_on_an_opaque_object_should_not_include_the_colour_of_objects_behind_it() {
         let mut world = World::empty();
@@ -69,7 +151,7 @@ mod intersections {
 
 mod shading {
     use super::*;
-    use crate::core::{Colour, Normal3D, Point3D, Ray, Transform};
+    use crate::core::{Colour, Normal3D, Point3D, Ray, Transform, Vector3D, VectorMaths};
     use crate::renderer::Camera;
     use crate::scene::Pattern;
     use approx::*;
@@ -90,6 +172,66 @@ mod shading {
         assert_abs_diff_eq!(expected, colour);
     }
 
+    #[test]
+    fn shading_synthetic_hit_data_should_match_shading_the_equivalent_ray_hit() {
+        let world = World::default();
+        let sphere = world.objects.first().unwrap();
+
+        let hit_data = HitData::synthetic(
+            sphere,
+            Point3D::new(0.0, 0.0, -1.0),
+            Normal3D::NEGATIVE_Z,
+            Normal3D::NEGATIVE_Z,
+            (1.0, 1.0),
+        );
+
+        assert_abs_diff_eq!(
+            world.shade_hit(&hit_data),
+            Colour::new(
+                0.38066119308103435,
+                0.47582649135129296,
+                0.28549589481077575,
+            )
+        );
+    }
+
+    #[test]
+    fn shading_synthetic_hit_data_facing_away_from_the_light_should_only_be_ambient() {
+        let world = World::default();
+        let sphere = world.objects.first().unwrap();
+
+        let hit_data = HitData::synthetic(
+            sphere,
+            Point3D::new(0.0, 0.0, 1.0),
+            Normal3D::POSITIVE_Z,
+            Normal3D::POSITIVE_Z,
+            (1.0, 1.0),
+        );
+
+        assert_abs_diff_eq!(world.shade_hit(&hit_data), Colour::new(0.8, 1.0, 0.6) * 0.1);
+    }
+
+    #[test]
+    fn a_larger_shadow_bias_should_stop_a_point_just_inside_a_surface_from_shadowing_itself() {
+        let mut world = World::default();
+        // as if rounding errors put the hit slightly below the surface facing the light
+        let normal = Vector3D::new(-1.0, 1.0, -1.0).normalised();
+        let point = Point3D::ORIGIN + normal * (1.0 - 1e-4);
+        let ambient = Colour::new(0.8, 1.0, 0.6) * 0.1;
+
+        let shade = |world: &World| {
+            let sphere = world.objects.first().unwrap();
+            let hit_data = HitData::synthetic(sphere, point, normal, normal, (1.0, 1.0));
+
+            world.shade_hit(&hit_data)
+        };
+
+        assert_abs_diff_eq!(shade(&world), ambient);
+
+        world.settings.shadow_bias = 1e-3;
+        assert!(shade(&world).intensity() > ambient.intensity() * 2.0);
+    }
+
     #[test]
     fn should_correctly_shade_an_internal_hit() {
         let mut world = World::default();
@@ -505,6 +647,7 @@ mod lighting {
     use super::*;
     use crate::core::{Colour, Normal3D, Point3D, Ray, Transform, Vector3D, VectorMaths};
     use crate::renderer::Camera;
+    use approx::*;
     use std::f64::consts::{FRAC_1_SQRT_2, FRAC_PI_4};
 
     #[test]
@@ -530,6 +673,60 @@ mod lighting {
         assert_eq!(colour, Colour::greyscale(0.1));
     }
 
+    #[test]
+    fn an_emissive_material_should_glow_even_in_full_shadow() {
+        let mut world = World::empty();
+        world
+            .lights
+            .push(Light::point(Colour::WHITE, Point3D::new(0.0, 0.0, -10.0)));
+        world.add(Object::sphere().with_material(Material {
+            kind: MaterialKind::Solid(Colour::RED),
+            emission: Colour::RED,
+            ..Default::default()
+        }));
+        world.add(
+            Object::sphere()
+                .transformed(Transform::identity().translate_z(-7.5))
+                .with_material(Material {
+                    kind: MaterialKind::Solid(Colour::BLUE),
+                    ..Default::default()
+                }),
+        );
+
+        let colour = world.colour_at(Ray::new(Point3D::new(0.0, 0.0, -5.0), Normal3D::POSITIVE_Z));
+
+        assert!(colour.red() >= 1.0, "{:?}", colour);
+        assert_eq!(colour, Colour::RED + Colour::new(0.1, 0.0, 0.0));
+    }
+
+    #[test]
+    fn an_emissive_material_should_glow_without_any_lights() {
+        let mut world = World::empty();
+        world.add(Object::sphere().with_material(Material {
+            emission: Colour::new(0.2, 0.4, 0.6),
+            ..Default::default()
+        }));
+
+        let colour = world.colour_at(Ray::new(Point3D::new(0.0, 0.0, -5.0), Normal3D::POSITIVE_Z));
+
+        assert_eq!(colour, Colour::new(0.2, 0.4, 0.6));
+    }
+
+    #[test]
+    fn raising_the_world_ambient_light_should_brighten_unlit_surfaces() {
+        let mut world = World::empty();
+        world.add(Object::sphere().with_material(Material {
+            kind: MaterialKind::Solid(Colour::new(1.0, 0.5, 0.0)),
+            ..Default::default()
+        }));
+
+        let ray = Ray::new(Point3D::new(0.0, 0.0, -5.0), Normal3D::POSITIVE_Z);
+        assert_eq!(world.colour_at(ray.clone()), Colour::BLACK);
+
+        world.settings.ambient_light = Colour::greyscale(0.2);
+        assert_eq!(world.colour_at(ray), Colour::new(0.2, 0.1, 0.0));
+    }
+
     #[test]
     fn lighting_with_the_light_behind_the_surface_should_only_have_ambient() {
         let mut world = World::empty();
@@ -543,6 +740,71 @@ mod lighting {
         assert_eq!(colour, Colour::greyscale(0.1));
     }
 
+    #[test]
+    fn lighting_with_a_spot_light_should_only_light_points_within_its_cone() {
+        let ray = Ray::new(Point3D::new(0.0, 0.0, -5.0), Normal3D::POSITIVE_Z);
+        let lit_by = |light: Light| {
+            let mut world = World::empty();
+            world.lights.push(light);
+            world.add(Object::sphere());
+
+            world.colour_at(ray.clone())
+        };
+        let spot_towards = |direction: Vector3D| {
+            Light::spot(
+                Colour::WHITE,
+                Point3D::new(0.0, 0.0, -10.0),
+                direction,
+                FRAC_PI_4 / 2.0,
+                FRAC_PI_4,
+            )
+        };
+
+        let fully_lit = lit_by(Light::point(Colour::WHITE, Point3D::new(0.0, 0.0, -10.0)));
+        let unlit = Colour::greyscale(0.1);
+
+        assert_eq!(
+            lit_by(spot_towards(Vector3D::new(0.0, 0.0, 1.0))),
+            fully_lit
+        );
+        assert_eq!(lit_by(spot_towards(Vector3D::new(0.0, 1.0, 0.0))), unlit);
+
+        // roughly 30 degrees away from the point, i.e. in the penumbra
+        let penumbra = lit_by(spot_towards(Vector3D::new(0.0, 0.5, 0.866)));
+        assert!(penumbra.red() > unlit.red() && penumbra.red() < fully_lit.red());
+    }
+
+    #[test]
+    fn a_surface_twice_as_far_from_an_inverse_square_light_should_receive_a_quarter_of_the_light() {
+        let lit_from = |distance: f64| {
+            let mut world = World::empty();
+            world.lights.push(Light::point_with_falloff(
+                Colour::WHITE,
+                Point3D::new(0.0, 0.0, -1.0 - distance),
+                0.0,
+                0.0,
+                1.0,
+            ));
+            world.add(Object::sphere().with_material(Material {
+                kind: MaterialKind::Solid(Colour::WHITE),
+                ambient: 0.0,
+                diffuse: 1.0,
+                specular: 0.0,
+                ..Default::default()
+            }));
+
+            world.colour_at(Ray::new(
+                Point3D::new(0.0, 0.0, -10.0),
+                Normal3D::POSITIVE_Z,
+            ))
+        };
+
+        let near = lit_from(2.0);
+        let far = lit_from(4.0);
+        assert_abs_diff_eq!(near, Colour::greyscale(0.25));
+        assert_abs_diff_eq!(far, near / 4.0);
+    }
+
     #[test]
     fn lighting_using_an_area_light_should_average_multiple_samples_from_the_light_source() {
         let mut world = World::empty();
@@ -553,7 +815,6 @@ mod lighting {
             Vector3D::new(0.0, 1.0, 0.0),
             nonzero_ext::nonzero!(2u8),
             nonzero_ext::nonzero!(2u8),
-            0,
         ));
         world.add(Object::sphere().with_material(Material {
             kind: MaterialKind::Solid(Colour::WHITE),
@@ -566,17 +827,125 @@ mod lighting {
         let eye = Point3D::new(0.0, 0.0, -5.0);
         let target = Point3D::new(0.0, 0.0, -1.0);
         let expected = world.colour_at(Ray::new(eye, (target - eye).normalised()));
-        assert_eq!(
-            expected,
-            Colour::new(0.9961547818617024, 0.9961547818617024, 0.9961547818617024)
-        );
+        assert_eq!(expected, Colour::greyscale(0.994698929174971));
 
         let target = Point3D::new(0.0, FRAC_1_SQRT_2, -FRAC_1_SQRT_2);
         let expected = world.colour_at(Ray::new(eye, (target - eye).normalised()));
-        assert_eq!(
-            expected,
-            Colour::new(0.6419842116276147, 0.6419842116276147, 0.6419842116276147)
-        );
+        assert_eq!(expected, Colour::greyscale(0.606015707154075));
+    }
+
+    mod caustics {
+        use super::*;
+
+        /// a glass ball hanging just above the floor, directly beneath a light, which focuses the light into a spot under it
+        fn glass_ball(caustics: bool) -> World {
+            let mut world = World::empty();
+            world.settings.caustics = caustics;
+            world
+                .lights
+                .push(Light::point(Colour::WHITE, Point3D::new(0.0, 100.0, 0.0)));
+            world.add(Object::plane().with_material(Material {
+                specular: 0.0,
+                ..Default::default()
+            }));
+            world.add(
+                Object::sphere()
+                    .with_material(Material {
+                        kind: MaterialKind::Solid(Colour::BLACK),
+                        transparency: 1.0,
+                        refractive: 1.5,
+                        ..Default::default()
+                    })
+                    .transformed(Transform::identity().translate_y(2.0)),
+            );
+
+            world
+        }
+
+        fn floor_colour(world: &World, x: f64) -> Colour {
+            world.colour_at(Ray::new(Point3D::new(x, 0.5, 0.0), Normal3D::NEGATIVE_Y))
+        }
+
+        #[test]
+        fn light_under_a_glass_sphere_should_be_brighter_at_the_focal_point_than_at_the_edges() {
+            let world = glass_ball(true);
+
+            let focal_point = floor_colour(&world, 0.0);
+            let edge = floor_colour(&world, 0.8);
+            assert!(
+                focal_point.intensity() > edge.intensity(),
+                "{:?} {:?}",
+                focal_point,
+                edge
+            );
+        }
+
+        #[test]
+        fn light_focused_by_a_glass_sphere_should_be_brighter_than_unshadowed_light() {
+            let world = glass_ball(true);
+
+            let focal_point = floor_colour(&world, 0.0);
+            let unshadowed = floor_colour(&world, 5.0);
+            assert!(
+                focal_point.intensity() > unshadowed.intensity() * 2.0,
+                "{:?} {:?}",
+                focal_point,
+                unshadowed
+            );
+        }
+
+        #[test]
+        fn without_caustics_the_shadow_of_a_glass_sphere_should_be_evenly_lit() {
+            let world = glass_ball(false);
+
+            assert_abs_diff_eq!(
+                floor_colour(&world, 0.0),
+                floor_colour(&world, 0.8),
+                epsilon = 1e-3
+            );
+        }
+
+        #[test]
+        fn caustics_should_not_affect_points_outside_the_shadow_of_refractive_objects() {
+            assert_eq!(
+                floor_colour(&glass_ball(true), 5.0),
+                floor_colour(&glass_ball(false), 5.0)
+            );
+        }
+    }
+
+    #[test]
+    fn the_soft_shadow_of_an_area_light_should_be_the_same_every_time_a_point_is_shaded() {
+        let mut world = World::empty();
+        world.lights.push(Light::area(
+            Colour::WHITE,
+            Point3D::new(-1.0, 4.0, -1.0),
+            Vector3D::new(2.0, 0.0, 0.0),
+            Vector3D::new(0.0, 0.0, 2.0),
+            nonzero_ext::nonzero!(4u8),
+            nonzero_ext::nonzero!(4u8),
+        ));
+        world.add(Object::plane());
+        world.add(Object::sphere().transformed(Transform::identity().translate_y(2.0)));
+
+        // in the penumbra, where only some of the samples are blocked by the sphere
+        let shade = || world.colour_at(Ray::new(Point3D::new(1.2, 1.0, 0.0), Normal3D::NEGATIVE_Y));
+        let first = shade();
+        let unshadowed =
+            world.colour_at(Ray::new(Point3D::new(5.0, 1.0, 0.0), Normal3D::NEGATIVE_Y));
+        assert!(first.intensity() > 0.1 && first.intensity() < unshadowed.intensity());
+
+        let repeated = std::thread::scope(|scope| {
+            let threads = (0..4).map(|_| scope.spawn(shade)).collect::<Vec<_>>();
+
+            threads
+                .into_iter()
+                .map(|thread| thread.join().unwrap())
+                .collect::<Vec<_>>()
+        });
+
+        assert!(repeated.into_iter().all(|colour| colour == first));
+        assert_eq!(shade(), first);
     }
 
     #[test]
@@ -589,7 +958,6 @@ mod lighting {
             Vector3D::new(0.0, 2.0, 0.0),
             nonzero_ext::nonzero!(8u8),
             nonzero_ext::nonzero!(8u8),
-            7859535925052243674,
         ));
 
         let light_source = Object::cube()
@@ -663,7 +1031,7 @@ mod lighting {
         let left_slightly_shadowed = camera.ray_at(982, 885, 0.5, 0.5);
         assert_eq!(
             world.colour_at(left_slightly_shadowed),
-            Colour::new(0.6492824372513257, 0.6492824372513257, 0.6492824372513257,)
+            Colour::greyscale(0.6510223014117764)
         );
 
         let fully_shadowed = camera.ray_at(1181, 827, 0.5, 0.5);
@@ -679,11 +1047,7 @@ mod lighting {
         let right_slightly_shadowed = camera.ray_at(1560, 793, 0.5, 0.5);
         assert_eq!(
             world.colour_at(right_slightly_shadowed),
-            Colour::new(
-                0.28073844088640443,
-                0.28073844088640443,
-                0.28073844088640443,
-            )
+            Colour::greyscale(0.274388736974412)
         );
     }
 }
@@ -788,6 +1152,79 @@ mod reflection_and_refraction {
 
         assert_abs_diff_eq!(expected, actual)
     }
+
+    mod glossy_reflections {
+        use super::*;
+
+        /// a mirror facing a wall (behind the camera), which is black for x < 0 and white for x >= 0;
+        /// the wall is only lit by the world ambient light, so its colour is reflected unchanged
+        fn world(roughness: f64) -> World {
+            let mut world = World::empty();
+            world.settings.ambient_light = Colour::WHITE;
+
+            world.add(
+                Object::plane()
+                    .with_material(Material {
+                        kind: MaterialKind::Pattern(Pattern::striped(Colour::WHITE, Colour::BLACK)),
+                        ambient: 0.0,
+                        diffuse: 0.0,
+                        specular: 0.0,
+                        ..Default::default()
+                    })
+                    .transformed(Transform::identity().rotate_x(PI / 2.0).translate_z(-10.0)),
+            );
+            world.add(
+                Object::plane()
+                    .with_material(Material {
+                        kind: MaterialKind::Solid(Colour::BLACK),
+                        ambient: 0.0,
+                        diffuse: 0.0,
+                        specular: 0.0,
+                        reflective: 1.0,
+                        roughness,
+                        ..Default::default()
+                    })
+                    .transformed(Transform::identity().rotate_x(-PI / 2.0)),
+            );
+
+            world
+        }
+
+        #[test]
+        fn a_smooth_mirror_should_reflect_a_hard_edge_sharply() {
+            let world = world(0.0);
+
+            let ray = Ray::new(Point3D::new(-0.05, 0.0, -5.0), Normal3D::POSITIVE_Z);
+            assert_eq!(world.colour_at(ray), Colour::BLACK);
+            let ray = Ray::new(Point3D::new(0.05, 0.0, -5.0), Normal3D::POSITIVE_Z);
+            assert_eq!(world.colour_at(ray), Colour::WHITE);
+        }
+
+        #[test]
+        fn a_rough_mirror_should_blur_a_hard_edge() {
+            let world = world(0.3);
+
+            [-0.05, 0.05].iter().for_each(|&x| {
+                let ray = Ray::new(Point3D::new(x, 0.0, -5.0), Normal3D::POSITIVE_Z);
+                let colour = world.colour_at(ray);
+
+                assert!(
+                    colour.red() > 0.0 && colour.red() < 1.0,
+                    "expected a blend of black and white at x = {}, but was {:?}",
+                    x,
+                    colour
+                );
+            });
+        }
+
+        #[test]
+        fn a_rough_mirror_should_be_rendered_deterministically() {
+            let world = world(0.3);
+            let ray = Ray::new(Point3D::new(0.05, 0.2, -5.0), Normal3D::POSITIVE_Z);
+
+            assert_eq!(world.colour_at(ray.clone()), world.colour_at(ray));
+        }
+    }
 }
 
 mod transparency {
@@ -1122,4 +1559,638 @@ mod transparency {
             Colour::new(0.7392412074801862, 0.7875626468175756, 0.7875626468175756);
         assert_eq!(world.colour_at(ray), slightly_yellow);
     }
+
+    mod frosted_glass {
+        use super::*;
+
+        /// a thin pane of glass in front of a wall, which is black for x < 0 and white for x >= 0;
+        /// the wall and glass are only lit by the world ambient light, so the wall colour is seen unchanged
+        fn world(refraction_roughness: f64) -> World {
+            let mut world = World::empty();
+            world.settings.ambient_light = Colour::WHITE;
+
+            world.add(
+                Object::plane()
+                    .with_material(Material {
+                        kind: MaterialKind::Pattern(Pattern::striped(Colour::WHITE, Colour::BLACK)),
+                        ambient: 0.0,
+                        diffuse: 0.0,
+                        specular: 0.0,
+                        ..Default::default()
+                    })
+                    .transformed(Transform::identity().rotate_x(-PI / 2.0).translate_z(10.0)),
+            );
+            world.add(
+                Object::cube()
+                    .with_material(Material {
+                        kind: MaterialKind::Solid(Colour::BLACK),
+                        ambient: 0.0,
+                        diffuse: 0.0,
+                        specular: 0.0,
+                        transparency: 1.0,
+                        refraction_roughness,
+                        ..Default::default()
+                    })
+                    .transformed(Transform::identity().scale_z(0.05)),
+            );
+
+            world
+        }
+
+        #[test]
+        fn clear_glass_should_not_blur_the_wall_behind_it() {
+            let world = world(0.0);
+
+            let ray = Ray::new(Point3D::new(-0.05, 0.0, -5.0), Normal3D::POSITIVE_Z);
+            assert_eq!(world.colour_at(ray), Colour::BLACK);
+            let ray = Ray::new(Point3D::new(0.05, 0.0, -5.0), Normal3D::POSITIVE_Z);
+            assert_eq!(world.colour_at(ray), Colour::WHITE);
+        }
+
+        #[test]
+        fn frosted_glass_should_blur_the_wall_behind_it() {
+            let world = world(0.3);
+
+            [-0.05, 0.05].iter().for_each(|&x| {
+                let ray = Ray::new(Point3D::new(x, 0.0, -5.0), Normal3D::POSITIVE_Z);
+                let colour = world.colour_at(ray);
+
+                assert!(
+                    colour.red() > 0.0 && colour.red() < 1.0,
+                    "expected a blend of black and white at x = {}, but was {:?}",
+                    x,
+                    colour
+                );
+            });
+        }
+
+        #[test]
+        fn frosted_glass_should_be_rendered_deterministically() {
+            let world = world(0.3);
+            let ray = Ray::new(Point3D::new(0.05, 0.2, -5.0), Normal3D::POSITIVE_Z);
+
+            assert_eq!(world.colour_at(ray.clone()), world.colour_at(ray));
+        }
+
+        #[test]
+        fn changing_the_seed_should_change_how_frosted_glass_scatters_rays() {
+            let ray = Ray::new(Point3D::new(0.05, 0.2, -5.0), Normal3D::POSITIVE_Z);
+
+            let colours = (0..4)
+                .map(|seed| {
+                    let mut world = world(0.3);
+                    world.settings.seed = seed;
+                    world.colour_at(ray.clone())
+                })
+                .collect::<Vec<_>>();
+
+            assert!(colours.iter().any(|colour| *colour != colours[0]));
+        }
+    }
+
+    mod absorption {
+        use super::*;
+
+        /// a pane of glass of the given `thickness` in front of a glowing white wall; nothing is lit, and the glass doesn't bend light,
+        /// so only light from the wall passing straight through the glass is seen
+        fn world(thickness: f64, absorption: Colour) -> World {
+            let mut world = World::empty();
+
+            world.add(
+                Object::plane()
+                    .with_material(Material {
+                        kind: MaterialKind::Solid(Colour::BLACK),
+                        emission: Colour::WHITE,
+                        ..Default::default()
+                    })
+                    .transformed(Transform::identity().rotate_x(-PI / 2.0).translate_z(10.0)),
+            );
+            world.add(
+                Object::cube()
+                    .with_material(Material {
+                        kind: MaterialKind::Solid(Colour::BLACK),
+                        transparency: 1.0,
+                        refractive: 1.0,
+                        absorption,
+                        ..Default::default()
+                    })
+                    .transformed(
+                        Transform::identity()
+                            .scale_x(5.0)
+                            .scale_y(5.0)
+                            .scale_z(thickness / 2.0),
+                    ),
+            );
+
+            world
+        }
+
+        fn ray() -> Ray {
+            Ray::new(Point3D::new(0.0, 0.0, -5.0), Normal3D::POSITIVE_Z)
+        }
+
+        #[test]
+        fn glass_without_absorption_should_not_change_the_colour_of_light_passing_through_it() {
+            assert_abs_diff_eq!(world(2.0, Colour::BLACK).colour_at(ray()), Colour::WHITE);
+        }
+
+        #[test]
+        fn light_should_be_attenuated_exponentially_by_the_distance_travelled_through_the_glass() {
+            let red_glass = Colour::new(0.0, 1.0, 1.0);
+
+            assert_abs_diff_eq!(
+                world(0.2, red_glass).colour_at(ray()),
+                Colour::new(1.0, (-0.2_f64).exp(), (-0.2_f64).exp())
+            );
+            assert_abs_diff_eq!(
+                world(2.0, red_glass).colour_at(ray()),
+                Colour::new(1.0, (-2.0_f64).exp(), (-2.0_f64).exp())
+            );
+        }
+
+        #[test]
+        fn a_longer_path_through_red_absorbing_glass_should_be_darker_and_redder() {
+            let red_glass = Colour::new(0.0, 0.5, 0.5);
+
+            let short = world(0.5, red_glass).colour_at(ray());
+            let long = world(3.0, red_glass).colour_at(ray());
+
+            assert!(long.green() < short.green(), "{:?} {:?}", long, short);
+            assert!(long.blue() < short.blue(), "{:?} {:?}", long, short);
+            assert_abs_diff_eq!(long.red(), short.red());
+
+            let redness = |colour: Colour| colour.red() / (colour.green() + colour.blue());
+            assert!(redness(long) > redness(short));
+        }
+
+        #[test]
+        fn light_from_an_object_nested_inside_absorbing_glass_should_be_attenuated_by_the_distance_travelled_through_the_glass(
+        ) {
+            let mut world = World::empty();
+            world.add(
+                Object::sphere()
+                    .with_material(Material {
+                        kind: MaterialKind::Solid(Colour::BLACK),
+                        transparency: 1.0,
+                        refractive: 1.0,
+                        absorption: Colour::new(0.0, 1.0, 1.0),
+                        ..Default::default()
+                    })
+                    .transformed(Transform::identity().scale_all(2.0)),
+            );
+            world.add(Object::sphere().with_material(Material {
+                kind: MaterialKind::Solid(Colour::BLACK),
+                emission: Colour::WHITE,
+                ..Default::default()
+            }));
+
+            // the ray travels from z = -2 to z = -1 through the glass, before hitting the glowing sphere inside it
+            assert_abs_diff_eq!(
+                world.colour_at(ray()),
+                Colour::new(1.0, (-1.0_f64).exp(), (-1.0_f64).exp())
+            );
+        }
+    }
+}
+
+mod editing {
+    use super::*;
+    use crate::core::{Colour, Normal3D, Point3D, Ray, Transform};
+
+    #[test]
+    fn adding_then_removing_an_object_should_leave_an_empty_world() {
+        let mut world = World::empty();
+        let sphere = Object::sphere();
+        let id = sphere.id();
+        world.add(sphere);
+
+        let removed = world.remove(id);
+        assert_eq!(removed.map(|object| object.id()), Some(id));
+        assert!(world.objects.is_empty());
+    }
+
+    #[test]
+    fn removing_an_object_which_does_not_exist_should_return_none() {
+        let mut world = World::default();
+
+        assert!(world.remove(u32::MAX).is_none());
+        assert_eq!(world.objects.len(), 2);
+    }
+
+    #[test]
+    fn removing_an_object_from_a_group_should_detach_it_from_the_group() {
+        let sphere = Object::sphere().transformed(Transform::identity().translate_x(5.0));
+        let sphere_id = sphere.id();
+        let cube = Object::cube().transformed(Transform::identity().translate_x(-5.0));
+
+        let mut world = World::empty();
+        world.add(Object::group(vec![sphere, cube]));
+
+        let removed = world.remove(sphere_id);
+        assert_eq!(removed.map(|object| object.id()), Some(sphere_id));
+        assert_eq!(world.objects.len(), 1);
+
+        let at_sphere = Ray::new(Point3D::new(5.0, 0.0, -5.0), Normal3D::POSITIVE_Z);
+        assert!(world.cast(at_sphere).is_none());
+        let at_cube = Ray::new(Point3D::new(-5.0, 0.0, -5.0), Normal3D::POSITIVE_Z);
+        assert!(world.cast(at_cube).is_some());
+    }
+
+    #[test]
+    fn removing_a_group_should_return_all_of_its_children() {
+        let group = Object::group(vec![Object::sphere(), Object::cube()]);
+        let group_id = group.id();
+
+        let mut world = World::empty();
+        world.add(group);
+
+        let removed = world.remove(group_id).unwrap();
+        assert_eq!(removed.children().len(), 2);
+        assert!(world.objects.is_empty());
+    }
+
+    #[test]
+    fn objects_should_be_mutable_after_being_added_to_the_world() {
+        let mut world = World::default();
+        world
+            .objects_mut()
+            .for_each(|object| object.material.kind = MaterialKind::Solid(Colour::RED));
+
+        assert!(world
+            .objects
+            .iter()
+            .all(|object| object.material.kind == MaterialKind::Solid(Colour::RED)));
+    }
+}
+
+mod ambient_occlusion {
+    use super::*;
+    use crate::core::{Colour, Normal3D, Point3D, Ray, Transform};
+    use crate::scene::AoSettings;
+    use approx::*;
+
+    fn white() -> Material {
+        Material {
+            kind: MaterialKind::Solid(Colour::WHITE),
+            ..Default::default()
+        }
+    }
+
+    fn ambient_only_world(ambient_occlusion: Option<AoSettings>) -> World {
+        let mut world = World::empty();
+        world.settings.ambient_light = Colour::WHITE;
+        world.settings.ambient_occlusion = ambient_occlusion;
+        world
+    }
+
+    #[test]
+    fn a_point_on_an_open_plane_should_receive_full_ambient_light() {
+        let mut world = ambient_only_world(Some(AoSettings {
+            samples: 32,
+            radius: 10.0,
+        }));
+        world.add(Object::plane().with_material(white()));
+
+        let ray = Ray::new(Point3D::new(0.0, 5.0, 0.0), Normal3D::NEGATIVE_Y);
+        assert_abs_diff_eq!(world.colour_at(ray), Colour::WHITE);
+    }
+
+    #[test]
+    fn a_point_deep_inside_a_concave_csg_should_receive_reduced_ambient_light() {
+        // a 2x2 hole, 3 units deep, in the top of a 4x4x4 cube
+        let hole = Object::cube().transformed(Transform::identity().scale_y(3.0).translate_y(2.0));
+        let block = Object::csg_difference(
+            Object::cube().transformed(Transform::identity().scale_all(2.0)),
+            hole,
+        )
+        .with_material(white());
+
+        let mut world = ambient_only_world(Some(AoSettings {
+            samples: 64,
+            radius: 10.0,
+        }));
+        world.add(block);
+
+        let ray = Ray::new(Point3D::new(0.0, 10.0, 0.0), Normal3D::NEGATIVE_Y);
+        let (_, _, point) = world.cast(ray.clone()).unwrap();
+        assert_abs_diff_eq!(point, Point3D::new(0.0, -1.0, 0.0));
+
+        let colour = world.colour_at(ray);
+        assert!(colour.red() > 0.0, "{:?}", colour);
+        assert!(colour.red() < 0.5, "{:?}", colour);
+    }
+
+    #[test]
+    fn a_fully_enclosed_point_should_receive_no_ambient_light_from_light_sources() {
+        let enclosure = || {
+            Object::sphere()
+                .transformed(Transform::identity().scale_all(5.0))
+                .with_material(white())
+        };
+        let ray = Ray::new(Point3D::ORIGIN, Normal3D::POSITIVE_Z);
+
+        let mut world = World::empty();
+        world
+            .lights
+            .push(Light::point(Colour::WHITE, Point3D::new(0.0, 0.0, -10.0)));
+        world.add(enclosure());
+        // the light is outside of the sphere, so only the ambient term of the material is visible
+        assert_abs_diff_eq!(world.colour_at(ray.clone()), Colour::greyscale(0.1));
+
+        world.settings.ambient_occlusion = Some(AoSettings {
+            samples: 16,
+            radius: 20.0,
+        });
+        world.remove(world.objects[0].id());
+        world.add(enclosure());
+        assert_abs_diff_eq!(world.colour_at(ray), Colour::BLACK);
+    }
+
+    #[test]
+    fn occluders_beyond_the_radius_should_not_reduce_ambient_light() {
+        let enclosure = Object::sphere()
+            .transformed(Transform::identity().scale_all(50.0))
+            .with_material(white());
+
+        let mut world = ambient_only_world(Some(AoSettings {
+            samples: 16,
+            radius: 1.0,
+        }));
+        world.add(enclosure);
+
+        // only rays almost parallel to the surface of such a large sphere hit the sphere again within 1 unit
+        let ray = Ray::new(Point3D::ORIGIN, Normal3D::POSITIVE_Z);
+        let colour = world.colour_at(ray);
+        assert!(colour.red() > 0.9, "{:?}", colour);
+    }
+}
+
+mod visibility {
+    use super::*;
+    use crate::core::{Colour, Normal3D, Point3D, Ray, Transform, VectorMaths};
+
+    const SKY: Colour = Colour::new(0.2, 0.4, 0.8);
+
+    /// a sphere resting on the floor, directly beneath a light
+    fn holdout(sphere_material: Material, floor_material: Material) -> World {
+        let mut world = World::empty();
+        world.settings.sky_colour = SKY;
+        world
+            .lights
+            .push(Light::point(Colour::WHITE, Point3D::new(0.0, 10.0, 0.0)));
+        world.add(Object::plane().with_material(floor_material));
+        world.add(
+            Object::sphere()
+                .with_material(sphere_material)
+                .transformed(Transform::identity().translate_y(1.0)),
+        );
+
+        world
+    }
+
+    fn matte() -> Material {
+        Material {
+            specular: 0.0,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn a_sphere_invisible_to_the_camera_should_show_the_background_while_still_casting_a_shadow() {
+        let world = holdout(
+            Material {
+                visible_to_camera: false,
+                ..matte()
+            },
+            matte(),
+        );
+
+        let at_sphere = Ray::new(Point3D::new(0.0, 1.0, -5.0), Normal3D::POSITIVE_Z);
+        assert_eq!(world.colour_at(at_sphere), SKY);
+
+        let through_sphere = Ray::new(Point3D::new(0.0, 5.0, 0.0), Normal3D::NEGATIVE_Y);
+        assert_eq!(world.colour_at(through_sphere), Colour::greyscale(0.1));
+    }
+
+    #[test]
+    fn a_sphere_invisible_to_the_camera_should_still_appear_in_reflections() {
+        let world = holdout(
+            Material {
+                kind: MaterialKind::Solid(Colour::RED),
+                visible_to_camera: false,
+                ..matte()
+            },
+            Material {
+                reflective: 1.0,
+                ..matte()
+            },
+        );
+
+        // passes through the sphere, then is reflected by the floor straight back up into it
+        let through_sphere = Ray::new(Point3D::new(0.0, 5.0, 0.0), Normal3D::NEGATIVE_Y);
+        let colour = world.colour_at(through_sphere);
+        assert!(colour.red() > colour.blue(), "{:?}", colour);
+    }
+
+    #[test]
+    fn a_sphere_invisible_in_reflections_should_be_skipped_by_reflected_rays_but_not_camera_rays() {
+        let world = holdout(
+            Material {
+                kind: MaterialKind::Solid(Colour::RED),
+                visible_in_reflections: false,
+                ..matte()
+            },
+            Material {
+                kind: MaterialKind::Solid(Colour::BLACK),
+                ambient: 0.0,
+                diffuse: 0.0,
+                reflective: 1.0,
+                ..matte()
+            },
+        );
+
+        // the floor in front of the sphere reflects the sky above it, rather than the sphere
+        let floor = Point3D::new(0.0, 0.0, -2.0);
+        let eye = Point3D::new(0.0, 1.0, -3.0);
+        let at_reflection = Ray::new(eye, (floor - eye).normalised());
+        assert_eq!(world.colour_at(at_reflection), SKY);
+
+        let at_sphere = Ray::new(Point3D::new(0.0, 1.0, -5.0), Normal3D::POSITIVE_Z);
+        assert_eq!(world.colour_at(at_sphere).blue(), 0.0);
+    }
+}
+
+mod culling {
+    use super::*;
+    use crate::core::{Colour, Normal3D, Point3D, Ray, Transform, Vector3D, VectorMaths};
+    use crate::renderer::Camera;
+    use std::f64::consts::FRAC_PI_2;
+    use std::num::NonZeroU16;
+
+    const SKY: Colour = Colour::new(0.2, 0.4, 0.8);
+
+    fn camera() -> Camera {
+        Camera::new(
+            NonZeroU16::new(100).unwrap(),
+            NonZeroU16::new(100).unwrap(),
+            FRAC_PI_2,
+            Transform::identity(),
+        )
+    }
+
+    fn world_with_sphere_at(transform: Transform) -> World {
+        let mut world = World::empty();
+        world.settings.sky_colour = SKY;
+        world
+            .lights
+            .push(Light::point(Colour::WHITE, Point3D::new(0.0, 10.0, 0.0)));
+        world.add(Object::sphere().transformed(transform));
+
+        world
+    }
+
+    #[test]
+    fn an_object_far_behind_the_camera_should_be_excluded_from_intersection_with_camera_rays() {
+        let mut world = world_with_sphere_at(Transform::identity().translate_z(100.0));
+
+        let backwards = || Ray::new(Point3D::ORIGIN, Normal3D::POSITIVE_Z);
+        assert_ne!(world.colour_at(backwards()), SKY);
+
+        world.cull(&camera());
+        assert_eq!(world.colour_at(backwards()), SKY);
+    }
+
+    #[test]
+    fn an_object_straddling_the_edge_of_the_view_should_not_be_culled() {
+        // the edge of a 90 degree view passes through (5, 0, -5), which is the centre of the sphere
+        let mut world =
+            world_with_sphere_at(Transform::identity().translate_x(5.0).translate_z(-5.0));
+
+        world.cull(&camera());
+
+        let ray = Ray::new(Point3D::ORIGIN, Vector3D::new(1.0, 0.0, -1.0).normalised());
+        assert_ne!(world.colour_at(ray), SKY);
+    }
+
+    #[test]
+    fn unbounded_objects_should_not_be_culled() {
+        let mut world = World::empty();
+        world.settings.sky_colour = SKY;
+        world.add(Object::plane().transformed(Transform::identity().translate_y(-1.0)));
+
+        world.cull(&camera());
+
+        let at_floor = Ray::new(Point3D::ORIGIN, Vector3D::new(0.0, -1.0, -1.0).normalised());
+        assert_ne!(world.colour_at(at_floor), SKY);
+    }
+
+    #[test]
+    fn a_culled_object_should_still_cast_shadows_into_the_view() {
+        let mut world = World::empty();
+        world
+            .lights
+            .push(Light::point(Colour::WHITE, Point3D::new(0.0, 4.0, 15.0)));
+        world.add(Object::plane().transformed(Transform::identity().translate_y(-1.0)));
+        // behind the camera, directly between the light and the floor in front of the camera
+        world.add(
+            Object::sphere().transformed(Transform::identity().translate_y(1.5).translate_z(5.0)),
+        );
+
+        let floor = Point3D::new(0.0, -1.0, -5.0);
+        let at_floor = || Ray::new(Point3D::ORIGIN, (floor - Point3D::ORIGIN).normalised());
+        let shadowed = world.colour_at(at_floor());
+
+        world.cull(&camera());
+        assert_eq!(world.colour_at(at_floor()), shadowed);
+
+        let sphere_id = world.objects().last().unwrap().id();
+        world.remove(sphere_id);
+        assert_ne!(world.colour_at(at_floor()), shadowed);
+    }
+}
+
+mod fog {
+    use super::*;
+    use crate::core::{Colour, Normal3D, Point3D, Ray, Transform};
+    use crate::scene::FogSettings;
+    use approx::*;
+
+    const FOG: FogSettings = FogSettings {
+        colour: Colour::greyscale(0.5),
+        density: 0.05,
+    };
+
+    /// a sphere directly in front of a light at the origin, such that its surface is lit the same regardless of `distance`
+    fn sphere_at(distance: f64, fog: Option<FogSettings>) -> World {
+        let mut world = World::empty();
+        world.settings.fog = fog;
+        world
+            .lights
+            .push(Light::point(Colour::WHITE, Point3D::ORIGIN));
+        world.add(
+            Object::sphere()
+                .with_material(Material::matte(Colour::RED))
+                .transformed(Transform::identity().translate_z(-distance)),
+        );
+
+        world
+    }
+
+    fn forwards() -> Ray {
+        Ray::new(Point3D::ORIGIN, Normal3D::NEGATIVE_Z)
+    }
+
+    fn distance_between(first: Colour, second: Colour) -> f64 {
+        let difference = first - second;
+        difference.red().abs() + difference.green().abs() + difference.blue().abs()
+    }
+
+    #[test]
+    fn a_distant_object_should_be_tinted_more_towards_the_fog_colour_than_a_near_one() {
+        let near = sphere_at(3.0, Some(FOG)).colour_at(forwards());
+        let far = sphere_at(30.0, Some(FOG)).colour_at(forwards());
+
+        assert!(
+            distance_between(far, FOG.colour) < distance_between(near, FOG.colour),
+            "near: {:?}, far: {:?}",
+            near,
+            far
+        );
+    }
+
+    #[test]
+    fn fog_should_blend_the_colour_by_the_distance_travelled() {
+        let unfogged = sphere_at(3.0, None).colour_at(forwards());
+        let fogged = sphere_at(3.0, Some(FOG)).colour_at(forwards());
+
+        // the sphere is 2 units from the origin
+        let expected = unfogged.lerp(&FOG.colour, 1.0 - (-FOG.density * 2.0).exp());
+        assert_abs_diff_eq!(fogged, expected);
+    }
+
+    #[test]
+    fn a_ray_which_misses_everything_should_only_see_the_fog() {
+        let world = sphere_at(3.0, Some(FOG));
+
+        let backwards = Ray::new(Point3D::ORIGIN, Normal3D::POSITIVE_Z);
+        assert_eq!(world.colour_at(backwards), FOG.colour);
+    }
+
+    #[test]
+    fn fog_with_no_density_should_have_no_effect() {
+        let fog = FogSettings {
+            density: 0.0,
+            ..FOG
+        };
+
+        let backwards = Ray::new(Point3D::ORIGIN, Normal3D::POSITIVE_Z);
+        assert_eq!(
+            sphere_at(3.0, Some(fog)).colour_at(backwards),
+            Colour::BLACK
+        );
+        assert_eq!(
+            sphere_at(3.0, Some(fog)).colour_at(forwards()),
+            sphere_at(3.0, None).colour_at(forwards())
+        );
+    }
 }
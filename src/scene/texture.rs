@@ -0,0 +1,214 @@
+use std::fmt::{self, Debug, Formatter};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+use arc_swap::ArcSwapOption;
+use image::{Rgb, RgbImage};
+
+use crate::core::Colour;
+use crate::scene::WrapMode;
+
+/// an image used by a UV pattern - either always kept in memory, or owned by a `TextureCache`, which decodes it when needed
+#[derive(Clone, Debug, PartialEq)]
+pub struct Texture(TextureKind);
+
+#[derive(Clone, Debug)]
+enum TextureKind {
+    Resident(Arc<RgbImage>),
+    Cached {
+        cache: Arc<TextureCache>,
+        texture: Arc<CachedTexture>,
+    },
+}
+
+impl Texture {
+    /// true if the image is currently decoded in memory
+    pub fn is_resident(&self) -> bool {
+        match &self.0 {
+            TextureKind::Resident(_) => true,
+            TextureKind::Cached { texture, .. } => texture.image.load().is_some(),
+        }
+    }
+
+    pub(super) fn image(&self) -> Arc<RgbImage> {
+        match &self.0 {
+            TextureKind::Resident(image) => Arc::clone(image),
+            TextureKind::Cached { cache, texture } => cache.get(texture),
+        }
+    }
+}
+
+impl From<Arc<RgbImage>> for Texture {
+    fn from(image: Arc<RgbImage>) -> Self {
+        Texture(TextureKind::Resident(image))
+    }
+}
+
+impl PartialEq for TextureKind {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (TextureKind::Resident(lhs), TextureKind::Resident(rhs)) => lhs == rhs,
+            (
+                TextureKind::Cached { texture, .. },
+                TextureKind::Cached {
+                    texture: other_texture,
+                    ..
+                },
+            ) => Arc::ptr_eq(texture, other_texture),
+            _ => false,
+        }
+    }
+}
+
+/// Keeps decoded textures in memory up to a budget (in bytes), evicting the least recently used texture when the budget is exceeded.
+/// Textures are only kept in memory once they're first sampled, and are decoded again if sampled after being evicted, so the
+/// budget should be large enough for every texture visible in a single image, otherwise rendering will keep re-decoding them.
+///
+/// Sampling a texture which is already in memory never waits for other threads. Decoding happens outside of any lock, so a
+/// thread decoding a texture doesn't stall threads sampling other textures.
+///
+/// Note: the most recently used texture is never evicted, so a single texture larger than the budget is still usable
+pub struct TextureCache {
+    budget: usize,
+    /// every texture loaded into the cache - only locked when loading, or when a newly decoded texture is added
+    textures: Mutex<Vec<Arc<CachedTexture>>>,
+    resident_bytes: AtomicUsize,
+    clock: AtomicU64,
+}
+
+#[derive(Debug)]
+struct CachedTexture {
+    path: PathBuf,
+    image: ArcSwapOption<RgbImage>,
+    last_used: AtomicU64,
+}
+
+impl TextureCache {
+    pub fn new(budget_bytes: usize) -> Arc<Self> {
+        Arc::new(TextureCache {
+            budget: budget_bytes,
+            textures: Default::default(),
+            resident_bytes: AtomicUsize::new(0),
+            clock: AtomicU64::new(0),
+        })
+    }
+
+    /// Adds the image at `path` to the cache. The image is decoded once, so that a missing or corrupt file is reported
+    /// while the scene is built, rather than while rendering, but isn't kept in memory until it's sampled
+    pub fn load(self: &Arc<Self>, path: impl Into<PathBuf>) -> image::ImageResult<Texture> {
+        let path = path.into();
+        image::open(&path)?;
+
+        let texture = Arc::new(CachedTexture {
+            path,
+            image: ArcSwapOption::empty(),
+            last_used: AtomicU64::new(0),
+        });
+        self.textures.lock().unwrap().push(Arc::clone(&texture));
+
+        Ok(Texture(TextureKind::Cached {
+            cache: Arc::clone(self),
+            texture,
+        }))
+    }
+
+    /// the total size of the textures currently decoded in memory
+    pub fn resident_bytes(&self) -> usize {
+        self.resident_bytes.load(Ordering::SeqCst)
+    }
+
+    fn get(&self, texture: &Arc<CachedTexture>) -> Arc<RgbImage> {
+        let now = self.clock.fetch_add(1, Ordering::Relaxed) + 1;
+        texture.last_used.store(now, Ordering::Relaxed);
+
+        if let Some(image) = texture.image.load_full() {
+            return image;
+        }
+
+        // the file was successfully decoded when it was loaded, so this can only fail if it's changed since then - rendering
+        // a placeholder (which is obviously wrong) is preferable to abandoning the whole render
+        let image = image::open(&texture.path)
+            .map(|image| image.to_rgb8())
+            .unwrap_or_else(|_| RgbImage::from_pixel(1, 1, Rgb([255, 0, 255])));
+
+        self.insert(texture, Arc::new(image))
+    }
+
+    /// makes the decoded `image` resident, evicting other textures to stay within the budget
+    fn insert(&self, texture: &Arc<CachedTexture>, image: Arc<RgbImage>) -> Arc<RgbImage> {
+        let textures = self.textures.lock().unwrap();
+
+        // another thread may have decoded the same texture at the same time
+        if let Some(existing) = texture.image.load_full() {
+            return existing;
+        }
+
+        texture.image.store(Some(Arc::clone(&image)));
+        let mut resident_bytes = self
+            .resident_bytes
+            .fetch_add(image.as_raw().len(), Ordering::SeqCst)
+            + image.as_raw().len();
+
+        while resident_bytes > self.budget {
+            let least_recently_used = textures
+                .iter()
+                .filter(|other| !Arc::ptr_eq(other, texture) && other.image.load().is_some())
+                .min_by_key(|other| other.last_used.load(Ordering::Relaxed));
+
+            if let Some(evicted) = least_recently_used.and_then(|other| other.image.swap(None)) {
+                resident_bytes = self
+                    .resident_bytes
+                    .fetch_sub(evicted.as_raw().len(), Ordering::SeqCst)
+                    - evicted.as_raw().len();
+            } else {
+                break;
+            }
+        }
+
+        image
+    }
+}
+
+impl Debug for TextureCache {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TextureCache")
+            .field("budget", &self.budget)
+            .field("resident_bytes", &self.resident_bytes())
+            .finish()
+    }
+}
+
+/// caches are only equal to themselves, as two caches with the same contents will not necessarily evict the same textures
+impl PartialEq for TextureCache {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self, other)
+    }
+}
+
+/// Samples the image at the given UV coordinates, blending the four nearest pixels by how close each one is, rather than
+/// snapping to the nearest pixel (which makes magnified images blocky).
+///
+/// The centres of the pixels along each edge of the image lie exactly on the edges of the UV square, with `v` increasing
+/// up the image. Coordinates outside of `0..=1` are mapped back into it by the `wrap` mode
+pub(super) fn sample_bilinear(img: &RgbImage, u: f64, v: f64, wrap: WrapMode) -> Colour {
+    let x = wrap.apply(u) * (img.width() - 1) as f64;
+    let y = wrap.apply(1.0 - v) * (img.height() - 1) as f64;
+
+    let pixel = |x: u32, y: u32| {
+        let pixel = img.get_pixel(x.min(img.width() - 1), y.min(img.height() - 1));
+        Colour::new(
+            pixel.0[0] as f64 / 255.0,
+            pixel.0[1] as f64 / 255.0,
+            pixel.0[2] as f64 / 255.0,
+        )
+    };
+
+    let (left, top) = (x.floor() as u32, y.floor() as u32);
+    let (x_fraction, y_fraction) = (x.fract(), y.fract());
+
+    let upper = pixel(left, top).lerp(&pixel(left + 1, top), x_fraction);
+    let lower = pixel(left, top + 1).lerp(&pixel(left + 1, top + 1), x_fraction);
+
+    upper.lerp(&lower, y_fraction)
+}
@@ -1,14 +1,49 @@
 use crate::core::*;
-use crate::scene::intersection::{HitData, Intersections};
+use crate::renderer::Camera;
+use crate::scene::intersection::{HitData, Intersections, ReflectionData};
 use crate::scene::Material;
 use crate::scene::MaterialKind;
 use crate::scene::Object;
+use crate::scene::Sky;
 use crate::scene::{Light, LightSample};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// the angle (in radians) between a shadow ray and the neighbouring rays used to measure how much light is focused onto a
+/// point - see `World::caustic_focus`
+const CAUSTIC_SAMPLE_ANGLE: f64 = 1e-3;
+/// the most that refractive objects can concentrate light - see `World::caustic_focus`
+const MAX_CAUSTIC_FOCUS: f64 = 10.0;
 
 pub struct World {
     pub(super) objects: Vec<Object>,
     pub lights: Vec<Light>,
     pub settings: WorldSettings,
+    /// the ids of top-level objects which camera rays skip - see `World::cull`
+    culled: HashSet<u32>,
+}
+
+/// counts of the rays cast, and the bounding boxes tested, during a single render (see `renderer::render_with_stats`).
+/// Counting is optional, as every thread incrementing the same atomics would noticeably slow down rendering
+#[derive(Debug, Default)]
+pub(crate) struct Counters {
+    pub(crate) primary_rays: AtomicU64,
+    pub(crate) reflection_rays: AtomicU64,
+    pub(crate) refraction_rays: AtomicU64,
+    pub(crate) shadow_rays: AtomicU64,
+    pub(crate) ambient_occlusion_rays: AtomicU64,
+    pub(crate) bbox_tests: AtomicU64,
+}
+
+impl Counters {
+    /// adds one to the chosen `counter`, if counting
+    fn count(counters: Option<&Counters>, counter: fn(&Counters) -> &AtomicU64) {
+        if let Some(counters) = counters {
+            counter(counters).fetch_add(1, Ordering::Relaxed);
+        }
+    }
 }
 
 pub struct WorldSettings {
@@ -17,11 +52,57 @@ pub struct WorldSettings {
     pub recursion_depth: u8,
     /// Default colour returned when a ray doesn't intersect any objects
     pub sky_colour: Colour,
+    /// Environment sampled by rays which don't intersect any objects - if set, this replaces `sky_colour`
+    pub sky: Option<Sky>,
+    /// `(horizon, zenith)` colours blended by how steeply upwards a ray which doesn't intersect any objects is pointing -
+    /// rays pointing level or downwards get the horizon colour. If set, this replaces `sky_colour`, but not `sky`
+    pub sky_gradient: Option<(Colour, Colour)>,
+    /// Global fill light, applied to every shaded surface (tinted by the surface colour) regardless of
+    /// light sources or shadows, as opposed to the `ambient` of each material, which depends on the lights in the scene
+    pub ambient_light: Colour,
     /// how strongly the colour of a transparent material should affect the light passing through - works best with low values
     pub transparent_colour_tint: f64,
-    /// the soft limit of group sizes - lower values will create more, smaller, bounding boxes, which speeds up rendering of
+    /// the maximum number of shapes in each group of the bounding volume hierarchy (see `Object::bvh`) - lower values will create more, smaller, bounding boxes, which speeds up rendering of
     /// more complex scenes, but potentially increases rendering time of very simple scenes
     pub group_size_threshold: u8,
+    /// the number of rays averaged when a ray is reflected by or refracted through a rough material - more samples produce smoother results,
+    /// but multiply rendering time of rough materials. To limit the cost of nested rough materials, only rough surfaces hit
+    /// directly by camera rays are sampled multiple times - rough surfaces seen in reflections or through refractions are
+    /// sampled once
+    pub glossy_samples: u8,
+    /// seeds the random scattering of rays (e.g. through rough materials), such that rendering the same scene always produces
+    /// the same image, on any platform
+    pub seed: u64,
+    /// if set, ambient light is reduced in crevices and corners, based on how much of the surrounding area is blocked by
+    /// nearby objects - this makes complex shapes look much more solid, at the cost of casting `samples` extra rays per hit
+    pub ambient_occlusion: Option<AoSettings>,
+    /// intersections with the surface a shadow (or reflection, refraction, etc) ray was cast from are ignored within this distance
+    /// of the surface - too small, and rounding errors cause "acne" (speckled self-shadowing), especially on large scenes;
+    /// too large, and shadows become detached from the objects casting them ("peter-panning"), especially on tiny scenes
+    pub shadow_bias: f64,
+    /// if set, shadow rays are bent by each refractive material they pass through, and the light reaching the surface is
+    /// scaled by how much those materials concentrate (or spread out) the light - glass objects then focus light into bright
+    /// spots (caustics) within their shadows, rather than casting an evenly tinted shadow
+    pub caustics: bool,
+    /// if set, every colour seen along a ray is blended towards the fog colour, the further the ray travels before hitting
+    /// anything - rays which don't hit anything see only the fog. This is a simple distance fog, so fog isn't lit or shadowed
+    pub fog: Option<FogSettings>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct AoSettings {
+    /// the number of rays cast over the hemisphere around each hit - more samples produce less noisy results
+    pub samples: u8,
+    /// objects further than this from the hit don't occlude it
+    pub radius: f64,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct FogSettings {
+    pub colour: Colour,
+    /// how quickly the fog thickens with distance - the fraction of the original colour remaining after a distance `d` is
+    /// `e^(-density * d)`
+    pub density: f64,
 }
 
 impl Default for WorldSettings {
@@ -29,8 +110,17 @@ impl Default for WorldSettings {
         WorldSettings {
             recursion_depth: 5,
             sky_colour: Colour::BLACK,
+            sky: None,
+            sky_gradient: None,
+            ambient_light: Colour::BLACK,
             transparent_colour_tint: 0.1,
             group_size_threshold: 4,
+            glossy_samples: 8,
+            seed: 0,
+            ambient_occlusion: None,
+            shadow_bias: EPSILON,
+            caustics: false,
+            fog: None,
         }
     }
 }
@@ -41,6 +131,7 @@ impl World {
             objects: Vec::new(),
             lights: Vec::new(),
             settings: Default::default(),
+            culled: HashSet::new(),
         }
     }
 
@@ -61,36 +152,135 @@ impl World {
                 Point3D::new(-10.0, 10.0, -10.0),
             )],
             settings: Default::default(),
+            culled: HashSet::new(),
         }
     }
 
     pub fn add(&mut self, object: Object) {
         self.objects
-            .push(object.optimised(self.settings.group_size_threshold as _));
+            .push(object.bvh(self.settings.group_size_threshold as _));
+    }
+
+    /// Detaches the object with the given `id` from the world, searching within groups if necessary, and returns it
+    /// (including all of its children, if it's a group)
+    pub fn remove(&mut self, id: u32) -> Option<Object> {
+        if let Some(index) = self.objects.iter().position(|object| object.id() == id) {
+            return Some(self.objects.remove(index));
+        }
+
+        self.objects
+            .iter_mut()
+            .find_map(|object| object.remove_child(id))
+    }
+
+    /// the top-level objects in the world
+    pub fn objects(&self) -> impl Iterator<Item = &Object> {
+        self.objects.iter()
+    }
+
+    /// the top-level objects in the world, which can be updated in-place (e.g. between frames of an animation)
+    pub fn objects_mut(&mut self) -> impl Iterator<Item = &mut Object> {
+        self.objects.iter_mut()
+    }
+
+    /// Skips every top-level object whose bounding box lies entirely outside of the volume visible to the `camera` (see
+    /// `Camera::frustum_planes`) when intersecting rays cast from the camera. Shadow, reflection, and refraction rays still
+    /// intersect every object, as objects outside of the view can still cast shadows into it, or be seen in reflections.
+    ///
+    /// Objects which are only partially outside of the view, or which are unbounded, are never skipped. This replaces any
+    /// previous culling, and should be repeated if the camera or any objects move
+    pub fn cull(&mut self, camera: &Camera) {
+        let planes = camera.frustum_planes();
+
+        self.culled = self
+            .objects
+            .iter()
+            .filter(|object| !object.bounds.is_unbounded())
+            .filter(|object| {
+                let (min, max) = object.bounds_world();
+
+                planes.iter().any(|(normal, distance)| {
+                    // the corner of the box furthest along the normal - if it's outside, the whole box is outside
+                    let furthest = Point3D::new(
+                        if normal.x() >= 0.0 { max.x() } else { min.x() },
+                        if normal.y() >= 0.0 { max.y() } else { min.y() },
+                        if normal.z() >= 0.0 { max.z() } else { min.z() },
+                    );
+
+                    normal.dot(furthest - Point3D::ORIGIN) + distance < 0.0
+                })
+            })
+            .map(|object| object.id())
+            .collect();
     }
 
     pub fn colour_at(&self, ray: Ray) -> Colour {
-        fn inner(this: &World, ray: Ray, last_hit: Option<u32>, limit: u8) -> Colour {
+        self.colour_at_counted(ray, None)
+    }
+
+    /// As `colour_at`, but adds every ray cast, and every bounding box tested, to the `counters`, if provided
+    pub(crate) fn colour_at_counted(&self, ray: Ray, counters: Option<&Counters>) -> Colour {
+        /// `visible` determines which objects the ray can see, depending on whether it's a camera ray or a reflection
+        fn inner(
+            this: &World,
+            ray: Ray,
+            last_hit: Option<u32>,
+            limit: u8,
+            visible: fn(&Material) -> bool,
+            counters: Option<&Counters>,
+        ) -> Colour {
             if limit == 0 {
                 return Colour::BLACK;
             }
 
-            let intersections = this.intersect(&ray);
-            if let Some(hit) = intersections.hit(last_hit) {
+            let bias = this.settings.shadow_bias;
+            // only rays cast from the camera have no previous hit
+            let from_camera = last_hit.is_none();
+            let nearest =
+                this.intersect_objects(&ray, Some((last_hit, bias)), from_camera, counters);
+            let (hit, intersections) = match nearest.hit_beyond(last_hit, bias) {
+                // finding the nearest hit skips objects beyond it, which would now be visible
+                Some(hit) if !visible(&hit.with.material) => {
+                    let intersections = this.intersect_objects(&ray, None, from_camera, counters);
+                    let hit = intersections
+                        .iter()
+                        .filter(|i| i.is_hit_beyond(last_hit, bias))
+                        .find(|i| visible(&i.with.material))
+                        .cloned();
+
+                    (hit, intersections)
+                }
+                hit => (hit, nearest),
+            };
+
+            // not infinite, so zero density fog has no effect rather than producing NaN
+            let distance = hit.as_ref().map_or(f64::MAX, |hit| hit.t);
+            let colour = if let Some(hit) = hit {
+                let distance = hit.t;
                 let hit_data = HitData::from(&ray, hit, intersections);
-                let surface = this.shade_hit(&hit_data);
+                let surface = this.shade_hit_counted(&hit_data, counters);
 
                 let reflected = if hit_data.object.material.reflective == 0.0 {
                     Colour::BLACK
                 } else {
                     let reflection_vector =
                         ray.direction.normalised().reflect_through(hit_data.normal);
-                    let reflection = Ray::new(hit_data.point, reflection_vector);
-                    inner(this, reflection, Some(hit_data.object.id()), limit - 1)
-                        * hit_data.object.material.reflective
+                    let roughness = hit_data.object.material.roughness;
+
+                    scattered(
+                        this,
+                        &hit_data,
+                        reflection_vector,
+                        hit_data.normal,
+                        roughness,
+                        limit,
+                        |material| material.visible_in_reflections,
+                        counters,
+                        |counters| &counters.reflection_rays,
+                    ) * hit_data.object.material.reflective
                 };
 
-                if hit_data.object.material.transparency == 0.0 {
+                let colour = if hit_data.object.material.transparency == 0.0 {
                     surface + reflected
                 } else {
                     // check for total internal reflection
@@ -99,14 +289,24 @@ impl World {
                     let refracted = if reflection_data.is_total() {
                         Colour::BLACK
                     } else {
-                        let refracted_direction =
-                            reflection_data.refraction_vector(hit_data.normal, hit_data.eye);
+                        let refracted_direction = reflection_data
+                            .refraction_vector(hit_data.normal, hit_data.eye)
+                            .normalised();
+                        let roughness = hit_data.object.material.refraction_roughness;
 
-                        let refracted_ray =
-                            Ray::new(hit_data.point, refracted_direction.normalised());
+                        let refracted = scattered(
+                            this,
+                            &hit_data,
+                            refracted_direction,
+                            -hit_data.normal,
+                            roughness,
+                            limit,
+                            visible,
+                            counters,
+                            |counters| &counters.refraction_rays,
+                        );
 
-                        inner(this, refracted_ray, Some(hit_data.object.id()), limit - 1)
-                            * hit_data.object.material.transparency
+                        refracted * hit_data.object.material.transparency
                     };
 
                     if hit_data.object.material.reflective > 0.0 {
@@ -117,33 +317,164 @@ impl World {
                     } else {
                         surface + reflected + refracted
                     }
+                };
+
+                // the ray has travelled through the medium since it was refracted into it (or since it was cast, if the
+                // camera is inside the medium), so the distance to the hit is the distance travelled through the medium
+                match hit_data.medium {
+                    Some(medium) => colour * absorbed_over(medium.material.absorption, distance),
+                    None => colour,
                 }
+            } else if let Some(sky) = &this.settings.sky {
+                sky.colour_at(ray.direction)
+            } else if let Some((horizon, zenith)) = this.settings.sky_gradient {
+                let height = ray.direction.normalised().y().clamp(0.0, 1.0);
+                horizon.lerp(&zenith, height)
             } else {
                 this.settings.sky_colour
+            };
+
+            match this.settings.fog {
+                Some(fog) => colour.lerp(&fog.colour, 1.0 - (-fog.density * distance).exp()),
+                None => colour,
             }
         }
 
-        inner(self, ray, None, self.settings.recursion_depth)
+        /// casts a ray from the hit in the given `direction` - if `roughness` is non-zero, the ray is randomly scattered,
+        /// and the first rough surface along each ray path averages multiple samples. Each ray cast is added to the `counter`
+        #[allow(clippy::too_many_arguments)]
+        fn scattered(
+            this: &World,
+            hit_data: &HitData,
+            direction: Normal3D,
+            into_surface: Normal3D,
+            roughness: f64,
+            limit: u8,
+            visible: fn(&Material) -> bool,
+            counters: Option<&Counters>,
+            counter: fn(&Counters) -> &AtomicU64,
+        ) -> Colour {
+            let last_hit = Some(hit_data.object.id());
+            if roughness == 0.0 {
+                let ray = Ray::new(hit_data.point, direction).with_time(hit_data.time);
+                Counters::count(counters, counter);
+
+                return inner(this, ray, last_hit, limit - 1, visible, counters);
+            }
+
+            let mut rng = this.rng_at(hit_data.point);
+            let samples = if limit == this.settings.recursion_depth {
+                this.settings.glossy_samples.max(1)
+            } else {
+                1
+            };
+
+            let total = (0..samples)
+                .map(|_| {
+                    let direction = scatter(direction, into_surface, roughness, &mut rng);
+                    let ray = Ray::new(hit_data.point, direction).with_time(hit_data.time);
+                    Counters::count(counters, counter);
+
+                    inner(this, ray, last_hit, limit - 1, visible, counters)
+                })
+                .fold(Colour::BLACK, |acc, next| acc + next);
+
+            total / samples as f64
+        }
+
+        Counters::count(counters, |counters| &counters.primary_rays);
+        inner(
+            self,
+            ray,
+            None,
+            self.settings.recursion_depth,
+            |material| material.visible_to_camera,
+            counters,
+        )
+    }
+
+    /// the first object hit by the `ray` (if any), along with the distance along the ray and the world-space point of the hit
+    pub fn cast(&self, ray: Ray) -> Option<(&Object, f64, Point3D)> {
+        let hit = self
+            .intersect_nearest(&ray, None, EPSILON, None)
+            .hit(None)?;
+
+        Some((hit.with, hit.t, ray.position(hit.t)))
+    }
+
+    /// a random number generator which is deterministic for each `point`, regardless of which thread renders it - see `seed_from`
+    fn rng_at(&self, point: Point3D) -> StdRng {
+        StdRng::seed_from_u64(seed_from(&[
+            self.settings.seed,
+            point.x().to_bits(),
+            point.y().to_bits(),
+            point.z().to_bits(),
+        ]))
     }
 
+    #[cfg(test)]
     pub(super) fn intersect(&self, ray: &Ray) -> Intersections {
+        self.intersect_objects(ray, None, false, None)
+    }
+
+    /// all intersections up to (and including) the nearest hit beyond the `last` object hit - see
+    /// `Object::intersect_counted`
+    fn intersect_nearest(
+        &self,
+        ray: &Ray,
+        last: Option<u32>,
+        bias: f64,
+        counters: Option<&Counters>,
+    ) -> Intersections<'_> {
+        self.intersect_objects(ray, Some((last, bias)), false, counters)
+    }
+
+    /// if the ray is cast `from_camera`, objects which have been culled (see `World::cull`) are skipped
+    fn intersect_objects(
+        &self,
+        ray: &Ray,
+        nearest_beyond: Option<(Option<u32>, f64)>,
+        from_camera: bool,
+        counters: Option<&Counters>,
+    ) -> Intersections<'_> {
+        let bbox_tests = counters.map(|counters| &counters.bbox_tests);
+
         self.objects
             .iter()
-            .map(|obj| obj.intersect(&ray))
+            .filter(|obj| !(from_camera && self.culled.contains(&obj.id())))
+            .map(|obj| obj.intersect_counted(ray, nearest_beyond, bbox_tests))
             .fold(Intersections::empty(), Intersections::join)
     }
 
+    #[cfg(test)]
     pub(super) fn shade_hit(&self, hit_data: &HitData) -> Colour {
-        self.lights
+        self.shade_hit_counted(hit_data, None)
+    }
+
+    fn shade_hit_counted(&self, hit_data: &HitData, counters: Option<&Counters>) -> Colour {
+        let unoccluded = self.settings.ambient_occlusion.map_or(1.0, |settings| {
+            self.unoccluded_fraction(hit_data, settings, counters)
+        });
+
+        let ambient_fill = if self.settings.ambient_light == Colour::BLACK {
+            Colour::BLACK
+        } else {
+            hit_data.raw_colour() * self.settings.ambient_light * unoccluded
+        };
+
+        // seeded by the point, so the jittered area light samples are the same regardless of which thread shades the point
+        let mut rng = self.rng_at(hit_data.point);
+        let lit = self
+            .lights
             .iter()
             .map(|light| {
-                let (samples, n_samples) = light.samples();
+                let n_samples = light.samples().1;
 
-                let sum = samples
+                let sum = light
+                    .jittered_samples(&mut rng)
                     .map(|point| {
-                        let sample = LightSample::new(*point, light.colour());
-                        let direct_light =
-                            self.direct_light(hit_data.point, &sample, hit_data.object.id());
+                        let sample = LightSample::new(point, light.colour());
+                        let direct_light = self.direct_light(hit_data, light, &sample, counters);
 
                         hit_data.colour(direct_light, &sample)
                     })
@@ -151,27 +482,107 @@ impl World {
 
                 sum / (n_samples as f64)
             })
-            .sum()
+            .sum::<Colour>();
+
+        // the ambient term of each light doesn't depend on shadows, so it can be removed from the lit colour after the fact
+        let occluded_ambient = if unoccluded < 1.0 {
+            let light_colours = self.lights.iter().map(Light::colour).sum::<Colour>();
+
+            hit_data.raw_colour()
+                * light_colours
+                * hit_data.object.material.ambient
+                * (1.0 - unoccluded)
+        } else {
+            Colour::BLACK
+        };
+
+        lit - occluded_ambient + ambient_fill + hit_data.object.material.emission
+    }
+
+    /// the fraction of rays cast over the hemisphere around the hit which don't hit anything within the AO `radius`
+    fn unoccluded_fraction(
+        &self,
+        hit_data: &HitData,
+        settings: AoSettings,
+        counters: Option<&Counters>,
+    ) -> f64 {
+        let mut rng = self.rng_at(hit_data.point);
+        let samples = settings.samples.max(1);
+
+        let escaped = (0..samples)
+            .filter(|_| {
+                let direction = loop {
+                    let offset = Vector3D::new(
+                        rng.gen_range(-1.0..1.0),
+                        rng.gen_range(-1.0..1.0),
+                        rng.gen_range(-1.0..1.0),
+                    );
+
+                    let magnitude = offset.magnitude();
+                    if magnitude <= 1.0 && magnitude.is_not_roughly_zero() {
+                        break offset.normalised();
+                    }
+                };
+                let direction = if direction.dot(hit_data.normal) < 0.0 {
+                    -direction
+                } else {
+                    direction
+                };
+
+                let ray = Ray::new(hit_data.point, direction).with_time(hit_data.time);
+                Counters::count(counters, |counters| &counters.ambient_occlusion_rays);
+
+                let last = Some(hit_data.object.id());
+                let bias = self.settings.shadow_bias;
+                match self
+                    .intersect_nearest(&ray, last, bias, counters)
+                    .hit_beyond(last, bias)
+                {
+                    Some(hit) => hit.t >= settings.radius,
+                    None => true,
+                }
+            })
+            .count();
+
+        escaped as f64 / samples as f64
     }
 
-    fn direct_light(&self, point: Point3D, light: &LightSample, target_id: u32) -> Colour {
+    fn direct_light(
+        &self,
+        hit_data: &HitData,
+        source: &Light,
+        light: &LightSample,
+        counters: Option<&Counters>,
+    ) -> Colour {
+        let point = hit_data.point;
+        let target_id = hit_data.object.id();
+
+        // e.g. outside of the cone of a spot light - no need to check for shadows
+        let intensity = source.intensity_at(point);
+        if intensity == 0.0 {
+            return Colour::BLACK;
+        }
+        let light_colour = light.colour * intensity;
+
         let light_vector = light.position - point;
         let light_distance = light_vector.magnitude();
 
         // if light source is exactly at the intersection point, use full intensity
         if light_distance.is_roughly_zero() {
-            return light.colour;
+            return light_colour;
         }
 
         let light_vector = light_vector.normalised();
 
-        let ray = Ray::new(point, light_vector);
+        let ray = Ray::new(point, light_vector).with_time(hit_data.time);
+        Counters::count(counters, |counters| &counters.shadow_rays);
 
-        self.intersect(&ray)
+        let shadowed = self
+            .intersect_objects(&ray, None, false, counters)
             .into_iter()
-            .filter(|i| i.with.id() != target_id || i.t.is_not_roughly_zero())
+            .filter(|i| i.with.id() != target_id || i.t.abs() > self.settings.shadow_bias)
             .filter(|i| i.t >= 0.0 && i.t < light_distance)
-            .fold(light.colour, |light, hit| {
+            .fold(light_colour, |light, hit| {
                 if light == Colour::BLACK {
                     return Colour::BLACK;
                 }
@@ -182,7 +593,7 @@ impl World {
                         return Colour::BLACK;
                     }
 
-                    let hit_colour = hit.with.raw_colour_at(ray.position(hit.t));
+                    let hit_colour = hit.raw_colour_at(ray.position(hit.t), ray.time);
                     // plain glass, etc, don't have a colour, and shouldn't change the colour of light passing though
                     if hit_colour == Colour::BLACK {
                         return light * hit.with.material.transparency;
@@ -209,6 +620,174 @@ impl World {
                     // object doesn't affect shadow calculations
                     light
                 }
-            })
+            });
+
+        if self.settings.caustics && shadowed != Colour::BLACK {
+            shadowed * self.caustic_focus(ray, target_id, light.position, counters)
+        } else {
+            shadowed
+        }
+    }
+
+    /// How much light from the light source is focused onto the start of the shadow `ray`, by any refractive materials in
+    /// its path - 1 if the ray isn't bent, below 1 if the light is spread out (or the ray is bent away from the light), or
+    /// above 1 (up to `MAX_CAUSTIC_FOCUS`) if the light is concentrated.
+    ///
+    /// Light rays are reversible, so the shadow ray and two neighbours a tiny angle either side of it are bent through each
+    /// material as light would be, and the concentration is the solid angle between the neighbours before bending, divided
+    /// by the solid angle between them afterwards - i.e. how much wider the cone of light focused onto the point is than the
+    /// cone which left the light source. This treats the light as infinitely far away compared to the size of the
+    /// refractive objects. The concentration is then reduced by how far the bent ray points away from the light
+    fn caustic_focus(
+        &self,
+        ray: Ray,
+        target_id: u32,
+        light_position: Point3D,
+        counters: Option<&Counters>,
+    ) -> f64 {
+        let bent = match self.refract_towards_light(&ray, target_id, light_position, counters) {
+            Some((bent, true)) => bent,
+            Some((_, false)) => return 1.0,
+            None => return 0.0,
+        };
+
+        let to_light = (light_position - bent.origin).normalised();
+        let alignment = bent.direction.dot(to_light).max(0.0);
+        if alignment == 0.0 {
+            return 0.0;
+        }
+
+        // any vector which isn't parallel to the ray can be used to find two directions perpendicular to it
+        let direction = ray.direction.normalised();
+        let side = if direction.x().abs() < 0.9 {
+            Normal3D::POSITIVE_X
+        } else {
+            Normal3D::POSITIVE_Y
+        };
+        let first_offset = direction.cross(side).normalised() * CAUSTIC_SAMPLE_ANGLE;
+        let second_offset = direction.cross(first_offset).normalised() * CAUSTIC_SAMPLE_ANGLE;
+
+        let neighbour = |offset: Vector3D| {
+            let ray = Ray::new(ray.origin, (direction + offset).normalised()).with_time(ray.time);
+            Counters::count(counters, |counters| &counters.shadow_rays);
+
+            match self.refract_towards_light(&ray, target_id, light_position, counters) {
+                Some((bent, true)) => Some((ray.direction, bent.direction)),
+                _ => None,
+            }
+        };
+
+        let concentration = match (neighbour(first_offset), neighbour(second_offset)) {
+            (Some((first_before, first_after)), Some((second_before, second_after))) => {
+                let solid_angle = |first: Vector3D, second: Vector3D, centre: Vector3D| {
+                    (first - centre).cross(second - centre).magnitude()
+                };
+
+                let before = solid_angle(first_before, second_before, direction.into());
+                let after = solid_angle(first_after, second_after, bent.direction);
+                // perfectly focused light would be infinitely bright
+                (before / after).min(MAX_CAUSTIC_FOCUS)
+            }
+            // a neighbour missed the refractive objects, or was totally internally reflected, so the point is on the edge
+            // of a caustic
+            _ => 1.0,
+        };
+
+        alignment * concentration
+    }
+
+    /// Follows the shadow `ray` through every refractive material in its path towards the light, bending it at each surface,
+    /// and returns the ray leaving the last material (or the original ray), and whether it was bent at all, or `None` if it's
+    /// totally internally reflected. Each refractive material is assumed to be surrounded by air
+    fn refract_towards_light(
+        &self,
+        ray: &Ray,
+        target_id: u32,
+        light_position: Point3D,
+        counters: Option<&Counters>,
+    ) -> Option<(Ray, bool)> {
+        let mut ray = ray.clone();
+        let mut last = target_id;
+        let mut refracted = false;
+
+        for _ in 0..self.settings.recursion_depth {
+            let light_distance = (light_position - ray.origin).magnitude();
+            let intersections = self.intersect_objects(&ray, None, false, counters);
+            let hit = intersections
+                .all_hits()
+                .filter(|i| i.with.id() != last || i.t > self.settings.shadow_bias)
+                .take_while(|i| i.t < light_distance)
+                .find(|i| {
+                    let material = &i.with.material;
+                    material.casts_shadow
+                        && material.transparency > 0.0
+                        && material.refractive != 1.0
+                });
+
+            let hit = match hit {
+                Some(hit) => hit,
+                None => break,
+            };
+
+            let point = ray.position(hit.t);
+            let normal = hit.normal_at(point, ray.time);
+            let eye = (-ray.direction).normalised();
+            let refractive = hit.with.material.refractive;
+            let (normal, entered, exited) = if eye.dot(normal) > 0.0 {
+                (normal, 1.0, refractive)
+            } else {
+                (-normal, refractive, 1.0)
+            };
+
+            let refraction = ReflectionData::new(eye, normal, entered, exited);
+            if refraction.is_total() {
+                return None;
+            }
+
+            let direction = refraction.refraction_vector(normal, eye).normalised();
+            ray = Ray::new(point, direction).with_time(ray.time);
+            Counters::count(counters, |counters| &counters.shadow_rays);
+            last = hit.with.id();
+            refracted = true;
+        }
+
+        Some((ray, refracted))
+    }
+}
+
+/// the fraction of each colour of light remaining after travelling `distance` through a material - see `Material::absorption`
+fn absorbed_over(absorption: Colour, distance: f64) -> Colour {
+    Colour::new(
+        (-absorption.red() * distance).exp(),
+        (-absorption.green() * distance).exp(),
+        (-absorption.blue() * distance).exp(),
+    )
+}
+
+/// randomly offsets the `direction` within a sphere of radius `roughness`, without pushing it past the surface
+/// (i.e. the result always points the same way as `into_surface`)
+fn scatter(
+    direction: Normal3D,
+    into_surface: Normal3D,
+    roughness: f64,
+    rng: &mut StdRng,
+) -> Normal3D {
+    let offset = loop {
+        let offset = Vector3D::new(
+            rng.gen_range(-1.0..1.0),
+            rng.gen_range(-1.0..1.0),
+            rng.gen_range(-1.0..1.0),
+        );
+
+        if offset.magnitude() <= 1.0 {
+            break offset;
+        }
+    };
+
+    let scattered = (Vector3D::from(direction) + offset * roughness).normalised();
+    if scattered.dot(into_surface) > 0.0 {
+        scattered
+    } else {
+        direction
     }
 }
@@ -0,0 +1,165 @@
+use std::convert::TryInto;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::core::{Point3D, Vector3D, VectorMaths, EPSILON};
+use crate::scene::Object;
+use anyhow::*;
+
+#[cfg(test)]
+mod tests;
+
+const HEADER_LENGTH: usize = 80;
+const FACET_LENGTH: usize = 50;
+
+pub struct StlParser {
+    resource_path: PathBuf,
+}
+
+impl StlParser {
+    pub fn new(resource_path: PathBuf) -> Self {
+        Self { resource_path }
+    }
+
+    /// loads an ASCII or binary STL file as a group of triangles
+    pub fn load(&self, file_name: &str) -> anyhow::Result<Object> {
+        if !file_name.ends_with(".stl") {
+            bail!("{} is not a .stl file", file_name);
+        };
+
+        let file = self.resource_path.join(file_name);
+        println!("loading STL file {}", file.to_str().unwrap());
+        let contents = fs::read(file)?;
+
+        parse_stl(&contents)
+    }
+}
+
+fn parse_stl(contents: &[u8]) -> anyhow::Result<Object> {
+    // binary files can start with `solid` too, so the only reliable way to tell them apart is the expected file size
+    if is_binary(contents) {
+        return parse_binary(contents);
+    }
+
+    match std::str::from_utf8(contents) {
+        Ok(text) if text.trim_start().starts_with("solid") => parse_ascii(text),
+        _ => bail!("not a valid ASCII or binary STL file"),
+    }
+}
+
+fn is_binary(contents: &[u8]) -> bool {
+    if contents.len() < HEADER_LENGTH + 4 {
+        return false;
+    }
+
+    let facets = read_u32(contents, HEADER_LENGTH) as usize;
+    contents.len() == HEADER_LENGTH + 4 + facets * FACET_LENGTH
+}
+
+fn parse_binary(contents: &[u8]) -> anyhow::Result<Object> {
+    let facets = read_u32(contents, HEADER_LENGTH) as usize;
+
+    let triangles = (0..facets)
+        .map(|index| {
+            let offset = HEADER_LENGTH + 4 + index * FACET_LENGTH;
+            let vector = |at: usize| {
+                let start = offset + at * 12;
+                (
+                    read_f32(contents, start) as f64,
+                    read_f32(contents, start + 4) as f64,
+                    read_f32(contents, start + 8) as f64,
+                )
+            };
+
+            // the trailing 2 bytes are an "attribute byte count", which is almost always unused
+            let (nx, ny, nz) = vector(0);
+            let vertices = [1, 2, 3].map(|at| {
+                let (x, y, z) = vector(at);
+                Point3D::new(x, y, z)
+            });
+
+            to_triangle(vertices, Vector3D::new(nx, ny, nz))
+        })
+        .collect();
+
+    Ok(Object::group(triangles))
+}
+
+fn parse_ascii(contents: &str) -> anyhow::Result<Object> {
+    let mut triangles = vec![];
+    let mut normal = None;
+    let mut vertices = Vec::with_capacity(3);
+
+    for (line_number, line) in contents.lines().enumerate() {
+        let mut tokens = line.split_whitespace();
+
+        match tokens.next() {
+            Some("facet") => {
+                if tokens.next() != Some("normal") {
+                    bail!("expected `facet normal` on line {}", line_number + 1);
+                }
+                let (x, y, z) = parse_triple(tokens, line_number)?;
+                normal = Some(Vector3D::new(x, y, z));
+                vertices.clear();
+            }
+            Some("vertex") => {
+                let (x, y, z) = parse_triple(tokens, line_number)?;
+                vertices.push(Point3D::new(x, y, z));
+            }
+            Some("endfacet") => {
+                let normal = normal
+                    .take()
+                    .ok_or_else(|| anyhow!("unexpected `endfacet` on line {}", line_number + 1))?;
+
+                if vertices.len() != 3 {
+                    bail!(
+                        "facet ending on line {} has {} vertices; expected 3",
+                        line_number + 1,
+                        vertices.len()
+                    );
+                }
+
+                triangles.push(to_triangle([vertices[0], vertices[1], vertices[2]], normal));
+            }
+            // `solid`, `outer loop`, `endloop`, and `endsolid` carry no information
+            _ => (),
+        }
+    }
+
+    Ok(Object::group(triangles))
+}
+
+/// many exporters write a zero normal and expect the reader to calculate it from the winding order
+fn to_triangle(vertices: [Point3D; 3], normal: Vector3D) -> Object {
+    let [p1, p2, p3] = vertices;
+
+    if normal.magnitude() > EPSILON {
+        let normal = normal.normalised();
+        Object::smooth_triangle(p1, p2, p3, normal, normal, normal)
+    } else {
+        Object::triangle(p1, p2, p3)
+    }
+}
+
+fn parse_triple<'a>(
+    mut tokens: impl Iterator<Item = &'a str>,
+    line_number: usize,
+) -> anyhow::Result<(f64, f64, f64)> {
+    let mut next = || -> anyhow::Result<f64> {
+        tokens
+            .next()
+            .ok_or_else(|| anyhow!("expected 3 numbers on line {}", line_number + 1))?
+            .parse()
+            .with_context(|| format!("invalid number on line {}", line_number + 1))
+    };
+
+    Ok((next()?, next()?, next()?))
+}
+
+fn read_u32(bytes: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes(bytes[offset..offset + 4].try_into().unwrap())
+}
+
+fn read_f32(bytes: &[u8], offset: usize) -> f32 {
+    f32::from_le_bytes(bytes[offset..offset + 4].try_into().unwrap())
+}
@@ -0,0 +1,210 @@
+use super::*;
+use crate::core::{Normal3D, Ray};
+
+const ASCII_CUBE: &str = "solid cube
+  facet normal 0 0 -1
+    outer loop
+      vertex 0 0 0
+      vertex 0 1 0
+      vertex 1 1 0
+    endloop
+  endfacet
+  facet normal 0 0 -1
+    outer loop
+      vertex 0 0 0
+      vertex 1 1 0
+      vertex 1 0 0
+    endloop
+  endfacet
+  facet normal 0 0 1
+    outer loop
+      vertex 0 0 1
+      vertex 1 0 1
+      vertex 1 1 1
+    endloop
+  endfacet
+  facet normal 0 0 1
+    outer loop
+      vertex 0 0 1
+      vertex 1 1 1
+      vertex 0 1 1
+    endloop
+  endfacet
+  facet normal 0 -1 0
+    outer loop
+      vertex 0 0 0
+      vertex 1 0 0
+      vertex 1 0 1
+    endloop
+  endfacet
+  facet normal 0 -1 0
+    outer loop
+      vertex 0 0 0
+      vertex 1 0 1
+      vertex 0 0 1
+    endloop
+  endfacet
+  facet normal 0 1 0
+    outer loop
+      vertex 0 1 0
+      vertex 0 1 1
+      vertex 1 1 1
+    endloop
+  endfacet
+  facet normal 0 1 0
+    outer loop
+      vertex 0 1 0
+      vertex 1 1 1
+      vertex 1 1 0
+    endloop
+  endfacet
+  facet normal -1 0 0
+    outer loop
+      vertex 0 0 0
+      vertex 0 0 1
+      vertex 0 1 1
+    endloop
+  endfacet
+  facet normal -1 0 0
+    outer loop
+      vertex 0 0 0
+      vertex 0 1 1
+      vertex 0 1 0
+    endloop
+  endfacet
+  facet normal 1 0 0
+    outer loop
+      vertex 1 0 0
+      vertex 1 1 0
+      vertex 1 1 1
+    endloop
+  endfacet
+  facet normal 1 0 0
+    outer loop
+      vertex 1 0 0
+      vertex 1 1 1
+      vertex 1 0 1
+    endloop
+  endfacet
+endsolid cube";
+
+/// builds a binary STL file from (normal, vertices) pairs
+fn binary_stl(facets: &[([f32; 3], [[f32; 3]; 3])]) -> Vec<u8> {
+    let mut bytes = vec![0; HEADER_LENGTH];
+    bytes.extend_from_slice(&(facets.len() as u32).to_le_bytes());
+
+    for (normal, vertices) in facets {
+        let floats = normal.iter().chain(vertices.iter().flatten());
+        floats.for_each(|f| bytes.extend_from_slice(&f.to_le_bytes()));
+        bytes.extend_from_slice(&[0, 0]);
+    }
+
+    bytes
+}
+
+#[test]
+fn an_ascii_cube_should_produce_a_group_of_twelve_triangles() {
+    let cube = parse_stl(ASCII_CUBE.as_bytes());
+    assert!(cube.is_ok(), "{}", cube.unwrap_err());
+    let cube = cube.unwrap();
+
+    assert_eq!(cube.children().len(), 12);
+
+    let ray = Ray::new(Point3D::new(0.25, 0.75, -5.0), Normal3D::POSITIVE_Z);
+    let ts = cube.intersect(&ray).iter().map(|i| i.t).collect::<Vec<_>>();
+    assert_eq!(ts, vec![5.0, 6.0]);
+}
+
+#[test]
+fn ascii_facet_normals_should_be_used_as_the_triangle_normals() {
+    let cube = parse_stl(ASCII_CUBE.as_bytes()).unwrap();
+
+    let bottom = &cube.children()[0];
+    assert_eq!(
+        bottom.normal_at(Point3D::new(0.25, 0.5, 0.0)),
+        Normal3D::NEGATIVE_Z
+    );
+    let right = &cube.children()[11];
+    assert_eq!(
+        right.normal_at(Point3D::new(1.0, 0.25, 0.5)),
+        Normal3D::POSITIVE_X
+    );
+}
+
+#[test]
+fn a_binary_stl_should_produce_a_group_of_triangles() {
+    let bytes = binary_stl(&[
+        (
+            [0.0, 0.0, -1.0],
+            [[0.0, 0.0, 0.0], [0.0, 1.0, 0.0], [1.0, 1.0, 0.0]],
+        ),
+        (
+            [0.0, 0.0, -1.0],
+            [[0.0, 0.0, 0.0], [1.0, 1.0, 0.0], [1.0, 0.0, 0.0]],
+        ),
+    ]);
+
+    let square = parse_stl(&bytes);
+    assert!(square.is_ok(), "{}", square.unwrap_err());
+    let square = square.unwrap();
+
+    assert_eq!(square.children().len(), 2);
+    let ray = Ray::new(Point3D::new(0.75, 0.25, -1.0), Normal3D::POSITIVE_Z);
+    assert_eq!(square.intersect(&ray).len(), 1);
+    assert_eq!(
+        square.children()[1].normal_at(Point3D::new(0.75, 0.25, 0.0)),
+        Normal3D::NEGATIVE_Z
+    );
+}
+
+#[test]
+fn a_binary_stl_with_a_header_starting_with_solid_should_still_be_parsed_as_binary() {
+    let mut bytes = binary_stl(&[(
+        [0.0, 0.0, 1.0],
+        [[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]],
+    )]);
+    bytes[..5].copy_from_slice(b"solid");
+
+    let triangle = parse_stl(&bytes);
+    assert!(triangle.is_ok(), "{}", triangle.unwrap_err());
+    assert_eq!(triangle.unwrap().children().len(), 1);
+}
+
+#[test]
+fn a_zero_facet_normal_should_be_calculated_from_the_winding_order() {
+    let bytes = binary_stl(&[(
+        [0.0, 0.0, 0.0],
+        [[0.0, 1.0, 0.0], [-1.0, 0.0, 0.0], [1.0, 0.0, 0.0]],
+    )]);
+
+    let triangle = parse_stl(&bytes).unwrap();
+    assert_eq!(
+        triangle.children()[0].normal_at(Point3D::new(0.0, 0.5, 0.0)),
+        Normal3D::NEGATIVE_Z
+    );
+}
+
+#[test]
+fn an_ascii_facet_without_three_vertices_should_fail_to_parse() {
+    let input = "solid broken
+facet normal 0 0 1
+outer loop
+vertex 0 0 0
+vertex 1 0 0
+endloop
+endfacet
+endsolid broken";
+
+    assert!(parse_stl(input.as_bytes()).is_err());
+}
+
+#[test]
+fn a_file_that_is_neither_ascii_nor_binary_stl_should_fail_to_parse() {
+    assert!(parse_stl(b"not an stl file").is_err());
+}
+
+#[test]
+fn loading_a_file_without_the_stl_extension_should_fail() {
+    let parser = StlParser::new(PathBuf::new());
+    assert!(parser.load("model.obj").is_err());
+}
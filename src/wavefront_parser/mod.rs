@@ -1,14 +1,16 @@
 use std::borrow::Borrow;
 use std::collections::HashMap;
+use std::convert::TryInto;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::str::SplitWhitespace;
 
 use crate::core::{Colour, Point3D, Vector3D, VectorMaths};
 use crate::scene::Object;
-use crate::scene::{Material, MaterialKind};
+use crate::scene::{Material, MaterialKind, UvPattern};
 use anyhow::*;
 use std::cell::RefCell;
+use std::sync::Arc;
 
 #[cfg(test)]
 mod tests;
@@ -53,6 +55,26 @@ impl WavefrontParser {
             .to_object()
     }
 
+    /// loads every `.obj` file directly inside the resource directory, keyed by file name - other files and subdirectories are skipped
+    pub fn load_all(&self) -> anyhow::Result<HashMap<String, Object>> {
+        let mut objects = HashMap::new();
+
+        for entry in fs::read_dir(&self.resource_path)? {
+            let entry = entry?;
+            if !entry.file_type()?.is_file() {
+                continue;
+            }
+
+            if let Some(file_name) = entry.file_name().to_str() {
+                if file_name.ends_with(".obj") {
+                    objects.insert(file_name.to_owned(), self.load(file_name)?);
+                }
+            }
+        }
+
+        Ok(objects)
+    }
+
     fn load_mtl_libraries(&self, file: &str) -> anyhow::Result<()> {
         file.lines()
             .map(|line| line.trim())
@@ -84,11 +106,13 @@ impl WavefrontParser {
 
         let file = self.resource_path.join(format!("{}.mtl", file_name));
         println!("loading MTL file {}", file.to_str().unwrap());
-        let contents = fs::read_to_string(file)?;
+        let contents = fs::read_to_string(&file)?;
+        // texture maps are relative to the MTL file, which isn't necessarily in the resource directory itself
+        let mtl_directory = file.parent().unwrap_or(&self.resource_path);
 
         self.mtl_cache
             .borrow_mut()
-            .insert(file_name.to_string(), parse_mtl(&contents)?);
+            .insert(file_name.to_string(), parse_mtl(&contents, mtl_directory)?);
 
         Ok(())
     }
@@ -98,6 +122,7 @@ impl WavefrontParser {
 
         let mut vertices = vec![];
         let mut normals = vec![];
+        let mut texture_vertices: Vec<Vec<(f64, f64)>> = vec![];
         let mut polys = vec![];
         let mut groups = vec![];
         let mut loaded_materials = HashMap::new();
@@ -105,11 +130,13 @@ impl WavefrontParser {
 
         input
             .lines()
-            .map(|line| line.trim())
-            .map(|line| {
+            .enumerate()
+            // line numbers start at 1, to match text editors
+            .map(|(index, line)| (index + 1, line.trim()))
+            .map(|(line_number, line)| {
                 let mut parts = line.split_whitespace();
 
-                match parts.next() {
+                let parsed = match parts.next() {
                     Some("mtllib") => {
                         let file_names = line.chars().skip("mttlib ".len()).collect::<String>();
                         let materials = file_names
@@ -135,9 +162,33 @@ impl WavefrontParser {
                         })
                     }
                     Some("v") => parse_vertex(parts).map(|v| vertices.push(v)),
-                    Some("f") => parse_polygon(parts, current_material.cloned()).map(|p| polys.push(p)),
+                    Some("f") => {
+                        let texture_count = texture_vertices.first().map_or(0, Vec::len);
+                        let counts = (vertices.len(), texture_count, normals.len());
+
+                        parse_polygon(parts, current_material.cloned(), counts, line_number).map(|p| polys.push(p))
+                    }
                     Some("vn") => parse_normal(parts).map(|n| normals.push(n)),
-                    Some("g") => {
+                    // `vt` defines the primary set of texture coordinates; non-standard `vt1`, `vt2`, etc statements
+                    // define additional UV channels, which share the texture vertex indices of the faces
+                    Some(statement) if statement.starts_with("vt") => {
+                        let channel = match &statement["vt".len()..] {
+                            "" => Some(0),
+                            suffix => suffix.parse::<usize>().ok(),
+                        };
+
+                        if let Some(channel) = channel {
+                            if texture_vertices.len() <= channel {
+                                texture_vertices.resize_with(channel + 1, Vec::new);
+                            }
+
+                            parse_texture_vertex(parts).map(|vt| texture_vertices[channel].push(vt))
+                        } else {
+                            Ok(())
+                        }
+                    }
+                    // objects (`o`) are treated the same as groups (`g`), as the distinction doesn't matter when rendering
+                    Some("g" | "o") => {
                         if !polys.is_empty() {
                             let polygons = std::mem::take(&mut polys);
                             groups.push(Group { polygons });
@@ -156,7 +207,9 @@ impl WavefrontParser {
                         })
                     }
                     _ => Ok(()),
-                }
+                };
+
+                parsed.map_err(|e| at_line(line_number, e))
             })
             .collect::<anyhow::Result<()>>()?;
 
@@ -167,14 +220,17 @@ impl WavefrontParser {
         Ok(ObjData {
             vertices,
             normals,
+            texture_vertices,
             groups,
         })
     }
 }
 
-pub fn parse_mtl(input: &str) -> anyhow::Result<Materials> {
+/// `resource_path` is the directory that texture maps (e.g. `map_Kd`) are loaded from
+pub fn parse_mtl(input: &str, resource_path: &Path) -> anyhow::Result<Materials> {
     MaterialParser {
         input,
+        resource_path,
         current: None,
         materials: HashMap::new(),
     }
@@ -192,6 +248,7 @@ impl Materials {
 
 struct MaterialParser<'input> {
     input: &'input str,
+    resource_path: &'input Path,
     current: Option<(&'input str, Material)>,
     materials: HashMap<String, Material>,
 }
@@ -220,8 +277,13 @@ impl<'input> MaterialParser<'input> {
                     // and the most accurate conversion seems to be to parse the diffuse as the colour, and leave the default
                     // diffuse strength of 0.9
                     Some("Kd") => {
-                        self.current_material()?.kind =
-                            MaterialKind::Solid(parse_colour(&mut parts)?)
+                        let colour = parse_colour(&mut parts)?;
+                        let material = self.current_material()?;
+
+                        // a texture map takes priority over the diffuse colour, regardless of which is defined first
+                        if !matches!(material.kind, MaterialKind::Uv(_)) {
+                            material.kind = MaterialKind::Solid(colour)
+                        }
                     }
                     // MTL ambience appears to be a percentage of the _scene_ ambience, which doesn't match the
                     // way the ray tracer models ambience - parsing MTL values directly to material `ambient` will
@@ -230,6 +292,20 @@ impl<'input> MaterialParser<'input> {
                     Some("Ka") => {
                         self.current_material()?.ambient = parse_rgb_to_f64(&mut parts)? * 0.1
                     }
+                    // options such as `-s` and `-o` aren't supported, so only the file name (which must come last) is used
+                    Some("map_Kd") => {
+                        let file_name = parts.last().ok_or_else(|| {
+                            anyhow!("`map_Kd` statement does not name the texture")
+                        })?;
+                        let file_path = self.resource_path.join(file_name);
+                        let image = image::open(&file_path).with_context(|| {
+                            format!("failed to load texture map from {:?}", file_path)
+                        })?;
+
+                        self.current_material()?.kind =
+                            MaterialKind::Uv(UvPattern::image(Arc::new(image.to_rgb8()).into()))
+                    }
+                    Some("Ke") => self.current_material()?.emission = parse_colour(&mut parts)?,
                     Some("Ks") => self.current_material()?.specular = parse_rgb_to_f64(&mut parts)?,
                     Some("Ns") => {
                         if let Some(shininess) = parts.next().and_then(|s| s.parse::<f64>().ok()) {
@@ -327,15 +403,22 @@ fn parse_colour(iterator: &mut SplitWhitespace) -> anyhow::Result<Colour> {
             let green = iterator.next().and_then(|g| g.parse::<f64>().ok());
             let blue = iterator.next().and_then(|b| b.parse::<f64>().ok());
 
-            if let Some(green) = green {
-                if let Some(blue) = blue {
-                    Ok(Colour::new(red, green, blue))
-                } else {
+            let (green, blue) = match (green, blue) {
+                (Some(green), Some(blue)) => (green, blue),
+                (Some(_), None) => {
                     bail!("Invalid RGB colour in statement - must either specify 1 f64 value or 3")
                 }
-            } else {
-                Ok(Colour::greyscale(red))
+                (None, _) => (red, red),
+            };
+
+            if ![red, green, blue]
+                .iter()
+                .all(|component| component.is_finite())
+            {
+                bail!("colour components must be finite");
             }
+
+            Ok(Colour::new(red, green, blue))
         }
     }
 }
@@ -363,6 +446,11 @@ fn parse_rgb_to_f64(iterator: &mut SplitWhitespace) -> anyhow::Result<f64> {
     }
 }
 
+/// prefixes the `error` with the `line` of the OBJ file it was found on, keeping any context it already has
+fn at_line(line: usize, error: Error) -> Error {
+    anyhow!("line {}: {:#}", line, error)
+}
+
 fn parse_vertex(mut line_parts: SplitWhitespace) -> anyhow::Result<Point3D> {
     let mut next = || {
         line_parts
@@ -377,33 +465,81 @@ fn parse_vertex(mut line_parts: SplitWhitespace) -> anyhow::Result<Point3D> {
     Ok(Point3D::new(next()?, next()?, next()?))
 }
 
+fn parse_texture_vertex(mut line_parts: SplitWhitespace) -> anyhow::Result<(f64, f64)> {
+    let u = line_parts
+        .next()
+        .ok_or_else(|| anyhow!("missing line part"))?;
+    let u = u.parse::<f64>().map_err(|e| {
+        anyhow!(
+            "unparseable texture vertex data `{}` ({})",
+            u,
+            e.to_string()
+        )
+    })?;
+
+    // `v` is optional, and defaults to 0; the optional `w` is ignored as 3D textures aren't supported
+    let v = line_parts
+        .next()
+        .map(|v| {
+            v.parse::<f64>().map_err(|e| {
+                anyhow!(
+                    "unparseable texture vertex data `{}` ({})",
+                    v,
+                    e.to_string()
+                )
+            })
+        })
+        .transpose()?
+        .unwrap_or(0.0);
+
+    Ok((u, v))
+}
+
+/// `counts` are the number of vertices, texture vertices, and normals defined so far, which negative (relative) indices count back from.
+/// The `line` number is kept so that invalid references can be reported once every vertex has been parsed
 fn parse_polygon(
     line_parts: SplitWhitespace,
     material: Option<Material>,
+    counts: (usize, usize, usize),
+    line: usize,
 ) -> anyhow::Result<Polygon> {
-    fn parse_usize(s: &str) -> anyhow::Result<usize> {
-        s.parse::<usize>()
-            .map_err(|e| anyhow!("Unparseable polygon data `{}` ({})", s, e.to_string()))
+    fn parse_index(s: &str, count: usize) -> anyhow::Result<usize> {
+        let index = s
+            .parse::<isize>()
+            .map_err(|e| anyhow!("Unparseable polygon data `{}` ({})", s, e.to_string()))?;
+
+        if index >= 0 {
+            Ok(index as usize)
+        } else {
+            // -1 refers to the most recently defined element
+            (count as isize + index + 1)
+                .try_into()
+                .ok()
+                .filter(|&index| index > 0)
+                .ok_or_else(|| anyhow!("relative index `{}` is out of range", s))
+        }
     }
 
+    let (vertex_count, texture_count, normal_count) = counts;
+
     let vertices = line_parts
         .map(|part| {
             let mut parts = part.split('/');
             let vertex = parts
                 .next()
                 .ok_or_else(|| anyhow!("Invalid polygon data: `{}`", part))?;
-            let vertex = parse_usize(vertex)?;
+            let vertex = parse_index(vertex, vertex_count)?;
 
-            let mut next = || {
+            let mut next = |count| {
                 parts
                     .next()
                     .filter(|&s| !s.is_empty())
-                    .map(parse_usize)
+                    .map(|s| parse_index(s, count))
                     .transpose()
             };
 
-            let texture_vertex = next()?;
-            let normal = next()?;
+            let texture_vertex = next(texture_count)?;
+            let normal = next(normal_count)?;
 
             Ok(VertexData {
                 vertex,
@@ -413,7 +549,11 @@ fn parse_polygon(
         })
         .collect::<anyhow::Result<Vec<_>>>()?;
 
-    Ok(Polygon { vertices, material })
+    Ok(Polygon {
+        vertices,
+        material,
+        line,
+    })
 }
 
 fn parse_normal(mut line_parts: SplitWhitespace) -> anyhow::Result<Vector3D> {
@@ -434,6 +574,8 @@ fn parse_normal(mut line_parts: SplitWhitespace) -> anyhow::Result<Vector3D> {
 struct Polygon {
     vertices: Vec<VertexData>,
     material: Option<Material>,
+    /// the line of the OBJ file the face was defined on
+    line: usize,
 }
 
 #[derive(Debug, PartialEq)]
@@ -452,15 +594,23 @@ struct VertexData {
 pub struct ObjData {
     vertices: Vec<Point3D>,
     normals: Vec<Vector3D>,
+    /// one list of texture vertices per UV channel
+    texture_vertices: Vec<Vec<(f64, f64)>>,
     groups: Vec<Group>,
 }
 
 impl ObjData {
     fn vertex(&self, index: usize) -> Option<Point3D> {
-        self.vertices.get(index - 1).copied()
+        self.vertices.get(index.checked_sub(1)?).copied()
     }
     fn normal(&self, index: usize) -> Option<Vector3D> {
-        self.normals.get(index - 1).copied()
+        self.normals.get(index.checked_sub(1)?).copied()
+    }
+    fn texture_vertex(&self, channel: usize, index: usize) -> Option<(f64, f64)> {
+        self.texture_vertices
+            .get(channel)
+            .and_then(|vts| vts.get(index.checked_sub(1)?))
+            .copied()
     }
 
     pub fn to_object(&self) -> anyhow::Result<Object> {
@@ -471,13 +621,15 @@ impl ObjData {
                 for face in triangulate(&polygon.vertices) {
                     let mut vertices = Vec::with_capacity(3);
                     let mut normals = Vec::with_capacity(3);
+                    let mut texture_indices = Vec::with_capacity(3);
 
-                    for &(vert_index, normal_index) in face.iter() {
+                    for &(vert_index, texture_index, normal_index) in face.iter() {
                         if let Some(vertex) = self.vertex(vert_index) {
                             vertices.push(vertex)
                         } else {
                             bail!(
-                                "invalid vertex reference `{}` in face {:?}",
+                                "line {}: invalid vertex reference `{}` in face {:?}",
+                                polygon.line,
                                 vert_index,
                                 polygon
                             );
@@ -488,15 +640,76 @@ impl ObjData {
                                 normals.push(normal)
                             } else {
                                 bail!(
-                                    "invalid normal reference `{}` in face {:?}",
+                                    "line {}: invalid normal reference `{}` in face {:?}",
+                                    polygon.line,
                                     normal_index,
                                     polygon
                                 );
                             }
                         }
+
+                        if let Some(texture_index) = texture_index {
+                            texture_indices.push(texture_index);
+                        }
                     }
 
-                    let triangle = if normals.is_empty() {
+                    // texture references are meaningless if the file doesn't define any texture vertices
+                    let texture_coordinates = if self.texture_vertices.is_empty()
+                        || texture_indices.is_empty()
+                    {
+                        vec![]
+                    } else if texture_indices.len() == 3 {
+                        (0..self.texture_vertices.len())
+                            .map(|channel| {
+                                let mut uvs = [(0.0, 0.0); 3];
+
+                                for (uv, &index) in uvs.iter_mut().zip(texture_indices.iter()) {
+                                    *uv = self.texture_vertex(channel, index).ok_or_else(|| {
+                                        anyhow!(
+                                            "line {}: invalid texture vertex reference `{}` (channel {}) in face {:?}",
+                                            polygon.line,
+                                            index,
+                                            channel,
+                                            polygon
+                                        )
+                                    })?;
+                                }
+
+                                Ok(uvs)
+                            })
+                            .collect::<anyhow::Result<Vec<_>>>()?
+                    } else {
+                        bail!(
+                            "line {}: Face {:?} must either have texture vertices for all vertices or none",
+                            polygon.line,
+                            polygon
+                        );
+                    };
+
+                    let triangle = if !texture_coordinates.is_empty() {
+                        let normals = match normals.len() {
+                            0 => None,
+                            // see below re: normalising
+                            3 => Some([
+                                normals[0].normalised(),
+                                normals[1].normalised(),
+                                normals[2].normalised(),
+                            ]),
+                            _ => bail!(
+                                "line {}: Face {:?} must either have normals for all faces or no faces",
+                                polygon.line,
+                                polygon
+                            ),
+                        };
+
+                        Object::textured_triangle(
+                            vertices[0],
+                            vertices[1],
+                            vertices[2],
+                            normals,
+                            texture_coordinates,
+                        )
+                    } else if normals.is_empty() {
                         Object::triangle(vertices[0], vertices[1], vertices[2])
                     } else if normals.len() == 3 {
                         Object::smooth_triangle(
@@ -510,7 +723,8 @@ impl ObjData {
                         )
                     } else {
                         bail!(
-                            "Face {:?} must either have normals for all faces or no faces",
+                            "line {}: Face {:?} must either have normals for all faces or no faces",
+                            polygon.line,
                             polygon
                         );
                     };
@@ -541,15 +755,23 @@ impl ObjData {
     }
 }
 
-fn triangulate(face: &[VertexData]) -> Vec<[(usize, Option<usize>); 3]> {
+type TriangleIndices = (usize, Option<usize>, Option<usize>);
+
+fn triangulate(face: &[VertexData]) -> Vec<[TriangleIndices; 3]> {
+    let indices = face
+        .iter()
+        .map(|v| (v.vertex, v.texture_vertex, v.normal))
+        .collect::<Vec<_>>();
+
+    fan(&indices)
+}
+
+/// splits a convex polygon into triangles which all share the first vertex
+pub(crate) fn fan<T: Copy>(face: &[T]) -> Vec<[T; 3]> {
     let mut out = vec![];
 
     for i in 2..face.len() {
-        out.push([
-            (face[0].vertex, face[0].normal),
-            (face[i - 1].vertex, face[i - 1].normal),
-            (face[i].vertex, face[i].normal),
-        ]);
+        out.push([face[0], face[i - 1], face[i]]);
     }
 
     out
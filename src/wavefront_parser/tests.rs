@@ -12,7 +12,7 @@ but this parser is very lazy
 and doesn't care about that
 ¯\\_(ツ)_/¯";
 
-        let out = parse_mtl(invalid_file);
+        let out = parse_mtl(invalid_file, Path::new(""));
         assert!(out.is_ok(), "{}", out.unwrap_err());
         let out = out.unwrap();
         assert_eq!(out, Materials(HashMap::new()));
@@ -35,7 +35,7 @@ Ni 1.450000
 d 1.000000
 illum 2";
 
-            let out = parse_mtl(input);
+            let out = parse_mtl(input, Path::new(""));
             assert!(out.is_err(), "expected parsing to fail, but it succeeded");
             assert_eq!(&out.unwrap_err().to_string(), "A material must be defined with a `newmtl` statement before material properties can be defined");
         }
@@ -46,7 +46,7 @@ illum 2";
 newmtl awful_green
 Kd 0.425245 0.800000 0.011982";
 
-            let materials = parse_mtl(input);
+            let materials = parse_mtl(input, Path::new(""));
             assert!(materials.is_ok(), "{}", materials.unwrap_err());
             let materials = materials.unwrap();
 
@@ -59,13 +59,88 @@ Kd 0.425245 0.800000 0.011982";
             );
         }
 
+        #[test]
+        fn a_ke_rgb_statement_should_define_the_emission_colour() {
+            let input = "
+newmtl lamp
+Ke 1.0 0.5 0.25";
+
+            let materials = parse_mtl(input, Path::new(""));
+            assert!(materials.is_ok(), "{}", materials.unwrap_err());
+            let materials = materials.unwrap();
+
+            let lamp = materials.get("lamp");
+            assert!(lamp.is_some());
+            assert_eq!(lamp.unwrap().emission, Colour::new(1.0, 0.5, 0.25));
+        }
+
+        mod texture_maps {
+            use super::*;
+            use crate::util::TestDirectory;
+            use image::{Rgb, RgbImage};
+
+            /// writes a 2x2 texture to a new temporary directory, and returns that directory
+            fn texture_directory(name: &str) -> TestDirectory {
+                let dir = TestDirectory::new();
+                dir.write_image(name, &RgbImage::from_pixel(2, 2, Rgb([255, 0, 0])));
+
+                dir
+            }
+
+            #[test]
+            fn a_map_kd_statement_should_define_a_uv_material() {
+                let dir = texture_directory("red_texture.png");
+                let input = "
+newmtl textured
+map_Kd red_texture.png";
+
+                let materials = parse_mtl(input, dir.path());
+                assert!(materials.is_ok(), "{}", materials.unwrap_err());
+                let materials = materials.unwrap();
+
+                let textured = materials.get("textured").unwrap();
+                assert!(
+                    matches!(textured.kind, MaterialKind::Uv(_)),
+                    "{:?}",
+                    textured.kind
+                );
+            }
+
+            #[test]
+            fn a_map_kd_statement_should_take_priority_over_a_later_kd_statement() {
+                let dir = texture_directory("red_texture_with_kd.png");
+                let input = "
+newmtl textured
+map_Kd red_texture_with_kd.png
+Kd 0.5 0.5 0.5";
+
+                let materials = parse_mtl(input, dir.path()).unwrap();
+
+                let textured = materials.get("textured").unwrap();
+                assert!(
+                    matches!(textured.kind, MaterialKind::Uv(_)),
+                    "{:?}",
+                    textured.kind
+                );
+            }
+
+            #[test]
+            fn a_map_kd_statement_referencing_a_missing_file_should_fail() {
+                let input = "
+newmtl textured
+map_Kd does_not_exist.png";
+
+                assert!(parse_mtl(input, &std::env::temp_dir()).is_err());
+            }
+        }
+
         #[test]
         fn a_kd_statement_with_a_single_value_should_define_the_material_greyscale_colour() {
             let input = "
 newmtl overcast_grey
 Kd 0.7";
 
-            let materials = parse_mtl(input);
+            let materials = parse_mtl(input, Path::new(""));
             assert!(materials.is_ok(), "{}", materials.unwrap_err());
             let materials = materials.unwrap();
 
@@ -75,13 +150,32 @@ Kd 0.7";
             assert_eq!(grey.kind, MaterialKind::Solid(Colour::greyscale(0.7)));
         }
 
+        #[test]
+        fn a_kd_statement_with_a_non_finite_value_should_fail() {
+            let input = "
+newmtl broken
+Kd nan 0 0";
+
+            let materials = parse_mtl(input, Path::new(""));
+            assert!(
+                materials.is_err(),
+                "expected parsing to fail, but it succeeded"
+            );
+            assert_eq!(
+                materials.unwrap_err().to_string(),
+                "colour components must be finite"
+            );
+
+            assert!(parse_mtl("newmtl broken\nKd inf", Path::new("")).is_err());
+        }
+
         #[test]
         fn an_ns_statement_should_define_the_materials_shininess() {
             let input = "
 newmtl blinding
 Ns 1000";
 
-            let materials = parse_mtl(input);
+            let materials = parse_mtl(input, Path::new(""));
             assert!(materials.is_ok(), "{}", materials.unwrap_err());
             let materials = materials.unwrap();
 
@@ -110,7 +204,7 @@ newmtl ambient
                     ka
                 );
 
-                let materials = parse_mtl(&input);
+                let materials = parse_mtl(&input, Path::new(""));
                 assert!(materials.is_ok(), "{}", materials.unwrap_err());
                 let materials = materials.unwrap();
 
@@ -140,7 +234,7 @@ newmtl specular
                     ks
                 );
 
-                let materials = parse_mtl(&input);
+                let materials = parse_mtl(&input, Path::new(""));
                 assert!(materials.is_ok(), "{}", materials.unwrap_err());
                 let materials = materials.unwrap();
 
@@ -157,7 +251,7 @@ newmtl specular
 newmtl refractive
 Ni 1.45";
 
-            let materials = parse_mtl(input);
+            let materials = parse_mtl(input, Path::new(""));
             assert!(materials.is_ok(), "{}", materials.unwrap_err());
             let materials = materials.unwrap();
 
@@ -173,7 +267,7 @@ Ni 1.45";
 newmtl opaque
 d 1.000";
 
-            let materials = parse_mtl(input);
+            let materials = parse_mtl(input, Path::new(""));
             assert!(materials.is_ok(), "{}", materials.unwrap_err());
             let materials = materials.unwrap();
 
@@ -189,7 +283,7 @@ d 1.000";
 newmtl transparent
 d 0.000";
 
-            let materials = parse_mtl(input);
+            let materials = parse_mtl(input, Path::new(""));
             assert!(materials.is_ok(), "{}", materials.unwrap_err());
             let materials = materials.unwrap();
 
@@ -232,7 +326,7 @@ illum {}",
                         idx
                     );
 
-                    let materials = parse_mtl(&input);
+                    let materials = parse_mtl(&input, Path::new(""));
                     assert!(materials.is_ok(), "{}", materials.unwrap_err());
                     let materials = materials.unwrap();
 
@@ -262,7 +356,7 @@ Ni 1.450000
 d 1.000000
 illum 2";
 
-            let materials = parse_mtl(input);
+            let materials = parse_mtl(input, Path::new(""));
             assert!(materials.is_ok(), "{}", materials.unwrap_err());
             let materials = materials.unwrap();
 
@@ -329,7 +423,7 @@ illum 4
 # the rest of the example materials definitely won't work properly
 ";
 
-        let materials = parse_mtl(input);
+        let materials = parse_mtl(input, Path::new(""));
         assert!(materials.is_ok(), "{}", materials.unwrap_err());
         let materials = materials.unwrap();
 
@@ -464,6 +558,100 @@ f 1 2 3 4 5";
         assert_eq!(out.groups[0].polygons[0].vertices(), vec![1, 2, 3, 4, 5]);
     }
 
+    #[test]
+    fn parser_should_resolve_negative_face_indices_relative_to_the_latest_vertex() {
+        let parser = WavefrontParser::new(PathBuf::new());
+
+        let input = "v 0 1 0
+v -1 0 0
+v 1 0 0
+
+f -3 -2 -1";
+
+        let out = parser.parse_obj(input);
+        assert!(out.is_ok(), "{}", out.unwrap_err());
+        let out = out.unwrap();
+
+        assert_eq!(out.groups[0].polygons[0].vertices(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn negative_face_indices_should_only_count_vertices_defined_before_the_face() {
+        let parser = WavefrontParser::new(PathBuf::new());
+
+        let input = "v 0 1 0
+v -1 0 0
+v 1 0 0
+vn 0 0 -1
+vt 0 0
+f -3/-1/-1 -2/-1/-1 -1/-1/-1
+v 0 2 0
+v -1 1 0
+v 1 1 0
+vn 0 0 1
+vt 1 1
+f -3/-1/-1 -2/-1/-1 -1/-1/-1";
+
+        let out = parser.parse_obj(input);
+        assert!(out.is_ok(), "{}", out.unwrap_err());
+        let out = out.unwrap();
+
+        let first = &out.groups[0].polygons[0];
+        assert_eq!(first.vertices(), vec![1, 2, 3]);
+        assert_eq!(first.vertices[0].texture_vertex, Some(1));
+        assert_eq!(first.vertices[0].normal, Some(1));
+
+        let second = &out.groups[0].polygons[1];
+        assert_eq!(second.vertices(), vec![4, 5, 6]);
+        assert_eq!(second.vertices[0].texture_vertex, Some(2));
+        assert_eq!(second.vertices[0].normal, Some(2));
+    }
+
+    #[test]
+    fn a_negative_face_index_before_the_first_vertex_should_fail_to_parse() {
+        let parser = WavefrontParser::new(PathBuf::new());
+
+        let input = "v 0 1 0
+v -1 0 0
+f -3 -2 -1";
+
+        assert!(parser.parse_obj(input).is_err());
+    }
+
+    #[test]
+    fn a_malformed_face_should_report_the_line_it_was_defined_on() {
+        let parser = WavefrontParser::new(PathBuf::new());
+
+        let input = "v 0 1 0
+v -1 0 0
+f 1 2 x";
+
+        let error = parser.parse_obj(input).unwrap_err().to_string();
+        assert!(error.starts_with("line 3: "), "{}", error);
+    }
+
+    #[test]
+    fn an_invalid_vertex_reference_should_report_the_line_the_face_was_defined_on() {
+        let parser = WavefrontParser::new(PathBuf::new());
+
+        let input = "v 0 1 0
+v -1 0 0
+v 1 0 0
+
+f 1 2 3
+f 1 2 99999";
+
+        let out = parser.parse_obj(input);
+        assert!(out.is_ok(), "{}", out.unwrap_err());
+
+        let error = out.unwrap().to_object().unwrap_err().to_string();
+        assert!(
+            error.starts_with("line 6: invalid vertex reference `99999`"),
+            "{}",
+            error
+        );
+    }
+
     #[test]
     fn obj_data_should_be_convertible_to_group_containing_parsed_faces() {
         let parser = WavefrontParser::new(PathBuf::new());
@@ -494,7 +682,8 @@ f 1 2 3 4 5";
             edge1: Vector3D(2.0, -1.0, 0.0), \
             edge2: Vector3D(2.0, -1.0, 0.0), \
             denominator: inf, \
-            kind: Uniform(Normal3D(0.0, 0.0, 0.0)) \
+            kind: Uniform(Normal3D(0.0, 0.0, 0.0)), \
+            texture_coordinates: [] \
             }"
             .to_string()
         );
@@ -508,7 +697,8 @@ f 1 2 3 4 5";
             edge1: Vector3D(2.0, -1.0, 0.0), \
             edge2: Vector3D(2.0, 0.0, 0.0), \
             denominator: 0.25, \
-            kind: Uniform(Normal3D(0.0, 0.0, -1.0)) \
+            kind: Uniform(Normal3D(0.0, 0.0, -1.0)), \
+            texture_coordinates: [] \
             }"
             .to_string()
         );
@@ -543,7 +733,8 @@ f 1 2 3 4 5";
             edge1: Vector3D(0.0, -1.0, 0.0), \
             edge2: Vector3D(2.0, -1.0, 0.0), \
             denominator: 0.25, \
-            kind: Uniform(Normal3D(0.0, 0.0, -1.0)) \
+            kind: Uniform(Normal3D(0.0, 0.0, -1.0)), \
+            texture_coordinates: [] \
             }"
             .to_string()
         );
@@ -557,7 +748,8 @@ f 1 2 3 4 5";
             edge1: Vector3D(2.0, -1.0, 0.0), \
             edge2: Vector3D(2.0, 0.0, 0.0), \
             denominator: 0.25, \
-            kind: Uniform(Normal3D(0.0, 0.0, -1.0)) \
+            kind: Uniform(Normal3D(0.0, 0.0, -1.0)), \
+            texture_coordinates: [] \
             }"
             .to_string()
         );
@@ -571,7 +763,8 @@ f 1 2 3 4 5";
              edge1: Vector3D(2.0, 0.0, 0.0), \
              edge2: Vector3D(1.0, 1.0, 0.0), \
              denominator: 0.25, \
-             kind: Uniform(Normal3D(0.0, 0.0, -1.0)) \
+             kind: Uniform(Normal3D(0.0, 0.0, -1.0)), \
+             texture_coordinates: [] \
              }"
             .to_string()
         );
@@ -626,6 +819,35 @@ f 1 2 3 4 5";
         assert_eq!(object.children()[1].children().len(), 1);
     }
 
+    #[test]
+    fn converting_obj_data_with_multiple_named_objects_should_create_a_group_with_subgroups() {
+        let parser = WavefrontParser::new(PathBuf::new());
+
+        let input = "v -1 1 0
+        v -1 0 0
+        v 1 0 0
+        v 1 1 0
+        v 0 2 0
+
+        o FirstObject
+        f 1 2 3
+        o SecondObject
+        f 1 3 4
+        f 1 4 5";
+
+        let output = parser.parse_obj(input);
+        assert!(output.is_ok(), "{}", output.unwrap_err());
+        let output = output.unwrap();
+
+        let object = output.to_object();
+        assert!(object.is_ok(), "{}", object.unwrap_err());
+        let object = object.unwrap();
+
+        assert_eq!(object.children().len(), 2);
+        assert_eq!(object.children()[0].children().len(), 1);
+        assert_eq!(object.children()[1].children().len(), 2);
+    }
+
     #[test]
     fn obj_parser_should_parse_vertex_normals() {
         let parser = WavefrontParser::new(PathBuf::new());
@@ -749,7 +971,8 @@ f 1 2 3 4 5";
             normal1: Normal3D(0.0, 1.0, 0.0), \
             normal2: Normal3D(-1.0, 0.0, 0.0), \
             normal3: Normal3D(1.0, 0.0, 0.0) \
-            } \
+            }, \
+            texture_coordinates: [] \
             }"
             .to_string()
         );
@@ -767,12 +990,165 @@ f 1 2 3 4 5";
             normal1: Normal3D(0.0, 1.0, 0.0), \
             normal2: Normal3D(-1.0, 0.0, 0.0), \
             normal3: Normal3D(1.0, 0.0, 0.0) \
-            } \
+            }, \
+            texture_coordinates: [] \
             }"
             .to_string()
         );
     }
 
+    #[test]
+    fn obj_parser_should_parse_texture_vertices_into_uv_channels() {
+        let parser = WavefrontParser::new(PathBuf::new());
+
+        let input = "vt 0 0
+        vt 1 0.5
+        vt 0.25
+        
+        vt1 0.5 0.5
+        vt1 1 1";
+
+        let output = parser.parse_obj(input);
+        assert!(output.is_ok(), "{}", output.unwrap_err());
+        let output = output.unwrap();
+
+        assert_eq!(output.texture_vertices.len(), 2);
+        assert_eq!(output.texture_vertex(0, 1), Some((0.0, 0.0)));
+        assert_eq!(output.texture_vertex(0, 2), Some((1.0, 0.5)));
+        assert_eq!(output.texture_vertex(0, 3), Some((0.25, 0.0)));
+        assert_eq!(output.texture_vertex(1, 1), Some((0.5, 0.5)));
+        assert_eq!(output.texture_vertex(1, 2), Some((1.0, 1.0)));
+        assert_eq!(output.texture_vertex(1, 3), None);
+    }
+
+    #[test]
+    fn uv_patterns_should_be_able_to_sample_different_uv_channels_of_the_same_mesh() {
+        use crate::scene::UvPattern;
+        use image::{Rgb, RgbImage};
+        use std::sync::Arc;
+
+        let parser = WavefrontParser::new(PathBuf::new());
+
+        // a unit square, where channel 0 covers the whole texture, and channel 1 (e.g. a lightmap)
+        // only covers the bottom left quarter
+        let input = "v 0 0 0
+        v 1 0 0
+        v 1 1 0
+        v 0 1 0
+        
+        vt 0 0
+        vt 1 0
+        vt 1 1
+        vt 0 1
+        
+        vt1 0 0
+        vt1 0.5 0
+        vt1 0.5 0.5
+        vt1 0 0.5
+        
+        f 1/1 2/2 3/3
+        f 1/1 3/3 4/4";
+
+        let output = parser.parse_obj(input);
+        assert!(output.is_ok(), "{}", output.unwrap_err());
+        let output = output.unwrap();
+
+        let checkers = UvPattern::checkers(
+            Colour::WHITE,
+            Colour::BLACK,
+            nonzero_ext::nonzero!(2usize),
+            nonzero_ext::nonzero!(2usize),
+        );
+        let object = output.to_object();
+        assert!(object.is_ok(), "{}", object.unwrap_err());
+        let object = object.unwrap().with_material(Material {
+            kind: MaterialKind::Uv(checkers),
+            ..Default::default()
+        });
+
+        let lower = &object.children()[0];
+        assert_eq!(
+            lower.raw_colour_at(Point3D::new(0.3, 0.2, 0.0)),
+            Colour::WHITE
+        );
+        assert_eq!(
+            lower.raw_colour_at(Point3D::new(0.8, 0.2, 0.0)),
+            Colour::BLACK
+        );
+
+        // red over (a little more than) the bottom left quarter, so that blending neighbouring pixels doesn't pull in any blue
+        let lightmap = RgbImage::from_fn(4, 4, |x, y| match (x, y) {
+            (0..=2, 1..=3) => Rgb([255, 0, 0]),
+            _ => Rgb([0, 0, 255]),
+        });
+        let object = output.to_object().unwrap().with_material(Material {
+            kind: MaterialKind::Uv(UvPattern::image(Arc::new(lightmap).into()).with_uv_channel(1)),
+            ..Default::default()
+        });
+
+        let lower = &object.children()[0];
+        assert_eq!(
+            lower.raw_colour_at(Point3D::new(0.3, 0.2, 0.0)),
+            Colour::RED
+        );
+        assert_eq!(
+            lower.raw_colour_at(Point3D::new(0.8, 0.2, 0.0)),
+            Colour::RED
+        );
+    }
+
+    #[test]
+    fn faces_with_texture_and_normal_indexes_should_produce_textured_smooth_triangles() {
+        use crate::scene::UvPattern;
+
+        let parser = WavefrontParser::new(PathBuf::new());
+
+        // the texture is mirrored horizontally relative to the vertices
+        let input = "v 0 0 0
+        v 1 0 0
+        v 0 1 0
+
+        vt 1 0
+        vt 0 0
+        vt 0 1
+
+        vn 0 0 -1
+        vn 0 0 -1
+        vn 0 1 -1
+
+        f 1/1/1 2/2/2 3/3/3";
+
+        let output = parser.parse_obj(input);
+        assert!(output.is_ok(), "{}", output.unwrap_err());
+        let object = output.unwrap().to_object();
+        assert!(object.is_ok(), "{}", object.unwrap_err());
+
+        let checkers = UvPattern::checkers(
+            Colour::WHITE,
+            Colour::BLACK,
+            nonzero_ext::nonzero!(2usize),
+            nonzero_ext::nonzero!(2usize),
+        );
+        let object = object.unwrap().with_material(Material {
+            kind: MaterialKind::Uv(checkers),
+            ..Default::default()
+        });
+        let triangle = &object.children()[0];
+
+        assert_eq!(
+            triangle.raw_colour_at(Point3D::new(0.1, 0.1, 0.0)),
+            Colour::BLACK
+        );
+        assert_eq!(
+            triangle.raw_colour_at(Point3D::new(0.8, 0.1, 0.0)),
+            Colour::WHITE
+        );
+        approx::assert_abs_diff_eq!(
+            triangle.normal_at(Point3D::new(0.0, 1.0, 0.0)),
+            Vector3D::new(0.0, 1.0, -1.0).normalised()
+        );
+    }
+
     mod material_tests {
         use super::*;
 
@@ -791,7 +1167,7 @@ f 1 2 3 4 5";
             assert!(output.is_err());
             assert_eq!(
                 &output.unwrap_err().to_string(),
-                "cannot `usemtl awful_green` as it has not been loaded from an MTL library"
+                "line 5: cannot `usemtl awful_green` as it has not been loaded from an MTL library"
             );
         }
 
@@ -1057,7 +1433,7 @@ f 1 2 3 4 5";
                 assert!(output.is_err());
                 assert_eq!(
                     &output.unwrap_err().to_string(),
-                    "cannot `usemtl mystery` as it has not been loaded from an MTL library"
+                    "line 6: cannot `usemtl mystery` as it has not been loaded from an MTL library"
                 );
             }
         }
@@ -1068,10 +1444,10 @@ f 1 2 3 4 5";
 
             let mtl_input = "newmtl awful_green
             Kd 0 1 0";
-            parser
-                .mtl_cache
-                .borrow_mut()
-                .insert("materials".to_owned(), parse_mtl(mtl_input).unwrap());
+            parser.mtl_cache.borrow_mut().insert(
+                "materials".to_owned(),
+                parse_mtl(mtl_input, Path::new("")).unwrap(),
+            );
 
             let obj_input = "mtllib materials.mtl
             v 0 1 0
@@ -1100,10 +1476,10 @@ f 1 2 3 4 5";
             let parser = WavefrontParser::new(PathBuf::new());
             let mtl_input = "newmtl awful_green
             Kd 0 1 0";
-            parser
-                .mtl_cache
-                .borrow_mut()
-                .insert("materials".to_owned(), parse_mtl(mtl_input).unwrap());
+            parser.mtl_cache.borrow_mut().insert(
+                "materials".to_owned(),
+                parse_mtl(mtl_input, Path::new("")).unwrap(),
+            );
 
             let obj_input = "mtllib materials.mtl
             v 0 1 0
@@ -1136,4 +1512,32 @@ f 1 2 3 4 5";
             );
         }
     }
+
+    mod loading_a_directory {
+        use super::*;
+        use crate::util::TestDirectory;
+
+        const TRIANGLE: &str = "v 0 1 0
+v -1 0 0
+v 1 0 0
+f 1 2 3";
+
+        #[test]
+        fn loading_all_obj_files_should_skip_files_of_other_types() {
+            let dir = TestDirectory::new();
+            dir.write("first.obj", TRIANGLE);
+            dir.write("second.obj", TRIANGLE);
+            dir.write("notes.txt", "not a mesh");
+
+            let parser = WavefrontParser::new(dir.path().to_path_buf());
+            let objects = parser.load_all();
+            assert!(objects.is_ok(), "{}", objects.unwrap_err());
+            let objects = objects.unwrap();
+
+            assert_eq!(objects.len(), 2);
+            assert!(objects.contains_key("first.obj"));
+            assert!(objects.contains_key("second.obj"));
+            assert_eq!(parser.obj_cache.borrow().len(), 2);
+        }
+    }
 }
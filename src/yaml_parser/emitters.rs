@@ -0,0 +1,365 @@
+use crate::core::{Colour, Point3D, Vector3D, VectorMaths};
+use crate::scene::CsgOperator;
+use crate::yaml_parser::model::{
+    CameraDescription, LightDescription, MaterialDescription, ObjectDescription, ObjectKind,
+    PatternKind, PatternType, SkyDescription, Transformation, UvPatternType,
+};
+use yaml_rust::yaml::Hash;
+use yaml_rust::Yaml;
+
+/// the inverse of `FromYaml` - the output must parse back into an equal value
+pub(in crate::yaml_parser) trait ToYaml {
+    fn to_yaml(&self) -> Yaml;
+}
+
+/// builds a hash from `(key, value)` pairs, skipping any `None` values
+fn hash<'a>(entries: impl IntoIterator<Item = (&'a str, Option<Yaml>)>) -> Yaml {
+    let mut hash = Hash::new();
+    for (key, value) in entries {
+        if let Some(value) = value {
+            hash.insert(string(key), value);
+        }
+    }
+
+    Yaml::Hash(hash)
+}
+
+fn string(value: &str) -> Yaml {
+    Yaml::String(value.to_owned())
+}
+
+fn array(values: impl IntoIterator<Item = Yaml>) -> Yaml {
+    Yaml::Array(values.into_iter().collect())
+}
+
+impl ToYaml for f64 {
+    fn to_yaml(&self) -> Yaml {
+        // `Debug` formatting is the shortest representation which parses back to exactly the same value
+        Yaml::Real(format!("{:?}", self))
+    }
+}
+
+impl ToYaml for usize {
+    fn to_yaml(&self) -> Yaml {
+        Yaml::Integer(*self as i64)
+    }
+}
+
+impl ToYaml for bool {
+    fn to_yaml(&self) -> Yaml {
+        Yaml::Boolean(*self)
+    }
+}
+
+impl ToYaml for Colour {
+    fn to_yaml(&self) -> Yaml {
+        array(vec![
+            self.red().to_yaml(),
+            self.green().to_yaml(),
+            self.blue().to_yaml(),
+        ])
+    }
+}
+
+impl ToYaml for Point3D {
+    fn to_yaml(&self) -> Yaml {
+        array(vec![
+            self.x().to_yaml(),
+            self.y().to_yaml(),
+            self.z().to_yaml(),
+        ])
+    }
+}
+
+impl ToYaml for Vector3D {
+    fn to_yaml(&self) -> Yaml {
+        array(vec![
+            self.x().to_yaml(),
+            self.y().to_yaml(),
+            self.z().to_yaml(),
+        ])
+    }
+}
+
+impl ToYaml for CameraDescription {
+    fn to_yaml(&self) -> Yaml {
+        hash(vec![
+            ("add", Some(string("camera"))),
+            ("width", Some(self.width.to_yaml())),
+            ("height", Some(self.height.to_yaml())),
+            ("field-of-view", Some(self.field_of_view.to_yaml())),
+            ("from", Some(self.from.to_yaml())),
+            ("to", Some(self.to.to_yaml())),
+            ("up", Some(self.up.to_yaml())),
+        ])
+    }
+}
+
+impl ToYaml for LightDescription {
+    fn to_yaml(&self) -> Yaml {
+        match self {
+            LightDescription::Point { colour, position } => hash(vec![
+                ("add", Some(string("light"))),
+                ("at", Some(position.to_yaml())),
+                ("intensity", Some(colour.to_yaml())),
+            ]),
+            LightDescription::Area {
+                colour,
+                bottom_left,
+                u,
+                v,
+                u_steps,
+                v_steps,
+                jitter,
+            } => hash(vec![
+                ("add", Some(string("light"))),
+                ("corner", Some(bottom_left.to_yaml())),
+                ("uvec", Some(u.to_yaml())),
+                ("vvec", Some(v.to_yaml())),
+                ("usteps", Some((u_steps.get() as usize).to_yaml())),
+                ("vsteps", Some((v_steps.get() as usize).to_yaml())),
+                ("jitter", Some(jitter.to_yaml())),
+                ("intensity", Some(colour.to_yaml())),
+            ]),
+        }
+    }
+}
+
+impl ToYaml for SkyDescription {
+    fn to_yaml(&self) -> Yaml {
+        let image = |file_name: &String| {
+            UvPatternType::Image {
+                file_name: file_name.clone(),
+            }
+            .to_yaml()
+        };
+
+        match self {
+            SkyDescription::CubeMap {
+                left,
+                right,
+                front,
+                back,
+                top,
+                bottom,
+            } => hash(vec![
+                ("add", Some(string("sky"))),
+                ("mapping", Some(string("cube"))),
+                ("front", Some(image(front))),
+                ("back", Some(image(back))),
+                ("up", Some(image(top))),
+                ("down", Some(image(bottom))),
+                ("left", Some(image(left))),
+                ("right", Some(image(right))),
+            ]),
+            SkyDescription::Environment(file_name) => hash(vec![
+                ("add", Some(string("sky"))),
+                ("mapping", Some(string("spherical"))),
+                ("image", Some(image(file_name))),
+            ]),
+        }
+    }
+}
+
+impl ToYaml for ObjectDescription {
+    fn to_yaml(&self) -> Yaml {
+        let mut entries = vec![];
+
+        match &self.kind {
+            ObjectKind::Plane => entries.push(("add", Some(string("plane")))),
+            ObjectKind::Sphere => entries.push(("add", Some(string("sphere")))),
+            ObjectKind::Cube => entries.push(("add", Some(string("cube")))),
+            ObjectKind::Cylinder { min, max, capped } | ObjectKind::Cone { min, max, capped } => {
+                let add = if let ObjectKind::Cylinder { .. } = self.kind {
+                    "cylinder"
+                } else {
+                    "cone"
+                };
+
+                entries.push(("add", Some(string(add))));
+                entries.push(("min", min.map(|min| min.to_yaml())));
+                entries.push(("max", max.map(|max| max.to_yaml())));
+                entries.push(("closed", Some(capped.to_yaml())));
+            }
+            ObjectKind::ObjFile { file_name } => {
+                entries.push(("add", Some(string("obj"))));
+                entries.push(("file", Some(string(file_name))));
+            }
+            ObjectKind::Group { children } => {
+                entries.push(("add", Some(string("group"))));
+                entries.push((
+                    "children",
+                    Some(array(children.iter().map(ToYaml::to_yaml))),
+                ));
+            }
+            ObjectKind::Csg {
+                operator,
+                left,
+                right,
+            } => {
+                let operation = match operator {
+                    CsgOperator::Subtract => "difference",
+                    CsgOperator::Union => "union",
+                    CsgOperator::Intersection => "intersection",
+                };
+
+                entries.push(("add", Some(string("csg"))));
+                entries.push(("operation", Some(string(operation))));
+                entries.push(("left", Some(left.to_yaml())));
+                entries.push(("right", Some(right.to_yaml())));
+            }
+        };
+
+        entries.push(("material", Some(self.material.to_yaml())));
+        entries.push(("transform", Some(self.transform.to_yaml())));
+        entries.push(("shadow", Some(self.casts_shadow.to_yaml())));
+
+        hash(entries)
+    }
+}
+
+impl ToYaml for MaterialDescription {
+    fn to_yaml(&self) -> Yaml {
+        let (colour, pattern) = match &self.pattern {
+            Some(PatternKind::Solid(colour)) => (Some(colour.to_yaml()), None),
+            Some(pattern) => (None, Some(pattern.to_yaml())),
+            None => (None, None),
+        };
+
+        hash(vec![
+            ("color", colour),
+            ("pattern", pattern),
+            ("diffuse", self.diffuse.map(|f| f.to_yaml())),
+            ("ambient", self.ambient.map(|f| f.to_yaml())),
+            ("specular", self.specular.map(|f| f.to_yaml())),
+            ("shininess", self.shininess.map(|f| f.to_yaml())),
+            ("reflective", self.reflective.map(|f| f.to_yaml())),
+            ("transparency", self.transparency.map(|f| f.to_yaml())),
+            ("refractive-index", self.refractive.map(|f| f.to_yaml())),
+            ("emission", self.emission.map(|c| c.to_yaml())),
+        ])
+    }
+}
+
+impl ToYaml for PatternKind {
+    fn to_yaml(&self) -> Yaml {
+        match self {
+            PatternKind::Solid(colour) => colour.to_yaml(),
+            PatternKind::Pattern {
+                pattern_type,
+                colours: (primary, secondary),
+                transforms,
+            } => {
+                let pattern_type = match pattern_type {
+                    PatternType::Stripes => "stripes",
+                    PatternType::Checkers => "checkers",
+                    PatternType::Rings => "rings",
+                    PatternType::Gradient => "gradient",
+                };
+
+                hash(vec![
+                    ("type", Some(string(pattern_type))),
+                    (
+                        "colors",
+                        Some(array(vec![primary.to_yaml(), secondary.to_yaml()])),
+                    ),
+                    ("transform", transforms.as_ref().map(ToYaml::to_yaml)),
+                ])
+            }
+            PatternKind::Uv {
+                uv_type,
+                transforms,
+            } => {
+                let mut uv = match uv_type {
+                    // the parser only uses the mapping to find the pattern, so the specific mapping doesn't matter
+                    UvPatternType::Checkers { .. } | UvPatternType::Image { .. } => hash(vec![
+                        ("mapping", Some(string("planar"))),
+                        ("uv_pattern", Some(uv_type.to_yaml())),
+                    ]),
+                    mapped => mapped.to_yaml(),
+                };
+
+                if let Yaml::Hash(hash) = &mut uv {
+                    hash.insert(string("type"), string("map"));
+                    if let Some(transforms) = transforms {
+                        hash.insert(string("transform"), transforms.to_yaml());
+                    }
+                }
+
+                uv
+            }
+        }
+    }
+}
+
+impl ToYaml for UvPatternType {
+    fn to_yaml(&self) -> Yaml {
+        match self {
+            UvPatternType::Checkers {
+                primary,
+                secondary,
+                width,
+                height,
+            } => hash(vec![
+                ("type", Some(string("checkers"))),
+                (
+                    "colors",
+                    Some(array(vec![primary.to_yaml(), secondary.to_yaml()])),
+                ),
+                ("width", Some(width.get().to_yaml())),
+                ("height", Some(height.get().to_yaml())),
+            ]),
+            UvPatternType::Image { file_name } => hash(vec![
+                ("type", Some(string("image"))),
+                ("file", Some(string(file_name))),
+            ]),
+            UvPatternType::Cube {
+                left,
+                right,
+                front,
+                back,
+                top,
+                bottom,
+            } => hash(vec![
+                ("mapping", Some(string("cube"))),
+                ("front", Some(front.to_yaml())),
+                ("back", Some(back.to_yaml())),
+                ("up", Some(top.to_yaml())),
+                ("down", Some(bottom.to_yaml())),
+                ("left", Some(left.to_yaml())),
+                ("right", Some(right.to_yaml())),
+            ]),
+            UvPatternType::Cylindrical { sides, caps } => hash(vec![
+                ("mapping", Some(string("cylindrical"))),
+                ("uv_pattern", Some(sides.to_yaml())),
+                ("top", caps.as_ref().map(|(top, _)| top.to_yaml())),
+                ("bottom", caps.as_ref().map(|(_, bottom)| bottom.to_yaml())),
+            ]),
+        }
+    }
+}
+
+impl ToYaml for Vec<Transformation> {
+    fn to_yaml(&self) -> Yaml {
+        array(self.iter().map(|transform| match transform {
+            Transformation::Translate { x, y, z } => array(vec![
+                string("translate"),
+                x.to_yaml(),
+                y.to_yaml(),
+                z.to_yaml(),
+            ]),
+            Transformation::Scale { x, y, z } => {
+                array(vec![string("scale"), x.to_yaml(), y.to_yaml(), z.to_yaml()])
+            }
+            Transformation::RotationX(radians) => {
+                array(vec![string("rotate-x"), radians.to_yaml()])
+            }
+            Transformation::RotationY(radians) => {
+                array(vec![string("rotate-y"), radians.to_yaml()])
+            }
+            Transformation::RotationZ(radians) => {
+                array(vec![string("rotate-z"), radians.to_yaml()])
+            }
+        }))
+    }
+}
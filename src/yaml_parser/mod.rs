@@ -3,13 +3,13 @@ use yaml_rust::{Yaml, YamlLoader};
 use anyhow::*;
 use model::*;
 use parsers::*;
-use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 #[cfg(test)]
 mod tests;
 
+mod emitters;
 mod model;
 mod parsers;
 
@@ -19,73 +19,132 @@ pub fn load(resource_dir: PathBuf, file_name: &str) -> anyhow::Result<SceneDescr
 }
 
 fn parse(input: &str, resource_dir: PathBuf) -> anyhow::Result<SceneDescription> {
-    match YamlLoader::load_from_str(input) {
-        Ok(yaml) => {
-            let mut camera = None;
-            let mut lights = vec![];
-            let mut new_defines = HashMap::new();
-            let mut objects = vec![];
-
-            if let Some(items) = yaml[0].as_vec() {
-                for item in items {
-                    let item = ParseState::new(item, &new_defines);
-                    match item.get("add").as_str() {
-                        Some("camera") => {
-                            camera = Some(
-                                item.with_context("add")
-                                    .with_extra_context("camera".into())
-                                    .parse()?,
-                            );
-                            continue;
-                        }
-                        Some("light") => {
-                            lights.push(
-                                item.with_context("add")
-                                    .with_extra_context("light".into())
-                                    .parse()?,
-                            );
-                            continue;
-                        }
-                        Some(add) => {
-                            objects.push(
-                                item.with_context("add")
-                                    .with_extra_context(add.into())
-                                    .parse()?,
-                            );
-                            continue;
-                        }
-                        None => (),
-                    }
-
-                    if let Some(name) = item.get("define").as_str() {
-                        let define = item
-                            .with_context("define")
-                            .with_extra_context(name.into())
-                            .parse()?;
-                        let name = name.to_owned();
-
-                        if let Some(_) = new_defines.insert(name.clone(), define) {
-                            bail!("duplicate `define` with name {:?}", name);
-                        }
-
-                        continue;
-                    }
+    let mut directives = Directives::default();
+    parse_directives(input, &resource_dir, &mut directives, &mut vec![])?;
+
+    directives.into_scene(resource_dir)
+}
+
+/// everything parsed so far, including from any `include`d files
+#[derive(Default)]
+struct Directives {
+    camera: Option<CameraDescription>,
+    lights: Vec<LightDescription>,
+    sky: Option<SkyDescription>,
+    defines: Defines,
+    objects: Vec<ObjectDescription>,
+}
+
+impl Directives {
+    fn into_scene(self, resource_dir: PathBuf) -> anyhow::Result<SceneDescription> {
+        let camera = self
+            .camera
+            .ok_or(anyhow!("No `add: camera` directive found"))?;
+
+        Ok(SceneDescription {
+            camera,
+            lights: self.lights,
+            objects: self.objects,
+            sky: self.sky,
+            resource_dir,
+            texture_cache: None,
+        })
+    }
+}
+
+/// `including` is the chain of files currently being parsed, used to detect cyclic `include`s
+fn parse_directives(
+    input: &str,
+    resource_dir: &Path,
+    directives: &mut Directives,
+    including: &mut Vec<PathBuf>,
+) -> anyhow::Result<()> {
+    let yaml = match YamlLoader::load_from_str(input) {
+        Ok(yaml) => yaml,
+        Err(error) => bail!(error),
+    };
+
+    let items = yaml[0]
+        .as_vec()
+        .ok_or_else(|| anyhow!("Expected a list of directives"))?;
+
+    for item in items {
+        // included files are parsed in place, so anything defined in them can be used by the rest of this file
+        if let Some(file_name) = item["include"].as_str() {
+            let path = resource_dir.join(file_name);
+            let contents = fs::read_to_string(&path)
+                .with_context(|| format!("cannot read included file {:?}", path))?;
+            let path = fs::canonicalize(&path)?;
+
+            if including.contains(&path) {
+                bail!("cyclic `include` of {:?}", path);
+            }
+
+            including.push(path);
+            parse_directives(&contents, resource_dir, directives, including)
+                .with_context(|| format!("cannot parse included file {:?}", file_name))?;
+            including.pop();
+
+            continue;
+        }
+
+        let item = ParseState::new(item, &directives.defines);
+        match item.get("add").as_str() {
+            Some("camera") => {
+                directives.camera = Some(
+                    item.with_context("add")
+                        .with_extra_context("camera".into())
+                        .parse()?,
+                );
+                continue;
+            }
+            Some("light") => {
+                let light = item
+                    .with_context("add")
+                    .with_extra_context("light".into())
+                    .parse()?;
+                directives.lights.push(light);
+                continue;
+            }
+            Some("sky") => {
+                if directives.sky.is_some() {
+                    bail!("only one `add: sky` directive is allowed");
                 }
-            } else {
-                bail!("Expected a list of directives");
+
+                directives.sky = Some(
+                    item.with_context("add")
+                        .with_extra_context("sky".into())
+                        .parse()?,
+                );
+                continue;
             }
+            Some(add) => {
+                let object = item
+                    .with_context("add")
+                    .with_extra_context(add.into())
+                    .parse()?;
+                directives.objects.push(object);
+                continue;
+            }
+            None => (),
+        }
+
+        if let Some(name) = item.get("define").as_str() {
+            let define = item
+                .with_context("define")
+                .with_extra_context(name.into())
+                .parse()?;
+            let name = name.to_owned();
 
-            let camera = camera.ok_or(anyhow!("No `add: camera` directive found"))?;
+            if let Some(_) = directives.defines.insert(name.clone(), define) {
+                bail!("duplicate `define` with name {:?}", name);
+            }
 
-            Ok(SceneDescription {
-                camera,
-                lights,
-                objects,
-                resource_dir,
-            })
+            continue;
         }
-        Err(error) => bail!(error),
     }
+
+    Ok(())
 }
 
 struct ParseState<'yaml> {
@@ -1,21 +1,26 @@
 use crate::core::{Colour, Point3D, Transform, Vector3D, VectorMaths};
 use crate::renderer::Camera;
-use crate::scene::{CsgOperator, Light};
+use crate::scene::{CsgOperator, Light, Sky, TextureCache};
 use crate::scene::{Material, MaterialKind, Pattern};
 use crate::scene::{Object, UvPattern};
 use crate::wavefront_parser::WavefrontParser;
+use crate::yaml_parser::emitters::ToYaml;
 use anyhow::*;
+use image::RgbImage;
 use std::collections::HashMap;
-use std::num::{NonZeroU16, NonZeroUsize};
+use std::num::{NonZeroU16, NonZeroU8, NonZeroUsize};
 use std::path::PathBuf;
 use std::sync::Arc;
+use yaml_rust::{Yaml, YamlEmitter};
 
 #[derive(Debug, PartialEq)]
 pub struct SceneDescription {
     pub(crate) camera: CameraDescription,
-    pub(crate) lights: Vec<Light>,
+    pub(crate) lights: Vec<LightDescription>,
     pub(crate) objects: Vec<ObjectDescription>,
+    pub(crate) sky: Option<SkyDescription>,
     pub(crate) resource_dir: PathBuf,
+    pub(crate) texture_cache: Option<Arc<TextureCache>>,
 }
 
 impl SceneDescription {
@@ -24,6 +29,29 @@ impl SceneDescription {
         self.camera.height = height;
     }
 
+    /// decode image textures only when they're needed, keeping at most `budget_bytes` of decoded textures in memory
+    /// (excluding the sky) - by default, every texture is decoded when the objects are created, and kept in memory
+    pub fn limit_texture_memory(&mut self, budget_bytes: usize) {
+        self.texture_cache = Some(TextureCache::new(budget_bytes));
+    }
+
+    /// describes the scene in the same format that `yaml_parser::load` reads, such that parsing the output produces an equal `SceneDescription`.
+    ///
+    /// Any `define`s in the original file are written out in full wherever they were used
+    pub fn to_yaml(&self) -> String {
+        let mut directives = vec![self.camera.to_yaml()];
+        directives.extend(self.lights.iter().map(ToYaml::to_yaml));
+        directives.extend(self.sky.iter().map(ToYaml::to_yaml));
+        directives.extend(self.objects.iter().map(ToYaml::to_yaml));
+
+        let mut out = String::new();
+        YamlEmitter::new(&mut out)
+            .dump(&Yaml::Array(directives))
+            .expect("writing to a String cannot fail");
+
+        out
+    }
+
     pub fn camera(&self) -> anyhow::Result<Camera> {
         fn validate_nonzero_u16(dimension: &str, value: usize) -> anyhow::Result<NonZeroU16> {
             let value = if value > (u16::MAX as usize) {
@@ -48,7 +76,36 @@ impl SceneDescription {
     }
 
     pub fn lights(&self) -> Vec<Light> {
-        self.lights.clone()
+        self.lights.iter().map(LightDescription::to_light).collect()
+    }
+
+    pub fn sky(&self) -> anyhow::Result<Option<Sky>> {
+        let load = |file_name: &String| -> anyhow::Result<Arc<RgbImage>> {
+            let file_path = self.resource_dir.join(file_name);
+            let img = image::open(&file_path)
+                .with_context(|| format!("failed to load sky image from {:?}", file_path))?;
+            Ok(Arc::new(img.to_rgb8()))
+        };
+
+        match &self.sky {
+            Some(SkyDescription::CubeMap {
+                left,
+                right,
+                front,
+                back,
+                top,
+                bottom,
+            }) => Ok(Some(Sky::CubeMap {
+                px: load(right)?,
+                nx: load(left)?,
+                py: load(top)?,
+                ny: load(bottom)?,
+                pz: load(front)?,
+                nz: load(back)?,
+            })),
+            Some(SkyDescription::Environment(image)) => Ok(Some(Sky::Environment(load(image)?))),
+            None => Ok(None),
+        }
     }
 
     pub fn objects(&self) -> anyhow::Result<Vec<Object>> {
@@ -143,6 +200,9 @@ impl SceneDescription {
             .map(|transparency| material.transparency = transparency);
         desc.refractive
             .map(|refractive| material.refractive = refractive);
+        if let Some(emission) = desc.emission {
+            material.emission = emission;
+        }
         material.casts_shadow = casts_shadow;
 
         material
@@ -198,9 +258,14 @@ impl SceneDescription {
                 } => UvPattern::checkers(*primary, *secondary, *width, *height),
                 UvPatternType::Image { file_name } => {
                     let file_path = this.resource_dir.join(file_name);
-                    let img = image::open(&file_path)
-                        .expect(&format!("failed to load uv pattern from {:?}", file_path));
-                    UvPattern::image(Arc::new(img.to_rgb8()))
+                    let texture = if let Some(cache) = &this.texture_cache {
+                        cache.load(&file_path)
+                    } else {
+                        image::open(&file_path).map(|img| Arc::new(img.to_rgb8()).into())
+                    };
+                    let texture =
+                        texture.expect(&format!("failed to load uv pattern from {:?}", file_path));
+                    UvPattern::image(texture)
                 }
                 UvPatternType::Cube {
                     left,
@@ -249,6 +314,49 @@ pub struct CameraDescription {
     pub(crate) up: Vector3D,
 }
 
+#[derive(PartialEq, Debug, Clone)]
+pub enum LightDescription {
+    Point {
+        colour: Colour,
+        position: Point3D,
+    },
+    Area {
+        colour: Colour,
+        bottom_left: Point3D,
+        u: Vector3D,
+        v: Vector3D,
+        u_steps: NonZeroU8,
+        v_steps: NonZeroU8,
+        jitter: bool,
+    },
+}
+
+impl LightDescription {
+    pub(crate) fn to_light(&self) -> Light {
+        match self {
+            LightDescription::Point { colour, position } => Light::point(*colour, *position),
+            LightDescription::Area {
+                colour,
+                bottom_left,
+                u,
+                v,
+                u_steps,
+                v_steps,
+                jitter: true,
+            } => Light::area(*colour, *bottom_left, *u, *v, *u_steps, *v_steps),
+            LightDescription::Area {
+                colour,
+                bottom_left,
+                u,
+                v,
+                u_steps,
+                v_steps,
+                jitter: false,
+            } => Light::area_fixed(*colour, *bottom_left, *u, *v, *u_steps, *v_steps),
+        }
+    }
+}
+
 #[derive(Debug, PartialEq)]
 pub enum Define {
     Material(MaterialDescription),
@@ -268,6 +376,7 @@ pub struct MaterialDescription {
     pub(crate) reflective: Option<f64>,
     pub(crate) transparency: Option<f64>,
     pub(crate) refractive: Option<f64>,
+    pub(crate) emission: Option<Colour>,
 }
 
 impl MaterialDescription {
@@ -281,6 +390,7 @@ impl MaterialDescription {
             reflective: self.reflective.or_else(|| base.reflective),
             transparency: self.transparency.or_else(|| base.transparency),
             refractive: self.refractive.or_else(|| base.refractive),
+            emission: self.emission.or(base.emission),
         }
     }
 }
@@ -332,6 +442,21 @@ pub enum UvPatternType {
     },
 }
 
+#[derive(Debug, PartialEq, Clone)]
+pub enum SkyDescription {
+    /// file names of the image on each face of the cube, as seen from inside the cube
+    CubeMap {
+        left: String,
+        right: String,
+        front: String,
+        back: String,
+        top: String,
+        bottom: String,
+    },
+    /// file name of a single latitude/longitude image
+    Environment(String),
+}
+
 #[derive(PartialEq, Debug, Clone, Copy)]
 pub enum Transformation {
     Translate { x: f64, y: f64, z: f64 },
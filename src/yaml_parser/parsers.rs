@@ -2,8 +2,8 @@ use crate::core::{Colour, Point3D, Vector3D};
 use crate::scene::{CsgOperator, Light};
 use crate::yaml_parser::model::Define;
 use crate::yaml_parser::model::{
-    CameraDescription, MaterialDescription, ObjectDescription, ObjectKind, PatternKind,
-    PatternType, Transformation, UvPatternType,
+    CameraDescription, LightDescription, MaterialDescription, ObjectDescription, ObjectKind,
+    PatternKind, PatternType, SkyDescription, Transformation, UvPatternType,
 };
 use crate::yaml_parser::ParseState;
 use anyhow::*;
@@ -18,8 +18,11 @@ pub(in crate::yaml_parser) trait FromYaml: Sized {
 impl FromYaml for f64 {
     fn from_yaml(parser: &ParseState) -> anyhow::Result<Self> {
         match parser.yaml() {
-            // yaml lib f64 parsing is lazy - this can't fail
-            Yaml::Real(real) => Ok(real.parse().unwrap()),
+            // yaml lib f64 parsing is lazy, but YAML floats (including `.inf` and `.nan`) are validated when loaded
+            Yaml::Real(_) => Ok(parser
+                .yaml()
+                .as_f64()
+                .expect("a YAML real should always be a valid f64")),
             Yaml::Integer(integer) => Ok(*integer as f64),
             Yaml::BadValue => bail!("value is undefined"),
             other => bail!("cannot parse {:?} as floating point", other),
@@ -316,6 +319,7 @@ impl FromYaml for MaterialDescription {
             let reflective = parser.get("reflective").parse()?;
             let transparency = parser.get("transparency").parse()?;
             let refractive = parser.get("refractive-index").parse()?;
+            let emission = parser.get("emission").parse()?;
 
             Ok(MaterialDescription {
                 pattern,
@@ -326,6 +330,7 @@ impl FromYaml for MaterialDescription {
                 reflective,
                 transparency,
                 refractive,
+                emission,
             })
         }
 
@@ -392,31 +397,26 @@ impl FromYaml for CameraDescription {
     }
 }
 
-pub(in crate::yaml_parser) const DEFAULT_AREA_LIGHT_SEED: u64 = 4; // totally randomly chosen
-
-impl FromYaml for Light {
+impl FromYaml for LightDescription {
     fn from_yaml(parser: &ParseState) -> anyhow::Result<Self> {
         let colour = parser.get("intensity").parse()?;
 
         // scene description format doesn't specify what kind of light to add, so have to guess based on what data is provided
         if let Some(position) = parser.get("at").parse()? {
-            Ok(Light::point(colour, position))
+            Ok(LightDescription::Point { colour, position })
         } else {
-            let bottom_left = parser.get("corner").parse()?;
-            let u = parser.get("uvec").parse()?;
-            let v = parser.get("vvec").parse()?;
-            let u_steps = parser.get("usteps").parse()?;
-            let v_steps = parser.get("vsteps").parse()?;
-            // ignore `jitter` - you don't get a choice between fixed vs random sampling
-            Ok(Light::area(
+            Ok(LightDescription::Area {
                 colour,
-                bottom_left,
-                u,
-                v,
-                u_steps,
-                v_steps,
-                DEFAULT_AREA_LIGHT_SEED,
-            ))
+                bottom_left: parser.get("corner").parse()?,
+                u: parser.get("uvec").parse()?,
+                v: parser.get("vvec").parse()?,
+                u_steps: parser.get("usteps").parse()?,
+                v_steps: parser.get("vsteps").parse()?,
+                jitter: parser
+                    .get("jitter")
+                    .parse::<Option<bool>>()?
+                    .unwrap_or(true),
+            })
         }
     }
 
@@ -425,6 +425,16 @@ impl FromYaml for Light {
     }
 }
 
+impl FromYaml for Light {
+    fn from_yaml(parser: &ParseState) -> anyhow::Result<Self> {
+        LightDescription::from_yaml(parser).map(|light| light.to_light())
+    }
+
+    fn type_name() -> String {
+        LightDescription::type_name()
+    }
+}
+
 impl FromYaml for PatternKind {
     fn from_yaml(parser: &ParseState) -> anyhow::Result<Self> {
         let transforms = parser.get("transform").parse()?;
@@ -544,6 +554,38 @@ impl FromYaml for UvPatternType {
     }
 }
 
+impl FromYaml for SkyDescription {
+    fn from_yaml(parser: &ParseState) -> anyhow::Result<Self> {
+        fn face(parser: &ParseState, name: &'static str) -> anyhow::Result<String> {
+            match parser.get(name).parse()? {
+                UvPatternType::Image { file_name } => Ok(file_name),
+                _ => bail!("the `{}` face of a sky must be an `image`", name),
+            }
+        }
+
+        match parser.get("mapping").as_str() {
+            Some("cube") => Ok(SkyDescription::CubeMap {
+                front: face(parser, "front")?,
+                back: face(parser, "back")?,
+                top: face(parser, "up")?,
+                bottom: face(parser, "down")?,
+                left: face(parser, "left")?,
+                right: face(parser, "right")?,
+            }),
+            Some("spherical") => match parser.get("image").parse()? {
+                UvPatternType::Image { file_name } => Ok(SkyDescription::Environment(file_name)),
+                _ => bail!("the `image` of a spherical sky must be an `image`"),
+            },
+            Some(other) => bail!("Unsupported sky mapping type {}", other),
+            None => bail!("a sky must have a `mapping`"),
+        }
+    }
+
+    fn type_name() -> String {
+        "Sky".to_string()
+    }
+}
+
 impl FromYaml for Define {
     fn from_yaml(parser: &ParseState) -> anyhow::Result<Self> {
         // array of transforms or hash of material or hash of object
@@ -568,7 +610,17 @@ impl FromYaml for Define {
 // defines From<(f64, f64, f64)> (or at least, From<T>)
 impl FromYaml for Colour {
     fn from_yaml(parser: &ParseState) -> anyhow::Result<Self> {
-        parser.parse().map(|(r, g, b)| Self::new(r, g, b))
+        let (r, g, b) = parser.parse::<(f64, f64, f64)>()?;
+        if ![r, g, b].iter().all(|component| component.is_finite()) {
+            bail!(
+                "colour components must be finite, but got [{}, {}, {}]",
+                r,
+                g,
+                b
+            );
+        }
+
+        Ok(Self::new(r, g, b))
     }
 
     fn type_name() -> String {
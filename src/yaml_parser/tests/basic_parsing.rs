@@ -104,12 +104,46 @@ intensity: [1.5, 1.5, 1.5]";
             Vector3D::new(2.0, 0.0, 0.0),
             Vector3D::new(0.0, 2.0, 0.0),
             nonzero_ext::nonzero!(10u8),
-            nonzero_ext::nonzero!(10u8),
-            DEFAULT_AREA_LIGHT_SEED
+            nonzero_ext::nonzero!(10u8)
         )
     );
 }
 
+#[test]
+fn should_parse_an_area_light_without_jitter_as_evenly_spaced_samples() {
+    let input = "\
+add: light
+corner: [-1, 2, 4]
+uvec: [2, 0, 0]
+vvec: [0, 2, 0]
+usteps: 2
+vsteps: 2
+jitter: false
+intensity: [1.5, 1.5, 1.5]";
+
+    let yaml = &YamlLoader::load_from_str(input).unwrap()[0];
+    let defines = HashMap::new();
+    let parse = || {
+        ParseState::new(yaml, &defines)
+            .with_context("add")
+            .parse::<Light>()
+    };
+    let output = parse();
+    assert!(output.is_ok(), "{}", output.unwrap_err());
+    let output = output.unwrap();
+
+    assert_eq!(
+        output.samples().0.copied().collect::<Vec<_>>(),
+        vec![
+            Point3D::new(-0.5, 2.5, 4.0),
+            Point3D::new(-0.5, 3.5, 4.0),
+            Point3D::new(0.5, 2.5, 4.0),
+            Point3D::new(0.5, 3.5, 4.0),
+        ]
+    );
+    assert_eq!(output, parse().unwrap());
+}
+
 #[test]
 fn should_parse_basic_material_define() {
     let input = "\
@@ -139,7 +173,33 @@ value:
             shininess: None,
             reflective: Some(0.1),
             transparency: None,
-            refractive: None
+            refractive: None,
+            emission: None
+        })
+    );
+}
+
+#[test]
+fn should_parse_a_material_define_with_an_emission_colour() {
+    let input = "\
+define: lamp-material
+value:
+  color: [ 1, 1, 1 ]
+  emission: [ 1, 0.9, 0.8 ]";
+
+    let yaml = &YamlLoader::load_from_str(input).unwrap()[0];
+    let defines = HashMap::new();
+    let define = ParseState::new(yaml, &defines)
+        .with_context("add")
+        .parse::<Define>();
+    assert!(define.is_ok(), "{}", define.unwrap_err());
+
+    assert_eq!(
+        define.unwrap(),
+        Define::Material(MaterialDescription {
+            pattern: Some(PatternKind::Solid(Colour::WHITE)),
+            emission: Some(Colour::new(1.0, 0.9, 0.8)),
+            ..Default::default()
         })
     );
 }
@@ -165,6 +225,7 @@ value:
             reflective: Some(0.1),
             transparency: None,
             refractive: None,
+            emission: None,
         }),
     );
 
@@ -184,7 +245,8 @@ value:
             shininess: None,
             reflective: Some(0.1),
             transparency: None,
-            refractive: None
+            refractive: None,
+            emission: None
         })
     )
 }
@@ -465,6 +527,7 @@ transform:
                 reflective: Some(0.7),
                 transparency: Some(0.7),
                 refractive: Some(1.5),
+                emission: None,
             },
             transform: vec![
                 Transformation::Translate {
@@ -530,6 +593,7 @@ transform:
             reflective: Some(0.1),
             transparency: None,
             refractive: None,
+            emission: None,
         }),
     );
 
@@ -552,6 +616,7 @@ transform:
                 reflective: Some(0.1),
                 transparency: None,
                 refractive: None,
+                emission: None,
             },
             transform: vec![
                 Transformation::Translate {
@@ -1307,6 +1372,116 @@ pattern:
     );
 }
 
+#[test]
+fn should_parse_cube_map_sky() {
+    let input = "\
+add: sky
+mapping: cube
+left:
+  type: image
+  file: negx.ppm
+right:
+  type: image
+  file: posx.ppm
+front:
+  type: image
+  file: posz.ppm
+back:
+  type: image
+  file: negz.ppm
+up:
+  type: image
+  file: posy.ppm
+down:
+  type: image
+  file: negy.ppm";
+
+    let yaml = &YamlLoader::load_from_str(input).unwrap()[0];
+    let defines = HashMap::new();
+    let sky = ParseState::new(yaml, &defines)
+        .with_context("add")
+        .parse::<SkyDescription>();
+    assert!(sky.is_ok(), "{}", sky.unwrap_err());
+    let sky = sky.unwrap();
+
+    assert_eq!(
+        sky,
+        SkyDescription::CubeMap {
+            left: "negx.ppm".into(),
+            right: "posx.ppm".into(),
+            front: "posz.ppm".into(),
+            back: "negz.ppm".into(),
+            top: "posy.ppm".into(),
+            bottom: "negy.ppm".into(),
+        }
+    );
+}
+
+#[test]
+fn should_parse_spherical_sky() {
+    let input = "\
+add: sky
+mapping: spherical
+image:
+  type: image
+  file: environment.ppm";
+
+    let yaml = &YamlLoader::load_from_str(input).unwrap()[0];
+    let defines = HashMap::new();
+    let sky = ParseState::new(yaml, &defines)
+        .with_context("add")
+        .parse::<SkyDescription>();
+    assert!(sky.is_ok(), "{}", sky.unwrap_err());
+
+    assert_eq!(
+        sky.unwrap(),
+        SkyDescription::Environment("environment.ppm".into())
+    );
+}
+
+#[test]
+fn should_not_parse_cube_map_sky_with_a_non_image_face() {
+    let input = "\
+add: sky
+mapping: cube
+left:
+  type: image
+  file: negx.ppm
+right:
+  type: image
+  file: posx.ppm
+front:
+  type: checkers
+  width: 2
+  height: 2
+  colors:
+    - [ 0, 0, 0 ]
+    - [ 1, 1, 1 ]
+back:
+  type: image
+  file: negz.ppm
+up:
+  type: image
+  file: posy.ppm
+down:
+  type: image
+  file: negy.ppm";
+
+    let yaml = &YamlLoader::load_from_str(input).unwrap()[0];
+    let defines = HashMap::new();
+    let sky = ParseState::new(yaml, &defines)
+        .with_context("add")
+        .parse::<SkyDescription>();
+    assert!(sky.is_err(), "expected parsing to fail, but it succeeded");
+    assert_eq!(
+        &format!("{:?}", sky.unwrap_err()),
+        "cannot parse `add` as Sky
+
+Caused by:
+    the `front` face of a sky must be an `image`"
+    );
+}
+
 #[test]
 fn should_parse_cylinder_with_single_uv_pattern() {
     let input = "\
@@ -1450,6 +1625,28 @@ Caused by:
     );
 }
 
+#[test]
+fn should_not_parse_a_colour_with_an_infinite_component() {
+    let input = "[ .inf, 0, 0 ]";
+
+    let yaml = &YamlLoader::load_from_str(input).unwrap()[0];
+    let defines = HashMap::new();
+    let colour = ParseState::new(yaml, &defines)
+        .with_context("color")
+        .parse::<Colour>();
+    assert!(
+        colour.is_err(),
+        "expected parsing to fail, but it succeeded"
+    );
+    assert_eq!(
+        &format!("{:?}", colour.unwrap_err()),
+        "cannot parse `color` as Colour
+
+Caused by:
+    colour components must be finite, but got [inf, 0, 0]"
+    );
+}
+
 #[test]
 fn should_not_parse_cylinder_with_uv_pattern_on_bottom_but_not_top_cap() {
     let input = "\
@@ -1515,8 +1712,14 @@ fn should_parse_scene_description() {
                 up: Vector3D::new(-0.45, 1.0, 0.0),
             },
             lights: vec![
-                Light::point(Colour::WHITE, Point3D::new(50.0, 100.0, -50.0)),
-                Light::point(Colour::greyscale(0.2), Point3D::new(-400.0, 50.0, -10.0)),
+                LightDescription::Point {
+                    colour: Colour::WHITE,
+                    position: Point3D::new(50.0, 100.0, -50.0)
+                },
+                LightDescription::Point {
+                    colour: Colour::greyscale(0.2),
+                    position: Point3D::new(-400.0, 50.0, -10.0)
+                },
             ],
             objects: vec![
                 ObjectDescription {
@@ -1549,6 +1752,7 @@ fn should_parse_scene_description() {
                         reflective: Some(0.7),
                         transparency: Some(0.7),
                         refractive: Some(1.5),
+                        emission: None,
                     },
                     transform: vec![
                         Transformation::Translate {
@@ -2148,7 +2352,9 @@ fn should_parse_scene_description() {
                     casts_shadow: true,
                 },
             ],
-            resource_dir: Default::default()
+            sky: None,
+            resource_dir: Default::default(),
+            texture_cache: None,
         }
     );
 }
@@ -719,7 +719,7 @@ fn should_be_able_to_create_a_csg() {
 
     assert_eq!(
         format!("{:?}", right.shape()),
-        "Cylinder { max_y: 0.26, min_y: -0.26, capped: true }"
+        "Cylinder { max_y: 0.26, min_y: -0.26, capped_top: true, capped_bottom: true }"
     );
     assert_eq!(
         right.transform(),
@@ -748,6 +748,110 @@ fn should_be_able_to_create_a_cone() {
 
     assert_eq!(
         format!("{:?}", objects[0].shape()),
-        "Cone { max_y: 0.0, min_y: -1.0, capped: true }"
+        "Cone { max_y: 0.0, min_y: -1.0, capped_top: true, capped_bottom: true }"
     );
 }
+
+mod includes {
+    use super::*;
+    use crate::util::TestDirectory;
+
+    /// writes each `(file name, contents)` pair to a new temporary directory, and returns that directory
+    fn write_files(files: &[(&str, &str)]) -> TestDirectory {
+        let dir = TestDirectory::new();
+        for (name, contents) in files {
+            dir.write(name, contents);
+        }
+
+        dir
+    }
+
+    #[test]
+    fn should_be_able_to_use_a_material_defined_in_an_included_file() {
+        let dir = write_files(&[(
+            "common.yml",
+            "\
+- define: blue-material
+  value:
+    color: [ 0.1, 0.2, 0.9 ]
+    diffuse: 0.7",
+        )]);
+        let input = with_camera_description(
+            "\
+- include: common.yml
+- add: sphere
+  material: blue-material",
+        );
+
+        let scene = parse(&input, dir.path().to_path_buf());
+        assert!(scene.is_ok(), "{:?}", scene.unwrap_err());
+        let objects = scene.unwrap().objects().unwrap();
+
+        assert_eq!(objects.len(), 1);
+        assert_eq!(
+            objects[0].material,
+            Material {
+                kind: MaterialKind::Solid(Colour::new(0.1, 0.2, 0.9)),
+                diffuse: 0.7,
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn lights_and_objects_in_an_included_file_should_be_added_to_the_scene() {
+        let dir = write_files(&[
+            (
+                "lights.yml",
+                "\
+- include: more_lights.yml
+- add: light
+  at: [ 0, 10, 0 ]
+  intensity: [ 1, 1, 1 ]
+- add: plane",
+            ),
+            (
+                "more_lights.yml",
+                "\
+- add: light
+  at: [ 0, 10, -10 ]
+  intensity: [ 0.5, 0.5, 0.5 ]",
+            ),
+        ]);
+        let input = with_camera_description(
+            "\
+- include: lights.yml
+- add: sphere",
+        );
+
+        let scene = parse(&input, dir.path().to_path_buf());
+        assert!(scene.is_ok(), "{:?}", scene.unwrap_err());
+        let scene = scene.unwrap();
+
+        assert_eq!(scene.lights().len(), 2);
+        assert_eq!(scene.objects().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn cyclic_includes_should_fail_to_parse() {
+        let dir = write_files(&[
+            ("first.yml", "- include: second.yml"),
+            ("second.yml", "- include: first.yml"),
+        ]);
+        let input = with_camera_description("- include: first.yml");
+
+        let scene = parse(&input, dir.path().to_path_buf());
+        assert!(scene.is_err());
+        assert!(
+            format!("{:?}", scene.unwrap_err()).contains("cyclic `include`"),
+            "expected a cyclic include error"
+        );
+    }
+
+    #[test]
+    fn including_a_missing_file_should_fail_to_parse() {
+        let input = with_camera_description("- include: does_not_exist.yml");
+
+        assert!(parse(&input, std::env::temp_dir()).is_err());
+    }
+}
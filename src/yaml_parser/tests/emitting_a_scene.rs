@@ -0,0 +1,120 @@
+use super::*;
+
+fn assert_round_trips(input: &str) {
+    let scene = parse(input, Default::default());
+    assert!(scene.is_ok(), "{}", scene.unwrap_err());
+    let scene = scene.unwrap();
+
+    let yaml = scene.to_yaml();
+    let reparsed = parse(&yaml, Default::default());
+    assert!(reparsed.is_ok(), "{}\n{}", reparsed.unwrap_err(), yaml);
+
+    assert_eq!(reparsed.unwrap(), scene, "{}", yaml);
+}
+
+#[test]
+fn the_cover_scene_should_survive_a_round_trip() {
+    assert_round_trips(include_str!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/examples/cover/resources/cover.yml"
+    )));
+}
+
+#[test]
+fn every_example_scene_should_survive_a_round_trip() {
+    vec![
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/examples/dragons/resources/bounding-boxes.yml"
+        )),
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/examples/orrery/resources/orrery.yml"
+        )),
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/examples/reflect_refract/resources/reflect-refract.yml"
+        )),
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/examples/skybox/resources/skybox.yml"
+        )),
+    ]
+    .into_iter()
+    .for_each(assert_round_trips);
+}
+
+#[test]
+fn lights_skies_and_uv_patterns_should_survive_a_round_trip() {
+    let input = "\
+- add: camera
+  width: 64
+  height: 48
+  field-of-view: 1.2
+  from: [ 0, 1.5, -5 ]
+  to: [ 0, 1, 0 ]
+  up: [ 0, 1, 0 ]
+- add: light
+  corner: [ -1, 2, 4 ]
+  uvec: [ 2, 0, 0 ]
+  vvec: [ 0, 2, 0 ]
+  usteps: 4
+  vsteps: 2
+  jitter: false
+  intensity: [ 1.5, 1.5, 1.5 ]
+- add: sky
+  mapping: spherical
+  image:
+    type: image
+    file: sky.png
+- add: cylinder
+  min: -1
+  max: 0.1
+  closed: true
+  shadow: false
+  material:
+    pattern:
+      type: map
+      mapping: cylindrical
+      uv_pattern:
+        type: checkers
+        colors: [ [ 1, 0, 0 ], [ 0, 0, 1 ] ]
+        width: 16
+        height: 8
+      top:
+        type: image
+        file: top.png
+      bottom:
+        type: image
+        file: bottom.png
+      transform:
+        - [ rotate-y, 0.5 ]
+- add: csg
+  operation: difference
+  left:
+    type: cube
+    material:
+      pattern:
+        type: stripes
+        colors: [ [ 1, 1, 1 ], [ 0, 0, 0 ] ]
+        transform:
+          - [ scale, 0.1, 0.1, 0.1 ]
+  right:
+    type: sphere
+    material:
+      pattern:
+        type: map
+        mapping: spherical
+        uv_pattern:
+          type: image
+          file: earth.png
+      transparency: 0.9
+      refractive-index: 1.5
+      emission: [ 0.25, 0.5, 0.75 ]
+  transform:
+    - [ translate, 0.1, 0.2, 0.3 ]
+    - [ rotate-x, 0.7853981633974483 ]
+";
+
+    assert_round_trips(input);
+}
@@ -1,4 +1,6 @@
 use super::*;
+use std::collections::HashMap;
 
 mod basic_parsing;
 mod creating_a_scene;
+mod emitting_a_scene;